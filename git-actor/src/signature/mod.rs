@@ -37,6 +37,34 @@ mod _ref {
     }
 }
 
+mod init {
+    use bstr::BString;
+
+    use crate::{Signature, Time};
+
+    impl Signature {
+        /// Create a signature for `name` and `email` at the current time in the local time zone, or in UTC if the local
+        /// time zone can't be determined.
+        pub fn now(name: impl Into<BString>, email: impl Into<BString>) -> Self {
+            let now = Time::now_local_or_utc();
+            Signature {
+                name: name.into(),
+                email: email.into(),
+                time: Time::new(now.seconds_since_unix_epoch, now.offset_in_seconds),
+            }
+        }
+
+        /// Create a signature for `name` and `email` at the current time in the time zone `offset_in_seconds` east of UTC.
+        pub fn now_with_tz(name: impl Into<BString>, email: impl Into<BString>, offset_in_seconds: i32) -> Self {
+            Signature {
+                name: name.into(),
+                email: email.into(),
+                time: Time::new(Time::now_utc().seconds_since_unix_epoch, offset_in_seconds),
+            }
+        }
+    }
+}
+
 mod convert {
     use crate::{Signature, SignatureRef};
 
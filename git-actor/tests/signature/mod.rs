@@ -77,3 +77,41 @@ fn round_trip() -> Result<(), Box<dyn std::error::Error>> {
     }
     Ok(())
 }
+
+mod now {
+    use git_actor::{Sign, Signature};
+
+    fn seconds_since_unix_epoch() -> u32 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("after epoch")
+            .as_secs() as u32
+    }
+
+    #[test]
+    fn uses_the_current_time() {
+        let before = seconds_since_unix_epoch();
+        let sig = Signature::now("name", "name@example.com");
+        assert_eq!(sig.name, "name");
+        assert_eq!(sig.email, "name@example.com");
+        assert!(sig.time.seconds_since_unix_epoch >= before);
+        assert!(sig.time.seconds_since_unix_epoch <= seconds_since_unix_epoch());
+    }
+
+    #[test]
+    fn with_tz_uses_the_given_offset() {
+        let before = seconds_since_unix_epoch();
+        let sig = Signature::now_with_tz("name", "name@example.com", -7 * 3600);
+        assert_eq!(sig.time.offset_in_seconds, -7 * 3600);
+        assert_eq!(sig.time.sign, Sign::Minus, "the sign follows the offset");
+        assert!(sig.time.seconds_since_unix_epoch >= before);
+
+        let sig = Signature::now_with_tz("name", "name@example.com", 2 * 3600);
+        assert_eq!(sig.time.offset_in_seconds, 2 * 3600);
+        assert_eq!(sig.time.sign, Sign::Plus);
+
+        let mut buf = Vec::new();
+        sig.write_to(&mut buf).expect("in-memory writes don't fail");
+        assert!(buf.ends_with(b" +0200"), "it's serialized with the given offset");
+    }
+}
@@ -45,6 +45,25 @@ pub fn decode(data: &[u8]) -> Result<(Vec, &[u8]), decode::Error> {
     ))
 }
 
+mod write {
+    use std::convert::TryInto;
+
+    impl super::Vec {
+        /// Serialize ourselves into `out` in the same format as [`decode()`][super::decode()] expects.
+        pub fn write_to(&self, mut out: impl std::io::Write) -> std::io::Result<()> {
+            let len: u32 = self.bits.len().try_into().expect("less than 4 billion words");
+            let rlw: u32 = self.rlw.try_into().expect("rlw is an offset into our words");
+            out.write_all(&self.num_bits.to_be_bytes())?;
+            out.write_all(&len.to_be_bytes())?;
+            for word in &self.bits {
+                out.write_all(&word.to_be_bytes())?;
+            }
+            out.write_all(&rlw.to_be_bytes())?;
+            Ok(())
+        }
+    }
+}
+
 mod access {
     use std::convert::{TryFrom, TryInto};
 
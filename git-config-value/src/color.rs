@@ -56,7 +56,8 @@ impl TryFrom<&BStr> for Color {
             Some(
                 Name::from_str(s)
                     .map(ColorItem::Value)
-                    .or_else(|_| Attribute::from_str(s).map(ColorItem::Attr)),
+                    .or_else(|_| Attribute::from_str(s).map(ColorItem::Attr))
+                    .map_err(|_| color_err(s)),
             )
         });
 
@@ -72,12 +73,15 @@ impl TryFrom<&BStr> for Color {
                         } else if background.is_none() {
                             background = Some(v);
                         } else {
-                            return Err(color_err(s));
+                            return Err(Error::new(
+                                "Colors can have at most a foreground and a background color, this one is superfluous",
+                                v.to_string(),
+                            ));
                         }
                     }
                     ColorItem::Attr(a) => attributes |= a,
                 },
-                Err(_) => return Err(color_err(s)),
+                Err(err) => return Err(err),
             }
         }
 
@@ -89,6 +93,14 @@ impl TryFrom<&BStr> for Color {
     }
 }
 
+impl FromStr for Color {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(BStr::new(s))
+    }
+}
+
 impl TryFrom<Cow<'_, BStr>> for Color {
     type Error = Error;
 
@@ -308,6 +320,7 @@ impl FromStr for Attribute {
     type Err = Error;
 
     fn from_str(mut s: &str) -> Result<Self, Self::Err> {
+        let input = s;
         let inverted = if let Some(rest) = s.strip_prefix("no-").or_else(|| s.strip_prefix("no")) {
             s = rest;
             true
@@ -317,7 +330,7 @@ impl FromStr for Attribute {
 
         match s {
             "reset" if !inverted => Ok(Attribute::RESET),
-            "reset" if inverted => Err(color_err(s)),
+            "reset" if inverted => Err(color_err(input)),
             "bold" if !inverted => Ok(Attribute::BOLD),
             "bold" if inverted => Ok(Attribute::NO_BOLD),
             "dim" if !inverted => Ok(Attribute::DIM),
@@ -332,7 +345,7 @@ impl FromStr for Attribute {
             "italic" if inverted => Ok(Attribute::NO_ITALIC),
             "strike" if !inverted => Ok(Attribute::STRIKE),
             "strike" if inverted => Ok(Attribute::NO_STRIKE),
-            _ => Err(color_err(s)),
+            _ => Err(color_err(input)),
         }
     }
 }
@@ -134,23 +134,27 @@ impl serde::Serialize for Suffix {
     }
 }
 
+fn suffix_err(input: impl Into<BString>) -> Error {
+    Error::new("Integer suffixes need to be one of 'k', 'm' or 'g', in any case", input)
+}
+
 impl FromStr for Suffix {
-    type Err = ();
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "k" | "K" => Ok(Self::Kibi),
             "m" | "M" => Ok(Self::Mebi),
             "g" | "G" => Ok(Self::Gibi),
-            _ => Err(()),
+            _ => Err(suffix_err(s)),
         }
     }
 }
 
 impl TryFrom<&BStr> for Suffix {
-    type Error = ();
+    type Error = Error;
 
     fn try_from(s: &BStr) -> Result<Self, Self::Error> {
-        Self::from_str(std::str::from_utf8(s).map_err(|_| ())?)
+        Self::from_str(std::str::from_utf8(s).map_err(|err| suffix_err(s).with_err(err))?)
     }
 }
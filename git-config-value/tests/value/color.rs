@@ -106,6 +106,12 @@ mod attribute {
         assert!(Attribute::from_str("no").is_err());
         assert!(Attribute::from_str("no-").is_err());
     }
+
+    #[test]
+    fn errors_name_the_entire_invalid_attribute() {
+        let err = Attribute::from_str("nobogus").unwrap_err();
+        assert_eq!(err.input, "nobogus", "the prefix isn't stripped");
+    }
 }
 
 mod from_git {
@@ -189,6 +195,32 @@ mod from_git {
         );
     }
 
+    #[test]
+    fn from_str_is_the_same_as_try_from() -> crate::Result {
+        use std::str::FromStr;
+        for input in ["bold cyan", "254 255 bold dim", "#ffffff reset", ""] {
+            assert_eq!(Color::from_str(input)?, Color::try_from(BStr::new(input))?);
+        }
+        assert!(Color::from_str("red green blue").is_err());
+        assert!(Color::from_str("not-a-color").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn errors_name_the_invalid_token() {
+        use std::str::FromStr;
+        let err = Color::from_str("red bold blnk").unwrap_err();
+        assert_eq!(err.input, "blnk");
+        assert!(
+            err.to_string().starts_with("Could not decode 'blnk': "),
+            "the message points to the bad token: {}",
+            err
+        );
+
+        let err = Color::from_str("red green blue").unwrap_err();
+        assert_eq!(err.input, "blue", "the first superfluous color is named");
+    }
+
     fn color<'a>(name: impl Into<&'a BStr>) -> String {
         try_color(name).expect("input color is expected to be valid")
     }
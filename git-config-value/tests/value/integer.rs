@@ -72,3 +72,12 @@ fn as_decimal() {
     assert_eq!(decimal(&format!("{}g", i64::MAX)), None, "overflow results in None");
     assert_eq!(decimal(&format!("{}g", i64::MIN)), None, "underflow results in None");
 }
+
+#[test]
+fn invalid_suffixes_are_named_in_the_error() {
+    use std::str::FromStr;
+    let err = Suffix::from_str("x").unwrap_err();
+    assert_eq!(err.input, "x");
+    assert!(Suffix::try_from(b("kk")).is_err());
+    assert_eq!(Suffix::from_str("K"), Ok(Suffix::Kibi));
+}
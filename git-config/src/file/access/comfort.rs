@@ -2,7 +2,7 @@ use std::{borrow::Cow, convert::TryFrom};
 
 use bstr::BStr;
 
-use crate::{file::MetadataFilter, lookup, parse::section, value, File};
+use crate::{file::MetadataFilter, parse::section, value, File};
 
 /// Comfortable API for accessing values
 impl<'event> File<'event> {
@@ -80,20 +80,24 @@ impl<'event> File<'event> {
         key: impl AsRef<str>,
         filter: &mut MetadataFilter,
     ) -> Option<Result<bool, value::Error>> {
-        let section_name = section_name.as_ref();
-        let key = key.as_ref();
-        match self.raw_value_filter(section_name, subsection_name, key, filter) {
-            Ok(v) => Some(crate::Boolean::try_from(v).map(|b| b.into())),
-            Err(lookup::existing::Error::KeyMissing) => {
-                let section = self
-                    .section_filter(section_name, subsection_name, filter)
-                    .ok()
-                    .flatten()?;
-                let key = section::Key::try_from(key).ok()?;
-                section.key_and_value_range_by(&key).map(|_| Ok(true))
+        let section_ids = self
+            .section_ids_by_name_and_subname(section_name.as_ref(), subsection_name)
+            .ok()?;
+        let key = section::Key::try_from(key.as_ref()).ok()?;
+        for section_id in section_ids.rev() {
+            let section = self.sections.get(&section_id).expect("known section id");
+            if !filter(section.meta()) {
+                continue;
             }
-            Err(_err) => None,
+            if section.key_and_value_range_by(&key).is_none() {
+                continue;
+            }
+            return Some(match section.value(&key) {
+                Some(v) => crate::Boolean::try_from(v).map(|b| b.into()),
+                None => Ok(true),
+            });
         }
+        None
     }
 
     /// Like [`value()`][File::value()], but returning an `Option` if the integer wasn't found.
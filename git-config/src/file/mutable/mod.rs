@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 
-use bstr::{BStr, BString, ByteSlice, ByteVec};
+use bstr::{BStr, BString, ByteSlice};
 
 use crate::{file, parse::Event};
 
@@ -9,32 +9,28 @@ pub(crate) mod section;
 pub(crate) mod value;
 
 fn escape_value(value: &BStr) -> BString {
-    let starts_with_whitespace = value.first().map_or(false, |b| b.is_ascii_whitespace());
-    let ends_with_whitespace = value
-        .get(value.len().saturating_sub(1))
-        .map_or(false, |b| b.is_ascii_whitespace());
-    let contains_comment_indicators = value.find_byteset(b";#").is_some();
-    let quote = starts_with_whitespace || ends_with_whitespace || contains_comment_indicators;
-
-    let mut buf: BString = Vec::with_capacity(value.len()).into();
-    if quote {
-        buf.push(b'"');
-    }
-
-    for b in value.iter().copied() {
-        match b {
-            b'\n' => buf.push_str("\\n"),
-            b'\t' => buf.push_str("\\t"),
-            b'"' => buf.push_str("\\\""),
-            b'\\' => buf.push_str("\\\\"),
-            _ => buf.push(b),
-        }
-    }
+    crate::value::escape(value).into_owned()
+}
 
-    if quote {
-        buf.push(b'"');
-    }
-    buf
+/// Replace the value events of the key-value pair in `range` of `events` with a single event holding `value`,
+/// leaving the key and its separator including the surrounding whitespace untouched.
+///
+/// Returns the new size of the key-value pair, or `None` if there was no separator and value to replace, like with implicit booleans.
+fn replace_value_in_place(
+    events: &mut crate::parse::section::Events<'_>,
+    range: std::ops::Range<usize>,
+    value: &BStr,
+) -> Option<usize> {
+    let pair = &events[range.clone()];
+    let separator_pos = pair.iter().position(|e| matches!(e, Event::KeyValueSeparator))?;
+    let value_start = range.start
+        + separator_pos
+        + pair[separator_pos..]
+            .iter()
+            .position(|e| matches!(e, Event::Value(_) | Event::ValueNotDone(_)))?;
+    events.drain(value_start..range.end);
+    events.insert(value_start, Event::Value(escape_value(value).into()));
+    Some(value_start - range.start + 1)
 }
 
 #[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
@@ -5,7 +5,7 @@ use bstr::{BStr, BString, ByteVec};
 use crate::{
     file::{
         self,
-        mutable::{escape_value, Whitespace},
+        mutable::{escape_value, replace_value_in_place, Whitespace},
         Section, SectionId,
     },
     lookup,
@@ -175,6 +175,10 @@ impl<'borrow, 'lookup, 'event> MultiValueMut<'borrow, 'lookup, 'event> {
         value: &BStr,
     ) {
         let (offset, size) = MultiValueMut::index_and_size(offsets, section_id, offset_index);
+        if let Some(new_size) = replace_value_in_place(&mut section.0, offset..offset + size, value) {
+            MultiValueMut::set_offset(offsets, section_id, offset_index, new_size);
+            return;
+        }
         let whitespace = Whitespace::from_body(section);
         let section = section.as_mut();
         section.drain(offset..offset + size);
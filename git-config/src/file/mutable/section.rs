@@ -9,7 +9,7 @@ use smallvec::SmallVec;
 use crate::{
     file::{
         self,
-        mutable::{escape_value, Whitespace},
+        mutable::{escape_value, replace_value_in_place, Whitespace},
         Index, Section, Size,
     },
     lookup, parse,
@@ -213,6 +213,10 @@ impl<'a, 'event> SectionMut<'a, 'event> {
         self.section.body.0.drain(start.0..end.0);
     }
 
+    pub(crate) fn replace_value(&mut self, index: Index, size: Size, value: &BStr) -> Option<Size> {
+        replace_value_in_place(&mut self.section.body.0, index.0..index.0 + size.0, value).map(Size)
+    }
+
     pub(crate) fn set_internal(&mut self, index: Index, key: Key<'event>, value: &BStr) -> Size {
         let mut size = 0;
 
@@ -35,11 +35,18 @@ impl<'borrow, 'lookup, 'event> ValueMut<'borrow, 'lookup, 'event> {
     /// Update the value to the provided one. This modifies the value such that
     /// the Value event(s) are replaced with a single new event containing the
     /// new value.
+    ///
+    /// The key and the whitespace around the separator are retained as they were.
     pub fn set<'a>(&mut self, input: impl Into<&'a BStr>) {
+        let input = input.into();
         if self.size.0 > 0 {
+            if let Some(size) = self.section.replace_value(self.index, self.size, input) {
+                self.size = size;
+                return;
+            }
             self.section.delete(self.index, self.index + self.size);
         }
-        self.size = self.section.set_internal(self.index, self.key.to_owned(), input.into());
+        self.size = self.section.set_internal(self.index, self.key.to_owned(), input);
     }
 
     /// Removes the value. Does nothing when called multiple times in
@@ -41,6 +41,34 @@ impl<'event> Body<'event> {
         None
     }
 
+    /// Retrieves the first matching value in a section with the given key, if present.
+    ///
+    /// Unlike with [`value()`][Self::value()], a key without key-value separator `=` is returned as `Some(None)`,
+    /// which is how implicit booleans are represented.
+    #[must_use]
+    pub fn first_value_implicit(&self, key: impl AsRef<str>) -> Option<Option<Cow<'_, BStr>>> {
+        let key = Key::from_str_unchecked(key.as_ref());
+        let key_pos = self
+            .0
+            .iter()
+            .position(|e| matches!(e, Event::SectionKey(k) if *k == key))?;
+        let mut has_separator = false;
+        let mut concatenated = BString::default();
+        for event in &self.0[key_pos + 1..] {
+            match event {
+                Event::KeyValueSeparator => has_separator = true,
+                Event::Value(v) => return Some(has_separator.then(|| normalize_bstr(v.as_ref()))),
+                Event::ValueNotDone(v) => concatenated.push_str(v.as_ref()),
+                Event::ValueDone(v) => {
+                    concatenated.push_str(v.as_ref());
+                    return Some(Some(normalize_bstring(concatenated)));
+                }
+                _ => (),
+            }
+        }
+        Some(None)
+    }
+
     /// Retrieves all values that have the provided key name. This may return
     /// an empty vec, which implies there were no values with the provided key.
     #[must_use]
@@ -15,6 +15,9 @@ impl File<'_> {
 
     /// Stream ourselves to the given `out`, in order to reproduce this file mostly losslessly
     /// as it was parsed.
+    ///
+    /// Values that were changed after parsing only affect their own value, all comments, whitespace and other lines
+    /// are written back as they were. The only exception is a missing newline at the end of the last section, which is added.
     pub fn write_to(&self, mut out: impl std::io::Write) -> std::io::Result<()> {
         let nl = self.detect_newline_style();
 
@@ -67,9 +70,13 @@ pub(crate) fn ends_with_newline(e: &[crate::parse::Event<'_>], nl: impl AsRef<[u
         .unwrap_or(false)
 }
 
+/// Return a single newline sequence from `e` if it is a newline event, which may hold multiple consecutive newlines.
 pub(crate) fn extract_newline<'a, 'b>(e: &'a Event<'b>) -> Option<&'a BStr> {
     match e {
-        Event::Newline(b) => b.as_ref().into(),
+        Event::Newline(b) => {
+            let nl_len = if b.starts_with(b"\r\n") { 2 } else { 1 };
+            b.get(..nl_len).map(Into::into)
+        }
         _ => None,
     }
 }
@@ -9,6 +9,66 @@ pub struct Key<'a> {
     pub value_name: &'a str,
 }
 
+impl Key<'_> {
+    /// Return an owned copy of this key, suitable for storage.
+    pub fn to_key_buf(&self) -> KeyBuf {
+        KeyBuf {
+            section_name: self.section_name.into(),
+            subsection_name: self.subsection_name.map(Into::into),
+            value_name: self.value_name.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for Key<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.section_name)?;
+        if let Some(subsection_name) = self.subsection_name {
+            write!(f, ".{}", subsection_name)?;
+        }
+        write!(f, ".{}", self.value_name)
+    }
+}
+
+/// An owned version of [`Key`] to allow keeping parsed keys around.
+#[derive(Debug, PartialEq, Ord, PartialOrd, Eq, Hash, Clone)]
+pub struct KeyBuf {
+    /// The name of the section, like `core` in `core.bare`.
+    pub section_name: String,
+    /// The name of the sub-section, like `origin` in `remote.origin.url`.
+    pub subsection_name: Option<String>,
+    /// The name of the section key, like `url` in `remote.origin.url`.
+    pub value_name: String,
+}
+
+impl KeyBuf {
+    /// Like [`parse_unvalidated()`], but returns an owned key.
+    pub fn parse_unvalidated(input: &str) -> Option<Self> {
+        parse_unvalidated(input).map(|key| key.to_key_buf())
+    }
+
+    /// Return a borrowed version of this key, as used by most accessors.
+    pub fn as_key(&self) -> Key<'_> {
+        Key {
+            section_name: &self.section_name,
+            subsection_name: self.subsection_name.as_deref(),
+            value_name: &self.value_name,
+        }
+    }
+}
+
+impl From<Key<'_>> for KeyBuf {
+    fn from(key: Key<'_>) -> Self {
+        key.to_key_buf()
+    }
+}
+
+impl std::fmt::Display for KeyBuf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_key().fmt(f)
+    }
+}
+
 /// Parse `input` like `core.bare` or `remote.origin.url` as a `Key` to make its fields available,
 /// or `None` if there were not at least 2 tokens separated by `.`.
 /// Note that `input` isn't validated, and is `str` as ascii is a subset of UTF-8 which is required for any valid keys.
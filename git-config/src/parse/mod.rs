@@ -27,7 +27,7 @@ pub mod section;
 
 ///
 mod key;
-pub use key::{parse_unvalidated as key, Key};
+pub use key::{parse_unvalidated as key, Key, KeyBuf};
 
 #[cfg(test)]
 pub(crate) mod tests;
@@ -0,0 +1,51 @@
+use std::borrow::Cow;
+
+use bstr::{BStr, BString, ByteSlice, ByteVec};
+
+/// Escape `value` so that it can be written into a configuration file and reads back as the same value, which is the
+/// inverse of [`normalize()`][crate::value::normalize()].
+///
+/// Values are quoted if they start or end with whitespace, or contain comment characters like `#` or `;`.
+/// Newlines, tabs, quotes and backslashes are escaped with a backslash.
+///
+/// # Examples
+///
+/// Values which don't need escaping are returned borrowed, without allocation.
+///
+/// ```
+/// # use std::borrow::Cow;
+/// # use bstr::ByteSlice;
+/// # use git_config::value::escape;
+/// assert!(matches!(escape(b"hello world".as_bstr()), Cow::Borrowed(_)));
+/// assert_eq!(escape(b"hello world ".as_bstr()).as_ref(), "\"hello world \"");
+/// assert_eq!(escape(b"a \"quote\"".as_bstr()).as_ref(), "a \\\"quote\\\"");
+/// ```
+pub fn escape(value: &BStr) -> Cow<'_, BStr> {
+    let starts_with_whitespace = value.first().map_or(false, |b| b.is_ascii_whitespace());
+    let ends_with_whitespace = value.last().map_or(false, |b| b.is_ascii_whitespace());
+    let contains_comment_indicators = value.find_byteset(b";#").is_some();
+    let quote = starts_with_whitespace || ends_with_whitespace || contains_comment_indicators;
+    if !quote && value.find_byteset(b"\n\t\"\\").is_none() {
+        return Cow::Borrowed(value);
+    }
+
+    let mut buf: BString = Vec::with_capacity(value.len() + 2).into();
+    if quote {
+        buf.push(b'"');
+    }
+
+    for b in value.iter().copied() {
+        match b {
+            b'\n' => buf.push_str("\\n"),
+            b'\t' => buf.push_str("\\t"),
+            b'"' => buf.push_str("\\\""),
+            b'\\' => buf.push_str("\\\\"),
+            _ => buf.push(b),
+        }
+    }
+
+    if quote {
+        buf.push(b'"');
+    }
+    Cow::Owned(buf)
+}
@@ -166,8 +166,8 @@ fn get_value_looks_up_all_sections_before_failing() -> crate::Result {
         "this one can't do it, needs special handling"
     );
     assert!(
-        !file.boolean("core", None, "bool-implicit").expect("present")?,
-        "this should work, but doesn't yet"
+        file.boolean("core", None, "bool-implicit").expect("present")?,
+        "the last value is implicitly true, even though a previous one is explicitly false"
     );
 
     assert!(!file.value::<Boolean>("core", None, "bool-explicit")?.0);
@@ -313,3 +313,28 @@ fn multi_line_value_outer_quotes_escaped_inner_quotes() {
     let expected = r#"!f() {            git status;            git add -A;            git commit -m "$1";            git push -f;            git log -1;          };         f;          unset f"#;
     assert_eq!(config.raw_value("alias", None, "save").unwrap().as_ref(), expected);
 }
+
+#[test]
+fn subsection_names_are_case_sensitive() -> crate::Result {
+    let config = r#"[remote "Origin"]
+        url = upper
+    [remote "origin"]
+        url = lower"#;
+    let file = File::try_from(config)?;
+    let string_by_key = |key: &str| {
+        let key = git_config::parse::key(key).expect("valid key");
+        file.string(key.section_name, key.subsection_name, key.value_name)
+    };
+    assert_eq!(string_by_key("remote.Origin.url").expect("present").as_ref(), "upper");
+    assert_eq!(string_by_key("remote.origin.url").expect("present").as_ref(), "lower");
+    assert_eq!(
+        string_by_key("REMOTE.Origin.URL").expect("present").as_ref(),
+        "upper",
+        "section and value names are case-insensitive"
+    );
+    assert!(
+        string_by_key("remote.ORIGIN.url").is_none(),
+        "but subsection names are matched exactly"
+    );
+    Ok(())
+}
@@ -100,7 +100,7 @@ mod set {
         values.set_all("Hello");
         assert_eq!(
             config.to_string(),
-            "[core]\n    a = Hello\n    [core]\n        a= Hello\n        a =Hello\n"
+            "[core]\n    a = Hello\n    [core]\n        a =Hello\n        a= Hello\n"
         );
         Ok(())
     }
@@ -112,7 +112,7 @@ mod set {
         values.set_all("");
         assert_eq!(
             config.to_string(),
-            "[core]\n    a = \n    [core]\n        a= \n        a =\n"
+            "[core]\n    a = \n    [core]\n        a =\n        a= \n"
         );
         Ok(())
     }
@@ -108,3 +108,55 @@ fn complex_lossless_roundtrip() {
         "Even lossy configuration serializes properly to be able to restore all values"
     );
 }
+
+#[test]
+fn setting_a_value_only_alters_its_own_line() -> crate::Result {
+    let input = r#"# a comment before everything
+
+[user]  ; the section comment
+	name = Some One   # not to be touched
+    ; a comment between values
+	email  =   old@example.com ; keep this comment
+
+[core]
+	# indented comment
+	autocrlf=false
+"#;
+    let mut config = git_config::File::try_from(input)?;
+    config.set_existing_raw_value("user", None, "email", "new@example.com")?;
+
+    let mut out = Vec::new();
+    config.write_to(&mut out)?;
+    let out = out.into_string()?;
+    assert_eq!(
+        out.lines().count(),
+        input.lines().count(),
+        "no line is added or removed"
+    );
+    for (line_number, (actual, expected)) in out.lines().zip(input.lines()).enumerate() {
+        if line_number == 5 {
+            assert_eq!(
+                actual, "\temail  =   new@example.com ; keep this comment",
+                "only the value is replaced, with spacing and comments around it retained"
+            );
+        } else {
+            assert_eq!(actual, expected, "line {} is byte-identical", line_number + 1);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn setting_a_value_in_a_heavily_commented_file_keeps_everything_else_verbatim() -> crate::Result {
+    let input = "; leading comment\r\n# another leading comment\r\n\r\n[core] ; section comment\r\n\t# indented comment\r\n\trepositoryformatversion = 0 # keep me\r\n  \tbare=true\r\n\r\n; between sections\r\n[remote \"origin\"]\r\n\turl = https://example.com/repo.git ;trailing\r\n\tfetch = +refs/heads/*:refs/remotes/origin/* \\\r\n\t\t# not a comment as it continues the value\r\n\r\n# trailing comment\r\n";
+    let mut config = git_config::File::try_from(input)?;
+    config.set_existing_raw_value("remote", Some("origin"), "url", "https://example.org/other.git")?;
+
+    let out = config.to_bstring();
+    let expected = input.replace("https://example.com/repo.git", "https://example.org/other.git");
+    assert_eq!(
+        out, expected,
+        "only the bytes of the value change, while newlines, comments, indentation and continuations are kept"
+    );
+    Ok(())
+}
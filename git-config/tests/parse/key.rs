@@ -37,3 +37,25 @@ fn section_name_subsection_and_key() {
         })
     );
 }
+
+#[test]
+fn owned_keys_can_be_stored_and_borrowed_again() {
+    let key = parse::KeyBuf::parse_unvalidated("remote.origin.url").expect("valid");
+    assert_eq!(key.section_name, "remote");
+    assert_eq!(key.subsection_name.as_deref(), Some("origin"));
+    assert_eq!(key.value_name, "url");
+    assert_eq!(key.as_key(), parse::key("remote.origin.url").unwrap());
+    assert_eq!(
+        key.to_string(),
+        "remote.origin.url",
+        "it displays like the input it was parsed from"
+    );
+
+    let key: parse::KeyBuf = parse::key("core.bare").expect("valid").into();
+    assert_eq!(key.section_name, "core");
+    assert_eq!(key.subsection_name, None);
+    assert_eq!(key.value_name, "bare");
+    assert_eq!(key.as_key().to_string(), "core.bare");
+
+    assert_eq!(parse::KeyBuf::parse_unvalidated("core"), None);
+}
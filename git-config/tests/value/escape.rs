@@ -0,0 +1,44 @@
+use std::borrow::Cow;
+
+use bstr::ByteSlice;
+use git_config::value::{escape, normalize_bstr};
+
+fn assert_roundtrip(value: &str, expected: &str) {
+    let escaped = escape(value.as_bytes().as_bstr());
+    assert_eq!(escaped.as_ref(), expected);
+    assert_eq!(
+        normalize_bstr(escaped.as_ref()).as_ref(),
+        value,
+        "normalization undoes the escaping"
+    );
+}
+
+#[test]
+fn not_modified_is_borrowed() {
+    let cow = escape(b"hello world".as_bstr());
+    assert_eq!(cow.as_ref(), "hello world");
+    assert!(matches!(cow, Cow::Borrowed(_)));
+}
+
+#[test]
+fn trailing_and_leading_whitespace_is_quoted() {
+    assert_roundtrip("value ", "\"value \"");
+    assert_roundtrip("\tvalue", "\"\\tvalue\"");
+}
+
+#[test]
+fn comment_characters_are_quoted() {
+    assert_roundtrip("a # b", "\"a # b\"");
+    assert_roundtrip("a;b", "\"a;b\"");
+}
+
+#[test]
+fn quotes_and_backslashes_are_escaped() {
+    assert_roundtrip("a \"b\"", "a \\\"b\\\"");
+    assert_roundtrip("C:\\path", "C:\\\\path");
+}
+
+#[test]
+fn newlines_are_escaped() {
+    assert_roundtrip("a\nb", "a\\nb");
+}
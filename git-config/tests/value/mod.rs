@@ -1 +1,2 @@
+mod escape;
 mod normalize;
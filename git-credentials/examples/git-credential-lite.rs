@@ -8,14 +8,15 @@ pub fn main() -> Result<(), git_credentials::program::main::Error> {
         std::io::stdout(),
         |action, context| {
             use git_credentials::program::main::Action::*;
-            git_credentials::helper::Cascade::default()
+            let (mut cascade, prompt_options) = git_credentials::helper::Cascade::from_environment();
+            cascade
                 .invoke(
                     match action {
                         Get => git_credentials::helper::Action::Get(context),
                         Erase => git_credentials::helper::Action::Erase(context.to_bstring()),
                         Store => git_credentials::helper::Action::Store(context.to_bstring()),
                     },
-                    git_prompt::Options::default().apply_environment(true, true, true),
+                    prompt_options,
                 )
                 .map(|outcome| outcome.and_then(|outcome| (&outcome.next).try_into().ok()))
         },
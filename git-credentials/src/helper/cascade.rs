@@ -34,6 +34,21 @@ impl Cascade {
         .map(|name| vec![Program::from_custom_definition(name)])
         .unwrap_or_default()
     }
+
+    /// Return a cascade without any programs along with the prompt options to pass to [`invoke()`][Cascade::invoke()],
+    /// configured like `git` would from the environment when no credential helper is set.
+    ///
+    /// - `GIT_ASKPASS` is used as [`askpass`][git_prompt::Options::askpass] program, or `SSH_ASKPASS` if the former isn't set.
+    /// - if `GIT_TERMINAL_PROMPT` is false, the prompt [`mode`][git_prompt::Options::mode] is [`Disable`][git_prompt::Mode::Disable].
+    ///
+    /// Note that askpass programs aren't credential helpers, which is why they are used for prompting instead of being added
+    /// to the [`programs`][Cascade::programs] of the cascade.
+    pub fn from_environment() -> (Self, git_prompt::Options<'static>) {
+        (
+            Cascade::default(),
+            git_prompt::Options::default().apply_environment(true, true, true),
+        )
+    }
 }
 
 /// Builder
@@ -59,6 +74,7 @@ impl Cascade {
     ///
     /// When _getting_ credentials, all programs are asked until the credentials are complete, stopping the cascade.
     /// When _storing_ or _erasing_ all programs are instructed in order.
+    /// Programs are skipped if their [actions][crate::program::Actions] don't include the kind of `action`.
     pub fn invoke(&mut self, mut action: helper::Action, mut prompt: git_prompt::Options<'_>) -> protocol::Result {
         let mut url = action
             .context_mut()
@@ -66,7 +82,8 @@ impl Cascade {
             .transpose()?
             .and_then(|ctx| ctx.url.take());
 
-        for program in &mut self.programs {
+        let kind_of_action = crate::program::Actions::from(&action);
+        for program in self.programs.iter_mut().filter(|p| p.actions.contains(kind_of_action)) {
             program.stderr = self.stderr;
             match helper::invoke::raw(program, &action) {
                 Ok(None) => {}
@@ -81,16 +98,24 @@ impl Cascade {
                             (ctx.host, &mut dst_ctx.host),
                             (ctx.username, &mut dst_ctx.username),
                             (ctx.password, &mut dst_ctx.password),
+                            (ctx.authtype, &mut dst_ctx.authtype),
+                            (ctx.credential, &mut dst_ctx.credential),
                         ] {
                             if let Some(src) = src {
                                 *dst = Some(src);
                             }
                         }
+                        for (key, value) in ctx.unknown_fields {
+                            if !key.ends_with("[]") {
+                                dst_ctx.unknown_fields.retain(|(existing, _)| *existing != key);
+                            }
+                            dst_ctx.unknown_fields.push((key, value));
+                        }
                         if let Some(src) = ctx.url {
                             dst_ctx.url = Some(src);
                             url = dst_ctx.destructure_url_in_place(self.use_http_path)?.url.take();
                         }
-                        if dst_ctx.username.is_some() && dst_ctx.password.is_some() {
+                        if (dst_ctx.username.is_some() && dst_ctx.password.is_some()) || dst_ctx.has_credential() {
                             break;
                         }
                         if ctx.quit.unwrap_or_default() {
@@ -106,7 +131,7 @@ impl Cascade {
         }
 
         if prompt.mode != git_prompt::Mode::Disable {
-            if let Some(ctx) = action.context_mut() {
+            if let Some(ctx) = action.context_mut().filter(|ctx| !ctx.has_credential()) {
                 ctx.url = url;
                 if ctx.username.is_none() {
                     let message = ctx.to_prompt("Username");
@@ -135,6 +160,8 @@ impl Cascade {
             action.context().map(|ctx| helper::Outcome {
                 username: ctx.username.clone(),
                 password: ctx.password.clone(),
+                authtype: ctx.authtype.clone(),
+                credential: ctx.credential.clone(),
                 quit: ctx.quit.unwrap_or(false),
                 next: ctx.to_owned().into(),
             }),
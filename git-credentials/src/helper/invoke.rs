@@ -29,6 +29,8 @@ pub fn invoke(helper: &mut crate::Program, action: &Action) -> Result {
             Ok(Some(Outcome {
                 username: ctx.username,
                 password: ctx.password,
+                authtype: ctx.authtype,
+                credential: ctx.credential,
                 quit: ctx.quit.unwrap_or(false),
                 next: NextAction {
                     previous_output: stdout.into(),
@@ -39,6 +41,18 @@ pub fn invoke(helper: &mut crate::Program, action: &Action) -> Result {
 }
 
 pub(crate) fn raw(helper: &mut crate::Program, action: &Action) -> std::result::Result<Option<Vec<u8>>, Error> {
+    if let crate::program::Kind::InProcess(f) = &helper.kind {
+        return in_process(f, action);
+    }
+    #[cfg(feature = "oauth-refresh")]
+    if let crate::program::Kind::OAuthRefresh {
+        client_id,
+        refresh_token,
+        token_endpoint,
+    } = &helper.kind
+    {
+        return oauth_refresh(client_id, refresh_token.as_str(), token_endpoint, action);
+    }
     let (stdin, stdout) = helper.start(action)?;
     if let (Action::Get(_), None) = (&action, &stdout) {
         panic!("BUG: `Helper` impls must return an output handle to read output from if Action::Get is provided")
@@ -64,3 +78,48 @@ pub(crate) fn raw(helper: &mut crate::Program, action: &Action) -> std::result::
         Some(stdout) => Ok(Some(stdout)),
     }
 }
+
+fn in_process(f: &crate::program::InProcess, action: &Action) -> std::result::Result<Option<Vec<u8>>, Error> {
+    let ctx = match action {
+        Action::Get(ctx) => ctx.clone(),
+        Action::Store(last) | Action::Erase(last) => Context::from_bytes(last)?,
+    };
+    let ctx = f
+        .call(action, ctx)
+        .map_err(|err| Error::CredentialsHelperFailed { source: err })?;
+    match action {
+        Action::Get(_) => {
+            let mut buf = Vec::new();
+            ctx.unwrap_or_default().write_to(&mut buf)?;
+            Ok(Some(buf))
+        }
+        Action::Store(_) | Action::Erase(_) => Ok(None),
+    }
+}
+
+#[cfg(feature = "oauth-refresh")]
+fn oauth_refresh(
+    client_id: &str,
+    refresh_token: &str,
+    token_endpoint: &git_url::Url,
+    action: &Action,
+) -> std::result::Result<Option<Vec<u8>>, Error> {
+    use crate::program::oauth;
+    let mut ctx = match action {
+        Action::Get(ctx) => ctx.clone(),
+        Action::Store(_) | Action::Erase(_) => return Ok(None),
+    };
+    let access_token =
+        oauth::refresh_access_token(client_id, refresh_token, token_endpoint).map_err(|err| match err {
+            // Like unreachable helper programs, unreachable token endpoints shouldn't stop the cascade.
+            oauth::Error::Http(err) => Error::CredentialsHelperFailed {
+                source: std::io::Error::new(std::io::ErrorKind::Other, err),
+            },
+            err => err.into(),
+        })?;
+    ctx.username.get_or_insert_with(|| "oauth2".into());
+    ctx.password = Some(access_token);
+    let mut buf = Vec::new();
+    ctx.write_to(&mut buf)?;
+    Ok(Some(buf))
+}
@@ -21,8 +21,12 @@ pub struct Cascade {
 pub struct Outcome {
     /// The username to use in the identity, if set.
     pub username: Option<String>,
-    /// The username to use in the identity, if set.
+    /// The password to use in the identity, if set.
     pub password: Option<String>,
+    /// The authentication scheme to use with `credential`, if set.
+    pub authtype: Option<String>,
+    /// The pre-encoded credential to use instead of the identity, if set.
+    pub credential: Option<String>,
     /// If set, the helper asked to stop the entire process, whether the identity is complete or not.
     pub quit: bool,
     /// A handle to the action to perform next in another call to [`helper::invoke()`][crate::helper::invoke()].
@@ -42,6 +46,19 @@ impl Outcome {
             .zip(self.password.take())
             .map(|(username, password)| git_sec::identity::Account { username, password })
     }
+
+    /// Try to fetch authtype _and_ credential to form a pre-encoded credential. This will fail if one of them is not set.
+    ///
+    /// This does nothing if only one of the fields is set, or consume both.
+    pub fn consume_credential(&mut self) -> Option<protocol::Credential> {
+        if self.authtype.is_none() || self.credential.is_none() {
+            return None;
+        }
+        self.authtype
+            .take()
+            .zip(self.credential.take())
+            .map(|(authtype, credential)| protocol::Credential { authtype, credential })
+    }
 }
 
 /// The Result type used in [`invoke()`][crate::helper::invoke()].
@@ -50,6 +67,7 @@ pub type Result = std::result::Result<Option<Outcome>, Error>;
 /// The error used in the [credentials helper invocation][crate::helper::invoke()].
 #[derive(Debug, thiserror::Error)]
 #[allow(missing_docs)]
+#[non_exhaustive]
 pub enum Error {
     #[error(transparent)]
     ContextDecode(#[from] protocol::context::decode::Error),
@@ -57,6 +75,9 @@ pub enum Error {
     Io(#[from] std::io::Error),
     #[error(transparent)]
     CredentialsHelperFailed { source: std::io::Error },
+    #[cfg(feature = "oauth-refresh")]
+    #[error("Could not obtain an access token with the OAuth refresh token")]
+    OAuthRefresh(#[from] crate::program::oauth::Error),
 }
 
 /// The action to perform by the credentials [helper][`crate::helper::invoke()`].
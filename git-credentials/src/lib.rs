@@ -16,6 +16,9 @@ pub struct Program {
     pub kind: program::Kind,
     /// If true, stderr is enabled, which is the default.
     pub stderr: bool,
+    /// The actions this program participates in when invoked as part of a [cascade][helper::Cascade], which are all
+    /// of them by default.
+    pub actions: program::Actions,
     /// `Some(…)` if the process is running.
     child: Option<std::process::Child>,
 }
@@ -1,11 +1,84 @@
-use std::process::{Command, Stdio};
+use std::{
+    process::{Command, Stdio},
+    sync::Arc,
+};
 
 use bstr::{BString, ByteSlice, ByteVec};
 
-use crate::{helper, Program};
+use crate::{helper, protocol::Context, Program};
+
+bitflags::bitflags! {
+    /// The [actions][helper::Action] a [`Program`] participates in when invoked as part of a [cascade][helper::Cascade].
+    ///
+    /// This is useful for helpers which only provide credentials but must not be asked to store or erase them,
+    /// like read-only token sources.
+    pub struct Actions: u8 {
+        /// Obtain credentials with [`Action::Get`][helper::Action::Get].
+        const GET = 1 << 0;
+        /// Store credentials with [`Action::Store`][helper::Action::Store].
+        const STORE = 1 << 1;
+        /// Erase credentials with [`Action::Erase`][helper::Action::Erase].
+        const ERASE = 1 << 2;
+    }
+}
+
+impl Default for Actions {
+    fn default() -> Self {
+        Actions::all()
+    }
+}
+
+impl From<&helper::Action> for Actions {
+    fn from(action: &helper::Action) -> Self {
+        match action {
+            helper::Action::Get(_) => Actions::GET,
+            helper::Action::Store(_) => Actions::STORE,
+            helper::Action::Erase(_) => Actions::ERASE,
+        }
+    }
+}
+
+/// The signature of functions implementing the credential helper protocol in-process, see [`InProcess`].
+pub type InProcessFn = dyn Fn(&helper::Action, Context) -> std::io::Result<Option<Context>> + Send + Sync;
+
+/// A credentials helper implemented by a function which is called instead of spawning a process.
+///
+/// It receives the action to perform along with the context it carries, which for [`Store`][helper::Action::Store] and
+/// [`Erase`][helper::Action::Erase] is decoded from the output of a previous invocation.
+/// Its output is only used when getting credentials, and an error is treated like a failing helper program.
+#[derive(Clone)]
+pub struct InProcess(Arc<InProcessFn>);
+
+impl InProcess {
+    /// Create a new instance which calls `f` on each invocation.
+    pub fn new(
+        f: impl Fn(&helper::Action, Context) -> std::io::Result<Option<Context>> + Send + Sync + 'static,
+    ) -> Self {
+        InProcess(Arc::new(f))
+    }
+
+    pub(crate) fn call(&self, action: &helper::Action, ctx: Context) -> std::io::Result<Option<Context>> {
+        (self.0)(action, ctx)
+    }
+}
+
+impl std::fmt::Debug for InProcess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("InProcess(..)")
+    }
+}
+
+impl PartialEq for InProcess {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for InProcess {}
 
 /// The kind of helper program to use.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum Kind {
     /// The built-in `git credential` helper program, part of any `git` distribution.
     Builtin,
@@ -23,6 +96,24 @@ pub enum Kind {
     },
     /// A script to execute with `sh`.
     ExternalShellScript(BString),
+    /// A function called in the current process, useful for testing or when embedding credential handling.
+    InProcess(InProcess),
+    /// Obtain a new access token for each [`Get`][helper::Action::Get] action by exchanging the `refresh_token` of `client_id`
+    /// at the `token_endpoint` of an OAuth authorization server.
+    ///
+    /// The access token is returned as password, along with the username `oauth2` unless one is already set.
+    /// If the refresh token is rejected, for instance because it expired, the error is returned to the caller instead of trying
+    /// the next program in the [cascade][helper::Cascade]. [`Store`][helper::Action::Store] and [`Erase`][helper::Action::Erase]
+    /// actions are ignored.
+    #[cfg(feature = "oauth-refresh")]
+    OAuthRefresh {
+        /// The identifier of the OAuth application the `refresh_token` was issued to.
+        client_id: String,
+        /// The long-lived token to exchange for short-lived access tokens.
+        refresh_token: oauth::RefreshToken,
+        /// The url to post the token request to.
+        token_endpoint: git_url::Url,
+    },
 }
 
 /// Initialization
@@ -33,6 +124,7 @@ impl Program {
             kind,
             child: None,
             stderr: true,
+            actions: Actions::default(),
         }
     }
 
@@ -61,6 +153,7 @@ impl Program {
             kind,
             child: None,
             stderr: true,
+            actions: Actions::default(),
         }
     }
 }
@@ -72,6 +165,13 @@ impl Program {
         self.stderr = false;
         self
     }
+
+    /// Only participate in the given `actions` when invoked as part of a [cascade][helper::Cascade], for example
+    /// [`Actions::GET`] for helpers which must not be asked to store or erase credentials.
+    pub fn with_actions(mut self, actions: Actions) -> Self {
+        self.actions = actions;
+        self
+    }
 }
 
 impl Program {
@@ -81,6 +181,9 @@ impl Program {
     ) -> std::io::Result<(std::process::ChildStdin, Option<std::process::ChildStdout>)> {
         assert!(self.child.is_none(), "BUG: must not call `start()` twice");
         let mut cmd = match &self.kind {
+            Kind::InProcess(_) => unreachable!("BUG: in-process helpers are called directly"),
+            #[cfg(feature = "oauth-refresh")]
+            Kind::OAuthRefresh { .. } => unreachable!("BUG: OAuth refresh helpers are called directly"),
             Kind::Builtin => {
                 let mut cmd = Command::new(cfg!(windows).then(|| "git.exe").unwrap_or("git"));
                 cmd.arg("credential").arg(action.as_arg(false));
@@ -126,6 +229,10 @@ impl Program {
     }
 }
 
+///
+#[cfg(feature = "oauth-refresh")]
+pub mod oauth;
+
 ///
 pub mod main;
 pub use main::function::main;
@@ -0,0 +1,130 @@
+use bstr::ByteSlice;
+
+/// The error returned when obtaining an access token with [`Kind::OAuthRefresh`][crate::program::Kind::OAuthRefresh] fails.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The refresh token was rejected by the token endpoint as it is invalid, expired or revoked, and must be renewed")]
+    InvalidGrant { description: Option<String> },
+    #[error("The token endpoint rejected the request with error '{error}'")]
+    Endpoint { error: String, description: Option<String> },
+    #[error("The token endpoint responded with HTTP status {status}")]
+    HttpStatus { status: u32 },
+    #[error("The response of the token endpoint could not be parsed")]
+    Decode(#[from] serde_json::Error),
+    #[error("The response of the token endpoint did not contain an access token")]
+    MissingAccessToken,
+    #[error("Refusing to send the refresh token to {url:?} as token endpoints must use https")]
+    InsecureTokenEndpoint { url: String },
+    #[error(transparent)]
+    Http(#[from] curl::Error),
+}
+
+/// A refresh token for use in [`Kind::OAuthRefresh`][crate::program::Kind::OAuthRefresh], which is never shown in its
+/// `Debug` representation.
+#[derive(Clone, Eq, PartialEq)]
+pub struct RefreshToken(String);
+
+impl RefreshToken {
+    /// Return the token itself.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for RefreshToken {
+    fn from(token: String) -> Self {
+        RefreshToken(token)
+    }
+}
+
+impl From<&str> for RefreshToken {
+    fn from(token: &str) -> Self {
+        RefreshToken(token.into())
+    }
+}
+
+impl std::fmt::Debug for RefreshToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+/// The time we wait for a connection to the token endpoint to be established.
+const CONNECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(20);
+/// The time we wait for the whole request to complete.
+const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Return true if `url` is safe to send a refresh token to, which is the case for `https` and for `http` on the loopback
+/// device, the latter being useful for testing.
+fn is_secure(url: &git_url::Url) -> bool {
+    match url.scheme {
+        git_url::Scheme::Https => true,
+        git_url::Scheme::Http => url.host_for_matching().map_or(false, |host| {
+            host == "localhost"
+                || host
+                    .parse::<std::net::IpAddr>()
+                    .map_or(false, |addr| addr.is_loopback())
+        }),
+        _ => false,
+    }
+}
+
+/// Exchange `refresh_token` of `client_id` for a new access token by posting to `token_endpoint`, as specified in
+/// [RFC 6749, section 6](https://www.rfc-editor.org/rfc/rfc6749#section-6).
+///
+/// `token_endpoint` must use `https`, unless it's on the loopback device.
+pub(crate) fn refresh_access_token(
+    client_id: &str,
+    refresh_token: &str,
+    token_endpoint: &git_url::Url,
+) -> Result<String, Error> {
+    if !is_secure(token_endpoint) {
+        return Err(Error::InsecureTokenEndpoint {
+            url: token_endpoint.to_bstring().to_string(),
+        });
+    }
+    let mut easy = curl::easy::Easy::new();
+    easy.connect_timeout(CONNECT_TIMEOUT)?;
+    easy.timeout(TIMEOUT)?;
+    let body = format!(
+        "grant_type=refresh_token&refresh_token={}&client_id={}",
+        easy.url_encode(refresh_token.as_bytes()),
+        easy.url_encode(client_id.as_bytes())
+    );
+    easy.url(&token_endpoint.to_bstring().to_str_lossy())?;
+    easy.post(true)?;
+    easy.post_fields_copy(body.as_bytes())?;
+    let mut headers = curl::easy::List::new();
+    headers.append("Accept: application/json")?;
+    easy.http_headers(headers)?;
+
+    let mut response = Vec::new();
+    {
+        let mut transfer = easy.transfer();
+        transfer.write_function(|data| {
+            response.extend_from_slice(data);
+            Ok(data.len())
+        })?;
+        transfer.perform()?;
+    }
+    let status = easy.response_code()?;
+
+    let response: serde_json::Value = match serde_json::from_slice(&response) {
+        Ok(response) => response,
+        Err(_) if !(200..300).contains(&status) => return Err(Error::HttpStatus { status }),
+        Err(err) => return Err(err.into()),
+    };
+    let field = |name: &str| response.get(name).and_then(|v| v.as_str()).map(ToOwned::to_owned);
+    match field("error") {
+        Some(error) if error == "invalid_grant" => Err(Error::InvalidGrant {
+            description: field("error_description"),
+        }),
+        Some(error) => Err(Error::Endpoint {
+            error,
+            description: field("error_description"),
+        }),
+        None if !(200..300).contains(&status) => Err(Error::HttpStatus { status }),
+        None => field("access_token").ok_or(Error::MissingAccessToken),
+    }
+}
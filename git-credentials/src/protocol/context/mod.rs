@@ -34,6 +34,11 @@ mod access {
             }
             buf.into()
         }
+        /// Return `true` if a pre-encoded credential along with its authtype is present, and if we advertised our
+        /// support for it with the `authtype` capability.
+        pub fn has_credential(&self) -> bool {
+            self.authtype.is_some() && self.credential.is_some() && self.capability.iter().any(|c| c == "authtype")
+        }
         /// Compute a prompt to obtain the given value.
         pub fn to_prompt(&self, field: &str) -> String {
             match self.to_url() {
@@ -17,6 +17,11 @@ mod write {
                 out.write_all(value)?;
                 out.write_all(b"\n")
             }
+            for capability in &self.capability {
+                validate("capability[]", capability.as_str().into())
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+                write_key(&mut out, "capability[]", capability.as_bytes().as_bstr())?;
+            }
             for (key, value) in [("url", &self.url), ("path", &self.path)] {
                 if let Some(value) = value {
                     validate(key, value.as_slice().into())
@@ -29,6 +34,8 @@ mod write {
                 ("host", &self.host),
                 ("username", &self.username),
                 ("password", &self.password),
+                ("authtype", &self.authtype),
+                ("credential", &self.credential),
             ] {
                 if let Some(value) = value {
                     validate(key, value.as_str().into())
@@ -36,6 +43,10 @@ mod write {
                     write_key(&mut out, key, value.as_bytes().as_bstr())?;
                 }
             }
+            for (key, value) in &self.unknown_fields {
+                validate(key, value.as_ref()).map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+                write_key(&mut out, key, value.as_ref())?;
+            }
             Ok(())
         }
 
@@ -83,16 +94,27 @@ pub mod decode {
             }) {
                 let (key, value) = res?;
                 match key {
-                    "protocol" | "host" | "username" | "password" => {
+                    "protocol" | "host" | "username" | "password" | "authtype" | "credential" | "capability[]" => {
                         if !value.is_utf8() {
                             return Err(Error::IllformedUtf8InValue { key: key.into(), value });
                         }
                         let value = value.to_string();
+                        if key == "capability[]" {
+                            // an empty value resets the list, just like git does it.
+                            if value.is_empty() {
+                                ctx.capability.clear();
+                            } else {
+                                ctx.capability.push(value);
+                            }
+                            continue;
+                        }
                         *match key {
                             "protocol" => &mut ctx.protocol,
                             "host" => &mut ctx.host,
                             "username" => &mut ctx.username,
                             "password" => &mut ctx.password,
+                            "authtype" => &mut ctx.authtype,
+                            "credential" => &mut ctx.credential,
                             _ => unreachable!("checked field names in match above"),
                         } = Some(value);
                     }
@@ -103,7 +125,7 @@ pub mod decode {
                             .ok()
                             .map(|b| b.into());
                     }
-                    _ => {}
+                    _ => ctx.unknown_fields.push((key.into(), value)),
                 }
             }
             Ok(ctx)
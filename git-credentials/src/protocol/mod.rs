@@ -6,11 +6,25 @@ use crate::helper;
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Outcome {
     /// The identity provide by the helper.
-    pub identity: git_sec::identity::Account,
+    ///
+    /// It's only `None` if a pre-encoded [`credential`][Outcome::credential] was provided instead, which can only happen if
+    /// the `authtype` capability was advertised in the [`Context`].
+    pub identity: Option<git_sec::identity::Account>,
+    /// A pre-encoded credential along with its authentication scheme, like a bearer token, if provided by the helper.
+    pub credential: Option<Credential>,
     /// A handle to the action to perform next in another call to [`helper::invoke()`][crate::helper::invoke()].
     pub next: helper::NextAction,
 }
 
+/// A pre-encoded credential to be used as is, for example to be sent in an HTTP `Authorization` header.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Credential {
+    /// The authentication scheme, like `Bearer` or `Basic`.
+    pub authtype: String,
+    /// The credential itself, like a bearer token, which is to be used along with `authtype`.
+    pub credential: String,
+}
+
 /// The Result type used in credentials top-level functions to obtain a complete identity.
 pub type Result = std::result::Result<Option<Outcome>, Error>;
 
@@ -48,35 +62,58 @@ pub struct Context {
     pub username: Option<String>,
     /// The credential’s password, if we are asking it to be stored.
     pub password: Option<String>,
+    /// The capabilities the sender supports, like `authtype`, each of which is sent as `capability[]`.
+    ///
+    /// Helpers may only provide an [`authtype`][Context::authtype] and [`credential`][Context::credential] if the `authtype`
+    /// capability was advertised to them.
+    pub capability: Vec<String>,
+    /// The authentication scheme to use with [`credential`][Context::credential], like `Bearer`.
+    pub authtype: Option<String>,
+    /// A pre-encoded credential like a bearer token, to be used with the [`authtype`][Context::authtype] scheme instead of
+    /// `username` and `password`.
+    pub credential: Option<String>,
     /// When this special attribute is read by git credential, the value is parsed as a URL and treated as if its constituent
     /// parts were read (e.g., url=<https://example.com> would behave as if
     /// protocol=https and host=example.com had been provided). This can help callers avoid parsing URLs themselves.
     pub url: Option<BString>,
     /// If true, the caller should stop asking for credentials immediately without calling more credential helpers in the chain.
     pub quit: Option<bool>,
+    /// All fields we don't know, in order, so they can be passed on unchanged.
+    ///
+    /// Keys of fields that may be repeated retain their `[]` suffix.
+    pub unknown_fields: Vec<(String, BString)>,
 }
 
 /// Convert the outcome of a helper invocation to a helper result, assuring that the identity is complete in the process.
 pub fn helper_outcome_to_result(outcome: Option<helper::Outcome>, action: helper::Action) -> Result {
     fn redact(mut ctx: Context) -> Context {
-        if let Some(pw) = ctx.password.as_mut() {
-            *pw = "<redacted>".into()
+        for secret in ctx.password.iter_mut().chain(ctx.credential.iter_mut()) {
+            *secret = "<redacted>".into()
         }
         ctx
     }
     match (action, outcome) {
         (helper::Action::Get(ctx), None) => Err(Error::IdentityMissing { context: redact(ctx) }),
-        (helper::Action::Get(ctx), Some(mut outcome)) => match outcome.consume_identity() {
-            Some(identity) => Ok(Some(Outcome {
-                identity,
-                next: outcome.next,
-            })),
-            None => Err(if outcome.quit {
-                Error::Quit
-            } else {
-                Error::IdentityMissing { context: redact(ctx) }
-            }),
-        },
+        (helper::Action::Get(ctx), Some(mut outcome)) => {
+            let credential = ctx
+                .capability
+                .iter()
+                .any(|c| c == "authtype")
+                .then(|| outcome.consume_credential())
+                .flatten();
+            match (outcome.consume_identity(), credential) {
+                (None, None) => Err(if outcome.quit {
+                    Error::Quit
+                } else {
+                    Error::IdentityMissing { context: redact(ctx) }
+                }),
+                (identity, credential) => Ok(Some(Outcome {
+                    identity,
+                    credential,
+                    next: outcome.next,
+                })),
+            }
+        }
         (helper::Action::Store(_) | helper::Action::Erase(_), _ignore) => Ok(None),
     }
 }
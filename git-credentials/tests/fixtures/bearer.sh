@@ -0,0 +1,7 @@
+#!/bin/bash
+
+echo capability[]=authtype
+echo authtype=Bearer
+echo credential=token
+echo wwwauth[]=Bearer realm=\"example\"
+echo x-custom=value
@@ -1,3 +1,39 @@
+mod from_environment {
+    use std::path::Path;
+
+    use git_credentials::helper::Cascade;
+    use git_testtools::Env;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn askpass_programs_and_terminal_prompt_configure_prompting() {
+        let _env = Env::new()
+            .set("GIT_ASKPASS", "git-askpass")
+            .set("SSH_ASKPASS", "ssh-askpass")
+            .set("GIT_TERMINAL_PROMPT", "false");
+        let (cascade, prompt) = Cascade::from_environment();
+        assert!(
+            cascade.programs.is_empty(),
+            "askpass programs aren't credential helpers"
+        );
+        assert_eq!(prompt.askpass.as_deref(), Some(Path::new("git-askpass")));
+        assert_eq!(prompt.mode, git_prompt::Mode::Disable);
+    }
+
+    #[test]
+    #[serial]
+    fn ssh_askpass_is_used_as_fallback() {
+        let _env = Env::new()
+            .unset("GIT_ASKPASS")
+            .set("SSH_ASKPASS", "ssh-askpass")
+            .set("GIT_TERMINAL_PROMPT", "true");
+        let (_cascade, prompt) = Cascade::from_environment();
+        assert_eq!(prompt.askpass.as_deref(), Some(Path::new("ssh-askpass")));
+        assert_eq!(prompt.mode, git_prompt::Mode::Hidden, "the default mode is kept");
+    }
+}
+
 mod invoke {
     use std::convert::TryInto;
 
@@ -19,7 +55,7 @@ mod invoke {
         )
         .unwrap()
         .expect("credentials");
-        assert_eq!(actual.identity, identity("user", "pass"));
+        assert_eq!(actual.identity, Some(identity("user", "pass")));
     }
 
     #[test]
@@ -27,7 +63,7 @@ mod invoke {
         let actual = invoke_cascade(["username", "custom-helper"], Action::get_for_url("does/not/matter"))
             .unwrap()
             .expect("credentials");
-        assert_eq!(actual.identity, identity("user-script", "pass-script"));
+        assert_eq!(actual.identity, Some(identity("user-script", "pass-script")));
     }
 
     #[test]
@@ -35,7 +71,7 @@ mod invoke {
         let actual = invoke_cascade(["fail", "custom-helper"], action_get())
             .unwrap()
             .expect("credentials");
-        assert_eq!(actual.identity, identity("user-script", "pass-script"));
+        assert_eq!(actual.identity, Some(identity("user-script", "pass-script")));
     }
 
     #[test]
@@ -110,7 +146,53 @@ mod invoke {
             .unwrap()
             .expect("credentials");
 
-        assert_eq!(actual.identity, identity("user", "pass"));
+        assert_eq!(actual.identity, Some(identity("user", "pass")));
+    }
+
+    #[test]
+    fn helpers_can_provide_a_pre_encoded_credential_if_the_authtype_capability_is_advertised() {
+        let mut action = Action::get_for_url("https://example.com");
+        action
+            .context_mut()
+            .expect("get action")
+            .capability
+            .push("authtype".into());
+        let actual = invoke_cascade(["bearer", "custom-helper"], action)
+            .unwrap()
+            .expect("credentials");
+
+        assert_eq!(
+            actual.identity, None,
+            "the cascade stops once the credential is complete"
+        );
+        assert_eq!(
+            actual.credential,
+            Some(protocol::Credential {
+                authtype: "Bearer".into(),
+                credential: "token".into()
+            })
+        );
+        let ctx: Context = (&actual.next).try_into().unwrap();
+        assert_eq!(ctx.authtype.as_deref(), Some("Bearer"));
+        assert_eq!(ctx.credential.as_deref(), Some("token"));
+        assert_eq!(
+            ctx.unknown_fields,
+            vec![
+                ("wwwauth[]".into(), r#"Bearer realm="example""#.into()),
+                ("x-custom".into(), "value".into())
+            ],
+            "unknown fields are passed through"
+        );
+    }
+
+    #[test]
+    fn pre_encoded_credentials_are_ignored_without_the_authtype_capability() {
+        let actual = invoke_cascade(["bearer", "custom-helper"], Action::get_for_url("https://example.com"))
+            .unwrap()
+            .expect("credentials");
+
+        assert_eq!(actual.identity, Some(identity("user-script", "pass-script")));
+        assert_eq!(actual.credential, None);
     }
 
     fn action_get() -> Action {
@@ -146,3 +228,155 @@ mod invoke {
             .collect()
     }
 }
+
+mod in_process {
+    use std::sync::{Arc, Mutex};
+
+    use git_credentials::{
+        helper::{Action, Cascade},
+        program,
+        program::{InProcess, Kind},
+        protocol::Context,
+        Program,
+    };
+    use git_sec::identity::Account;
+
+    fn no_prompt() -> git_prompt::Options<'static> {
+        git_prompt::Options {
+            mode: git_prompt::Mode::Disable,
+            askpass: None,
+        }
+    }
+
+    fn helper(f: impl Fn(&Action, Context) -> std::io::Result<Option<Context>> + Send + Sync + 'static) -> Program {
+        Program::from_kind(Kind::InProcess(InProcess::new(f)))
+    }
+
+    #[test]
+    fn credentials_are_filled_in_one_by_one_and_stop_when_complete() {
+        let actual = Cascade::default()
+            .extend([
+                helper(|_action, _ctx| {
+                    Ok(Some(Context {
+                        username: Some("user".into()),
+                        ..Default::default()
+                    }))
+                }),
+                helper(|_action, ctx| {
+                    assert_eq!(ctx.username.as_deref(), Some("user"), "previous values are passed on");
+                    Ok(Some(Context {
+                        password: Some("pass".into()),
+                        ..Default::default()
+                    }))
+                }),
+                helper(|_action, _ctx| unreachable!("credentials are complete")),
+            ])
+            .invoke(Action::get_for_url("https://example.com/repo"), no_prompt())
+            .unwrap()
+            .expect("credentials");
+        assert_eq!(
+            actual.identity,
+            Some(Account {
+                username: "user".into(),
+                password: "pass".into()
+            })
+        );
+    }
+
+    #[test]
+    fn failing_helpers_are_skipped() {
+        let actual = Cascade::default()
+            .extend([
+                helper(|_action, _ctx| Err(std::io::Error::new(std::io::ErrorKind::Other, "fails"))),
+                helper(|_action, _ctx| {
+                    Ok(Some(Context {
+                        username: Some("user".into()),
+                        password: Some("pass".into()),
+                        ..Default::default()
+                    }))
+                }),
+            ])
+            .invoke(Action::get_for_url("https://example.com/repo"), no_prompt())
+            .unwrap()
+            .expect("credentials");
+        assert_eq!(actual.identity.expect("complete").username, "user");
+    }
+
+    #[test]
+    fn helpers_are_only_invoked_for_the_actions_they_participate_in() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let recording_helper = |name: &'static str| {
+            let calls = Arc::clone(&calls);
+            helper(move |action, _ctx| {
+                calls.lock().unwrap().push((name, action.as_arg(true).to_owned()));
+                Ok(matches!(action, Action::Get(_)).then(|| Context {
+                    username: Some("user".into()),
+                    password: Some("pass".into()),
+                    ..Default::default()
+                }))
+            })
+        };
+        let mut cascade = Cascade::default().extend([
+            recording_helper("get-only").with_actions(program::Actions::GET),
+            recording_helper("all"),
+        ]);
+
+        let outcome = cascade
+            .invoke(Action::get_for_url("https://example.com/repo"), no_prompt())
+            .unwrap()
+            .expect("credentials");
+        assert!(cascade
+            .invoke(outcome.next.clone().store(), no_prompt())
+            .unwrap()
+            .is_none());
+        assert!(cascade.invoke(outcome.next.erase(), no_prompt()).unwrap().is_none());
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                ("get-only", "get".to_string()),
+                ("all", "store".into()),
+                ("all", "erase".into()),
+            ],
+            "the get-only helper provides the credentials but isn't asked to store or erase them"
+        );
+    }
+
+    #[test]
+    fn store_and_erase_are_dispatched_with_the_previous_context() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mut cascade = Cascade::default().extend([helper({
+            let calls = Arc::clone(&calls);
+            move |action, ctx| {
+                calls
+                    .lock()
+                    .unwrap()
+                    .push((action.as_arg(true).to_owned(), ctx.username.clone()));
+                Ok(matches!(action, Action::Get(_)).then(|| Context {
+                    username: Some("user".into()),
+                    password: Some("pass".into()),
+                    ..Default::default()
+                }))
+            }
+        })]);
+
+        let outcome = cascade
+            .invoke(Action::get_for_url("https://example.com/repo"), no_prompt())
+            .unwrap()
+            .expect("credentials");
+        assert!(cascade
+            .invoke(outcome.next.clone().store(), no_prompt())
+            .unwrap()
+            .is_none());
+        assert!(cascade.invoke(outcome.next.erase(), no_prompt()).unwrap().is_none());
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                ("get".to_string(), None),
+                ("store".into(), Some("user".into())),
+                ("erase".into(), Some("user".into())),
+            ]
+        );
+    }
+}
@@ -12,6 +12,19 @@ fn encode_decode_roundtrip_works_only_for_serializing_fields() {
             url: Some("https://github.com/byron/gitoxide".into()),
             ..Default::default()
         },
+        Context {
+            protocol: Some("https".into()),
+            host: Some("github.com".into()),
+            capability: vec!["authtype".into(), "state".into()],
+            authtype: Some("Bearer".into()),
+            credential: Some("token".into()),
+            unknown_fields: vec![
+                ("wwwauth[]".into(), "Basic realm=\"example\"".into()),
+                ("wwwauth[]".into(), "Bearer".into()),
+                ("future".into(), "value".into()),
+            ],
+            ..Default::default()
+        },
         Context::default(),
     ] {
         let mut buf = Vec::<u8>::new();
@@ -78,15 +91,41 @@ username=bob";
     }
 
     #[test]
-    fn unknown_field_names_are_skipped() {
+    fn unknown_field_names_are_retained_in_order() {
         let input = b"protocol=https
 unknown=value
-username=bob";
+username=bob
+wwwauth[]=Basic
+wwwauth[]=Bearer";
         assert_eq!(
             Context::from_bytes(input).unwrap(),
             Context {
                 protocol: Some("https".into()),
                 username: Some("bob".into()),
+                unknown_fields: vec![
+                    ("unknown".into(), "value".into()),
+                    ("wwwauth[]".into(), "Basic".into()),
+                    ("wwwauth[]".into(), "Bearer".into()),
+                ],
+                ..Default::default()
+            }
+        )
+    }
+
+    #[test]
+    fn capabilities_accumulate_and_are_reset_by_an_empty_value() {
+        let input = b"capability[]=authtype
+capability[]=
+capability[]=state
+capability[]=authtype
+authtype=Bearer
+credential=token";
+        assert_eq!(
+            Context::from_bytes(input).unwrap(),
+            Context {
+                capability: vec!["state".into(), "authtype".into()],
+                authtype: Some("Bearer".into()),
+                credential: Some("token".into()),
                 ..Default::default()
             }
         )
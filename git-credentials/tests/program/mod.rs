@@ -1 +1,3 @@
 mod from_custom_definition;
+#[cfg(feature = "oauth-refresh")]
+mod oauth_refresh;
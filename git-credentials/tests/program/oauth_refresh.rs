@@ -0,0 +1,149 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+};
+
+use git_credentials::{
+    helper::{Action, Cascade, Error},
+    program::{oauth, Kind},
+    protocol, Program,
+};
+
+/// Serve a single request with `status` and the json `body`, returning the url to connect to and a handle to obtain
+/// the body of the request.
+fn serve_once(status: &str, body: &'static str) -> (String, std::thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/token", listener.local_addr().unwrap());
+    let status = status.to_owned();
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                content_length = len.trim().parse().unwrap();
+            }
+        }
+        let mut request_body = vec![0; content_length];
+        reader.read_exact(&mut request_body).unwrap();
+        write!(
+            reader.get_mut(),
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+        .unwrap();
+        String::from_utf8(request_body).unwrap()
+    });
+    (url, handle)
+}
+
+fn program(token_endpoint: &str) -> Program {
+    Program::from_kind(Kind::OAuthRefresh {
+        client_id: "client".into(),
+        refresh_token: "refresh/token".into(),
+        token_endpoint: git_url::parse(token_endpoint.into()).unwrap(),
+    })
+}
+
+#[test]
+fn access_token_is_returned_as_password() -> crate::Result {
+    let (url, server) = serve_once("200 OK", r#"{"access_token":"new-token","token_type":"bearer"}"#);
+    let outcome = git_credentials::helper::invoke(&mut program(&url), &Action::get_for_url("https://example.com"))?
+        .expect("credentials");
+    assert_eq!(outcome.username.as_deref(), Some("oauth2"));
+    assert_eq!(outcome.password.as_deref(), Some("new-token"));
+    assert_eq!(
+        server.join().unwrap(),
+        "grant_type=refresh_token&refresh_token=refresh%2Ftoken&client_id=client"
+    );
+    Ok(())
+}
+
+#[test]
+fn store_and_erase_are_ignored() -> crate::Result {
+    let mut program = program("http://127.0.0.1:1/unused");
+    assert!(git_credentials::helper::invoke(&mut program, &Action::Store("url=https://example.com".into()))?.is_none());
+    assert!(git_credentials::helper::invoke(&mut program, &Action::Erase("url=https://example.com".into()))?.is_none());
+    Ok(())
+}
+
+#[test]
+fn expired_refresh_tokens_fail_the_cascade() {
+    let (url, _server) = serve_once(
+        "400 Bad Request",
+        r#"{"error":"invalid_grant","error_description":"expired"}"#,
+    );
+    let err = Cascade::default()
+        .extend(Some(program(&url)))
+        .invoke(
+            Action::get_for_url("https://example.com"),
+            git_prompt::Options {
+                mode: git_prompt::Mode::Disable,
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    match err {
+        protocol::Error::InvokeHelper(Error::OAuthRefresh(oauth::Error::InvalidGrant { description })) => {
+            assert_eq!(description.as_deref(), Some("expired"))
+        }
+        err => panic!("unexpected error: {:?}", err),
+    }
+}
+
+#[test]
+fn other_endpoint_errors_are_reported() {
+    let (url, _server) = serve_once("401 Unauthorized", r#"{"error":"invalid_client"}"#);
+    let err =
+        git_credentials::helper::invoke(&mut program(&url), &Action::get_for_url("https://example.com")).unwrap_err();
+    assert!(
+        matches!(err, Error::OAuthRefresh(oauth::Error::Endpoint { ref error, .. }) if error == "invalid_client"),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn token_endpoints_must_use_https_unless_on_the_loopback_device() {
+    for url in [
+        "http://example.com/token",
+        "git://example.com/token",
+        "ssh://example.com/token",
+    ] {
+        let err = git_credentials::helper::invoke(&mut program(url), &Action::get_for_url("https://example.com"))
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::OAuthRefresh(oauth::Error::InsecureTokenEndpoint { .. })),
+            "{}: {:?}",
+            url,
+            err
+        );
+    }
+    for url in [
+        "http://localhost:1/token",
+        "http://127.0.0.1:1/token",
+        "http://[::1]:1/token",
+    ] {
+        let err = git_credentials::helper::invoke(&mut program(url), &Action::get_for_url("https://example.com"))
+            .unwrap_err();
+        assert!(
+            !matches!(err, Error::OAuthRefresh(oauth::Error::InsecureTokenEndpoint { .. })),
+            "{}: loopback endpoints are allowed, but nobody listens: {:?}",
+            url,
+            err
+        );
+    }
+}
+
+#[test]
+fn the_refresh_token_is_not_shown_in_debug_output() {
+    let program = program("https://example.com/token");
+    assert!(!format!("{:?}", program).contains("refresh/token"));
+}
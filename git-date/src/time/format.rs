@@ -24,6 +24,16 @@ pub const UNIX: Format<'static> = Format::Unix;
 /// E.g. `1660874655 +0800`
 pub const RAW: Format<'static> = Format::Raw;
 
+/// E.g. `Thu Sep 4 10:45:06 2022 -0400`, like `git log` displays dates by default.
+pub const GIT_DEFAULT: &[FormatItem<'_>] = format_description!(
+    "[weekday repr:short] [month repr:short] [day padding:none] [hour]:[minute]:[second] [year] [offset_hour sign:mandatory][offset_minute]"
+);
+
+/// Format times relative to `now`, like `2 days ago`.
+pub fn relative(now: std::time::SystemTime) -> Format<'static> {
+    Format::Relative { now }
+}
+
 /// E.g. `Thu Sep 04 2022 10:45:06 -0400`
 pub const DEFAULT: &[FormatItem<'_>] = format_description!(
     "[weekday repr:short] [month repr:short] [day] [year] [hour]:[minute]:[second] [offset_hour sign:mandatory][offset_minute]"
@@ -55,10 +65,58 @@ impl Time {
                 .expect("well-known format into memory never fails"),
             Format::Unix => self.seconds_since_unix_epoch.to_string(),
             Format::Raw => self.to_bstring().to_string(),
+            Format::Relative { now } => {
+                let now = now.duration_since(std::time::UNIX_EPOCH).map_or(0, |d| d.as_secs());
+                relative_to_now(
+                    now.saturating_sub(self.seconds_since_unix_epoch as u64),
+                    now < self.seconds_since_unix_epoch as u64,
+                )
+            }
         }
     }
 }
 
+/// Produce a string like `git` does for relative dates, given the amount of seconds that passed `diff`, or `is_in_future`.
+fn relative_to_now(diff: u64, is_in_future: bool) -> String {
+    fn plural(n: u64, unit: &str) -> String {
+        format!("{} {}{}", n, unit, if n == 1 { "" } else { "s" })
+    }
+    if is_in_future {
+        return "in the future".into();
+    }
+    if diff < 90 {
+        return format!("{} ago", plural(diff, "second"));
+    }
+    let minutes = (diff + 30) / 60;
+    if minutes < 90 {
+        return format!("{} ago", plural(minutes, "minute"));
+    }
+    let hours = (minutes + 30) / 60;
+    if hours < 36 {
+        return format!("{} ago", plural(hours, "hour"));
+    }
+    let days = (hours + 12) / 24;
+    if days < 14 {
+        return format!("{} ago", plural(days, "day"));
+    }
+    if days < 70 {
+        return format!("{} ago", plural((days + 3) / 7, "week"));
+    }
+    if days < 365 {
+        return format!("{} ago", plural((days + 15) / 30, "month"));
+    }
+    if days < 1825 {
+        let total_months = (days * 12 * 2 + 365) / (365 * 2);
+        let (years, months) = (total_months / 12, total_months % 12);
+        return if months == 0 {
+            format!("{} ago", plural(years, "year"))
+        } else {
+            format!("{}, {} ago", plural(years, "year"), plural(months, "month"))
+        };
+    }
+    format!("{} ago", plural((days + 183) / 365, "year"))
+}
+
 impl Time {
     fn to_time(self) -> time::OffsetDateTime {
         time::OffsetDateTime::from_unix_timestamp(self.seconds_since_unix_epoch as i64)
@@ -31,6 +31,11 @@ pub enum Format<'a> {
     Unix,
     /// The seconds since 1970, followed by the offset, like `1660874655 +0800`
     Raw,
+    /// The time relative to `now`, like `2 days ago`, as displayed by `git log --date=relative`.
+    Relative {
+        /// The time to compute the relative time against, typically [`SystemTime::now()`][std::time::SystemTime::now()].
+        now: std::time::SystemTime,
+    },
 }
 
 ///
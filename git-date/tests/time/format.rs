@@ -46,6 +46,43 @@ fn default() {
     );
 }
 
+#[test]
+fn git_default() {
+    assert_eq!(time().format(format::GIT_DEFAULT), "Thu Nov 29 21:33:09 1973 +0230");
+}
+
+#[test]
+fn relative() {
+    let now = |secs_after: u64| std::time::UNIX_EPOCH + std::time::Duration::from_secs(123456789 + secs_after);
+    for (secs_after, expected) in [
+        (0, "0 seconds ago"),
+        (1, "1 second ago"),
+        (89, "89 seconds ago"),
+        (90, "2 minutes ago"),
+        (60 * 60, "60 minutes ago"),
+        (2 * 60 * 60, "2 hours ago"),
+        (24 * 60 * 60, "24 hours ago"),
+        (3 * 24 * 60 * 60, "3 days ago"),
+        (21 * 24 * 60 * 60, "3 weeks ago"),
+        (100 * 24 * 60 * 60, "3 months ago"),
+        (365 * 24 * 60 * 60, "1 year ago"),
+        (500 * 24 * 60 * 60, "1 year, 4 months ago"),
+        (3650 * 24 * 60 * 60, "10 years ago"),
+    ] {
+        assert_eq!(
+            time().format(format::relative(now(secs_after))),
+            expected,
+            "{}s",
+            secs_after
+        );
+    }
+    assert_eq!(
+        time().format(format::relative(std::time::UNIX_EPOCH)),
+        "in the future",
+        "times after 'now' can't be relative"
+    );
+}
+
 #[test]
 fn custom_compile_time() {
     assert_eq!(
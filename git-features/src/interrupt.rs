@@ -1,9 +1,50 @@
 //! Utilities to cause interruptions in common traits, like Read/Write and Iterator.
 use std::{
     io,
-    sync::atomic::{AtomicBool, Ordering},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    time::Duration,
 };
 
+/// A timer which sets an interrupt flag once its duration elapsed, unless it was dropped before.
+///
+/// This makes it easy to let operations which support interruption fail after a given amount of time.
+pub struct Timeout {
+    cancel: Option<mpsc::Sender<()>>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl Timeout {
+    /// Spawn a thread which stores `true` in `flag` once `duration` elapsed, unless the returned instance is dropped first.
+    pub fn new(duration: Duration, flag: &'static AtomicBool) -> Self {
+        let (cancel, cancelled) = mpsc::channel::<()>();
+        let thread = std::thread::Builder::new()
+            .name("interrupt-timeout".into())
+            .spawn(move || {
+                if let Err(mpsc::RecvTimeoutError::Timeout) = cancelled.recv_timeout(duration) {
+                    flag.store(true, Ordering::SeqCst);
+                }
+            })
+            .expect("spawning a thread works");
+        Timeout {
+            cancel: Some(cancel),
+            thread: Some(thread),
+        }
+    }
+}
+
+impl Drop for Timeout {
+    fn drop(&mut self) {
+        // Dropping the sender wakes up the thread which then exits without touching the flag.
+        drop(self.cancel.take());
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+}
+
 /// A wrapper for an inner iterator which will check for interruptions on each iteration, stopping the iteration when
 /// that is requested.
 pub struct Iter<'a, I> {
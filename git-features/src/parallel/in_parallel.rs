@@ -187,3 +187,81 @@ where
     })
     .expect("no panic")
 }
+
+/// Divide `input` into chunks of at most `chunk_size` items and `process` each of them in parallel, returning all outputs
+/// concatenated in the order of their chunks, just as if `process` would have been called serially on each chunk.
+///
+/// This is best used if each chunk takes about the same time to process, like when hashing evenly sized items.
+/// The first error returned by `process` stops all threads as soon as possible and is returned.
+///
+/// * if `thread_limit` is `Some`, the given amount of threads will be used. If `None`, all logical cores will be used.
+/// * `chunk_size` must be larger than 0.
+pub fn in_parallel_with_chunks<T, O, E>(
+    input: &[T],
+    chunk_size: usize,
+    thread_limit: Option<usize>,
+    process: impl Fn(&[T]) -> Result<Vec<O>, E> + Send + Clone,
+) -> Result<Vec<O>, E>
+where
+    T: Sync,
+    O: Send,
+    E: Send,
+{
+    assert_ne!(chunk_size, 0, "chunk size must be larger than 0");
+    let num_chunks = (input.len() + chunk_size - 1) / chunk_size;
+    let num_threads = num_threads(thread_limit).min(num_chunks.max(1));
+    let stop_everything = &AtomicBool::default();
+    let next_chunk = &AtomicUsize::default();
+
+    let mut outputs = crossbeam_utils::thread::scope(|s| {
+        let threads: Vec<_> = (0..num_threads)
+            .map(|_| {
+                s.spawn({
+                    let process = process.clone();
+                    move |_| -> Result<Vec<(usize, Vec<O>)>, E> {
+                        let mut out = Vec::new();
+                        loop {
+                            let chunk_index = next_chunk.fetch_add(1, Ordering::SeqCst);
+                            if chunk_index >= num_chunks || stop_everything.load(Ordering::Relaxed) {
+                                break;
+                            }
+                            let start = chunk_index * chunk_size;
+                            let end = (start + chunk_size).min(input.len());
+                            match process(&input[start..end]) {
+                                Ok(chunk_out) => out.push((chunk_index, chunk_out)),
+                                Err(err) => {
+                                    stop_everything.store(true, Ordering::Relaxed);
+                                    return Err(err);
+                                }
+                            }
+                        }
+                        Ok(out)
+                    }
+                })
+            })
+            .collect();
+
+        let mut outputs = Vec::with_capacity(num_chunks);
+        let mut first_error = None;
+        for thread in threads {
+            match thread.join() {
+                Ok(Ok(out)) => outputs.extend(out),
+                Ok(Err(err)) => {
+                    first_error.get_or_insert(err);
+                }
+                Err(err) => {
+                    stop_everything.store(true, Ordering::Relaxed);
+                    std::panic::resume_unwind(err);
+                }
+            }
+        }
+        match first_error {
+            Some(err) => Err(err),
+            None => Ok(outputs),
+        }
+    })
+    .expect("no panic")?;
+
+    outputs.sort_by_key(|(chunk_index, _)| *chunk_index);
+    Ok(outputs.into_iter().flat_map(|(_, out)| out).collect())
+}
@@ -77,10 +77,32 @@ mod not_parallel {
         }
         Ok(vec![state_to_rval(state)])
     }
+
+    /// Divide `input` into chunks of at most `chunk_size` items and `process` each of them, returning all outputs
+    /// concatenated in the order of their chunks.
+    ///
+    /// * `thread_limit` has no effect as everything is run on the current thread, but is present to keep the signature
+    ///   similar to the parallel version.
+    /// * `chunk_size` must be larger than 0.
+    ///
+    /// **This serial version performing all calculations on the current thread.**
+    pub fn in_parallel_with_chunks<T, O, E>(
+        input: &[T],
+        chunk_size: usize,
+        _thread_limit: Option<usize>,
+        process: impl Fn(&[T]) -> Result<Vec<O>, E>,
+    ) -> Result<Vec<O>, E> {
+        assert_ne!(chunk_size, 0, "chunk size must be larger than 0");
+        let mut out = Vec::with_capacity(input.len());
+        for chunk in input.chunks(chunk_size) {
+            out.extend(process(chunk)?);
+        }
+        Ok(out)
+    }
 }
 
 #[cfg(not(feature = "parallel"))]
-pub use not_parallel::{in_parallel_with_slice, join, threads, Scope, ScopedJoinHandle};
+pub use not_parallel::{in_parallel_with_chunks, in_parallel_with_slice, join, threads, Scope, ScopedJoinHandle};
 
 /// Read items from `input` and `consume` them in a single thread, producing an output to be collected by a `reducer`,
 /// whose task is to aggregate these outputs into the final result returned by this function.
@@ -7,6 +7,10 @@ pub use prodash::{
     unit, Progress, Unit,
 };
 
+/// Sending progress information from other threads.
+pub mod channel;
+pub use channel::{channel, Channel, ProgressMessage};
+
 /// A unit for displaying bytes with throughput and progress percentage.
 pub fn bytes() -> Option<Unit> {
     Some(unit::dynamic_and_mode(
@@ -0,0 +1,357 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc, Mutex,
+    },
+};
+
+use prodash::progress::{Step, StepShared};
+
+use crate::progress::{MessageLevel, Progress, Unit};
+
+/// The id of the progress instance created by [`channel()`], which is forwarded to the parent of the [`Receiver`].
+const ROOT_ID: usize = 0;
+
+/// A message sent by [`Channel`] instances to their [`Receiver`], to be replayed on the receiving [`Progress`] implementation.
+#[derive(Debug, Clone)]
+pub enum ProgressMessage {
+    /// A new child named `name` was added and will be referred to by `id` from now on.
+    AddChild {
+        /// The id of the newly added child.
+        id: usize,
+        /// The name of the child.
+        name: String,
+    },
+    /// [`Progress::init()`] was called.
+    Init {
+        /// The id of the progress instance this message belongs to.
+        id: usize,
+        /// The maximum of the progress, if bounded.
+        max: Option<Step>,
+        /// The unit to display, if any.
+        unit: Option<Unit>,
+    },
+    /// [`Progress::set()`] was called.
+    Set {
+        /// The id of the progress instance this message belongs to.
+        id: usize,
+        /// The step to set.
+        step: Step,
+    },
+    /// [`Progress::set_max()`] was called.
+    SetMax {
+        /// The id of the progress instance this message belongs to.
+        id: usize,
+        /// The new maximum.
+        max: Option<Step>,
+    },
+    /// [`Progress::inc_by()`] was called.
+    IncBy {
+        /// The id of the progress instance this message belongs to.
+        id: usize,
+        /// The amount of steps to increment by.
+        step: Step,
+    },
+    /// [`Progress::set_name()`] was called.
+    SetName {
+        /// The id of the progress instance this message belongs to.
+        id: usize,
+        /// The new name.
+        name: String,
+    },
+    /// [`Progress::message()`] was called.
+    Message {
+        /// The id of the progress instance this message belongs to.
+        id: usize,
+        /// The level of the message.
+        level: MessageLevel,
+        /// The message itself.
+        message: String,
+    },
+    /// The last clone of the progress instance was dropped, and it won't send any more messages.
+    Done {
+        /// The id of the progress instance this message belongs to.
+        id: usize,
+    },
+}
+
+/// Create a new [`Channel`] whose calls are replayed on `parent` by the returned [`Receiver`].
+///
+/// The [`Channel`] can be cloned and moved to other threads freely, while the [`Receiver`] stays with `parent`
+/// and forwards progress information whenever it is used as [`Progress`] itself, or when
+/// [`Receiver::forward_pending()`] is called.
+pub fn channel<P: Progress>(parent: P) -> (Channel, Receiver<P>) {
+    let (sender, receiver) = mpsc::channel();
+    (
+        Channel {
+            handle: Handle::new(ROOT_ID, None, &sender),
+            sender,
+            next_id: Arc::new(AtomicUsize::new(ROOT_ID + 1)),
+            step: Default::default(),
+        },
+        Receiver {
+            parent,
+            children: HashMap::new(),
+            receiver,
+        },
+    )
+}
+
+/// A [`Progress`] implementation which sends all calls to its [`Receiver`] without any need for boxing or
+/// synchronization, making it suitable for use in background threads.
+///
+/// Clones refer to the same progress instance and share its step, maximum, unit and name.
+#[derive(Clone)]
+pub struct Channel {
+    handle: Arc<Handle>,
+    sender: mpsc::Sender<ProgressMessage>,
+    next_id: Arc<AtomicUsize>,
+    step: StepShared,
+}
+
+/// Shared by all clones of a [`Channel`] to keep the state of the progress instance, and to let the [`Receiver`] know
+/// when the last one of them is dropped.
+struct Handle {
+    id: usize,
+    info: Mutex<Info>,
+    sender: Mutex<mpsc::Sender<ProgressMessage>>,
+}
+
+#[derive(Default)]
+struct Info {
+    max: Option<Step>,
+    unit: Option<Unit>,
+    name: Option<String>,
+}
+
+impl Handle {
+    fn new(id: usize, name: Option<String>, sender: &mpsc::Sender<ProgressMessage>) -> Arc<Self> {
+        Arc::new(Handle {
+            id,
+            info: Mutex::new(Info {
+                name,
+                ..Default::default()
+            }),
+            sender: Mutex::new(sender.clone()),
+        })
+    }
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        if let Ok(sender) = self.sender.get_mut() {
+            sender.send(ProgressMessage::Done { id: self.id }).ok();
+        }
+    }
+}
+
+impl Channel {
+    fn id(&self) -> usize {
+        self.handle.id
+    }
+
+    fn shared_info(&self) -> std::sync::MutexGuard<'_, Info> {
+        self.handle.info.lock().expect("no panic while holding the lock")
+    }
+
+    fn send(&self, msg: ProgressMessage) {
+        // The receiver might be gone, and progress is never important enough to fail the operation.
+        self.sender.send(msg).ok();
+    }
+}
+
+impl Progress for Channel {
+    type SubProgress = Channel;
+
+    fn add_child(&mut self, name: impl Into<String>) -> Self::SubProgress {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let name = name.into();
+        self.send(ProgressMessage::AddChild { id, name: name.clone() });
+        Channel {
+            handle: Handle::new(id, Some(name), &self.sender),
+            sender: self.sender.clone(),
+            next_id: Arc::clone(&self.next_id),
+            step: Default::default(),
+        }
+    }
+
+    fn init(&mut self, max: Option<Step>, unit: Option<Unit>) {
+        {
+            let mut info = self.shared_info();
+            info.max = max;
+            info.unit = unit.clone();
+        }
+        self.step.store(0, Ordering::SeqCst);
+        self.send(ProgressMessage::Init {
+            id: self.id(),
+            max,
+            unit,
+        });
+    }
+
+    fn set(&mut self, step: Step) {
+        self.step.store(step, Ordering::SeqCst);
+        self.send(ProgressMessage::Set { id: self.id(), step });
+    }
+
+    fn unit(&self) -> Option<Unit> {
+        self.shared_info().unit.clone()
+    }
+
+    fn max(&self) -> Option<Step> {
+        self.shared_info().max
+    }
+
+    fn set_max(&mut self, max: Option<Step>) -> Option<Step> {
+        self.send(ProgressMessage::SetMax { id: self.id(), max });
+        std::mem::replace(&mut self.shared_info().max, max)
+    }
+
+    fn step(&self) -> Step {
+        self.step.load(Ordering::Relaxed)
+    }
+
+    fn inc_by(&mut self, step: Step) {
+        self.step.fetch_add(step, Ordering::SeqCst);
+        self.send(ProgressMessage::IncBy { id: self.id(), step });
+    }
+
+    fn set_name(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        self.shared_info().name = Some(name.clone());
+        self.send(ProgressMessage::SetName { id: self.id(), name });
+    }
+
+    fn name(&self) -> Option<String> {
+        self.shared_info().name.clone()
+    }
+
+    fn message(&mut self, level: MessageLevel, message: impl Into<String>) {
+        self.send(ProgressMessage::Message {
+            id: self.id(),
+            level,
+            message: message.into(),
+        });
+    }
+}
+
+/// The receiving end of a [`Channel`], replaying all received messages on its parent [`Progress`] implementation.
+///
+/// Children added to any [`Channel`] become direct children of the parent, as the type of the parent's
+/// sub-progress doesn't allow for arbitrary nesting. They are dropped once the last clone of their [`Channel`] is dropped.
+pub struct Receiver<P: Progress> {
+    parent: P,
+    children: HashMap<usize, P::SubProgress>,
+    receiver: mpsc::Receiver<ProgressMessage>,
+}
+
+impl<P: Progress> Receiver<P> {
+    /// Forward all messages that are currently available without blocking.
+    pub fn forward_pending(&mut self) {
+        while let Ok(msg) = self.receiver.try_recv() {
+            self.forward(msg);
+        }
+    }
+
+    /// Forward all messages until all [`Channel`] instances are dropped, blocking while waiting for new ones.
+    pub fn forward_until_disconnected(&mut self) {
+        while let Ok(msg) = self.receiver.recv() {
+            self.forward(msg);
+        }
+    }
+
+    /// Forward all pending messages and return the parent progress.
+    pub fn into_inner(mut self) -> P {
+        self.forward_pending();
+        self.parent
+    }
+
+    fn forward(&mut self, msg: ProgressMessage) {
+        macro_rules! forward {
+            ($id:expr, |$p:ident| $call:expr) => {
+                if $id == ROOT_ID {
+                    let $p = &mut self.parent;
+                    $call;
+                } else if let Some($p) = self.children.get_mut(&$id) {
+                    $call;
+                }
+            };
+        }
+        use ProgressMessage::*;
+        match msg {
+            AddChild { id, name } => {
+                let child = self.parent.add_child(name);
+                self.children.insert(id, child);
+            }
+            Init { id, max, unit } => forward!(id, |p| p.init(max, unit)),
+            Set { id, step } => forward!(id, |p| p.set(step)),
+            SetMax { id, max } => forward!(id, |p| p.set_max(max)),
+            IncBy { id, step } => forward!(id, |p| p.inc_by(step)),
+            SetName { id, name } => forward!(id, |p| p.set_name(name)),
+            Message { id, level, message } => forward!(id, |p| p.message(level, message)),
+            Done { id } => {
+                self.children.remove(&id);
+            }
+        }
+    }
+}
+
+impl<P: Progress> Progress for Receiver<P> {
+    type SubProgress = P::SubProgress;
+
+    fn add_child(&mut self, name: impl Into<String>) -> Self::SubProgress {
+        self.forward_pending();
+        self.parent.add_child(name)
+    }
+
+    fn init(&mut self, max: Option<Step>, unit: Option<Unit>) {
+        self.forward_pending();
+        self.parent.init(max, unit)
+    }
+
+    fn set(&mut self, step: Step) {
+        self.forward_pending();
+        self.parent.set(step)
+    }
+
+    fn unit(&self) -> Option<Unit> {
+        self.parent.unit()
+    }
+
+    fn max(&self) -> Option<Step> {
+        self.parent.max()
+    }
+
+    fn set_max(&mut self, max: Option<Step>) -> Option<Step> {
+        self.forward_pending();
+        self.parent.set_max(max)
+    }
+
+    fn step(&self) -> Step {
+        self.parent.step()
+    }
+
+    fn inc_by(&mut self, step: Step) {
+        self.forward_pending();
+        self.parent.inc_by(step)
+    }
+
+    fn set_name(&mut self, name: impl Into<String>) {
+        self.forward_pending();
+        self.parent.set_name(name)
+    }
+
+    fn name(&self) -> Option<String> {
+        self.parent.name()
+    }
+
+    fn message(&mut self, level: MessageLevel, message: impl Into<String>) {
+        self.forward_pending();
+        self.parent.message(level, message)
+    }
+
+    fn counter(&self) -> Option<StepShared> {
+        self.parent.counter()
+    }
+}
@@ -0,0 +1,30 @@
+mod timeout {
+    use std::{
+        sync::atomic::{AtomicBool, Ordering},
+        time::Duration,
+    };
+
+    use git_features::interrupt::Timeout;
+
+    #[test]
+    fn sets_the_flag_once_the_duration_elapsed() {
+        static FLAG: AtomicBool = AtomicBool::new(false);
+        let _timeout = Timeout::new(Duration::from_millis(1), &FLAG);
+        let start = std::time::Instant::now();
+        while !FLAG.load(Ordering::SeqCst) {
+            assert!(
+                start.elapsed() < Duration::from_secs(10),
+                "the flag should be set eventually"
+            );
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn does_not_set_the_flag_when_dropped_before_the_duration_elapsed() {
+        static FLAG: AtomicBool = AtomicBool::new(false);
+        let timeout = Timeout::new(Duration::from_secs(60 * 60), &FLAG);
+        drop(timeout);
+        assert!(!FLAG.load(Ordering::SeqCst));
+    }
+}
@@ -59,6 +59,40 @@ fn in_parallel_with_mut_slice_in_chunks() {
     assert_eq!(input.iter().sum::<usize>(), expected * 2, "we increment each entry");
 }
 
+#[test]
+fn in_parallel_with_chunks_retains_order() {
+    let input: Vec<usize> = (0..1000).collect();
+    for chunk_size in [1, 7, 100, 1000, 2000] {
+        let out = parallel::in_parallel_with_chunks(&input, chunk_size, None, |chunk| {
+            Ok::<_, ()>(chunk.iter().map(|n| n * 2).collect())
+        })
+        .unwrap();
+        assert_eq!(
+            out,
+            input.iter().map(|n| n * 2).collect::<Vec<_>>(),
+            "chunk size {}",
+            chunk_size
+        );
+    }
+
+    let out =
+        parallel::in_parallel_with_chunks(&[] as &[usize], 10, None, |chunk| Ok::<_, ()>(chunk.to_vec())).unwrap();
+    assert!(out.is_empty(), "empty input is fine");
+}
+
+#[test]
+fn in_parallel_with_chunks_fails_on_first_error() {
+    let input: Vec<usize> = (0..100).collect();
+    let res = parallel::in_parallel_with_chunks(&input, 10, None, |chunk| {
+        if chunk.contains(&42) {
+            Err(42)
+        } else {
+            Ok(chunk.to_vec())
+        }
+    });
+    assert_eq!(res, Err(42));
+}
+
 #[test]
 fn stepped_reduce_next() {
     let mut iter = parallel::reduce::Stepwise::new(
@@ -0,0 +1,164 @@
+mod channel {
+    use std::sync::{Arc, Mutex};
+
+    use git_features::progress::{self, MessageLevel, Progress, Unit};
+
+    #[derive(Clone, Default)]
+    struct Record {
+        name: String,
+        events: Arc<Mutex<Vec<String>>>,
+        step: usize,
+    }
+
+    impl Record {
+        fn named(name: &str) -> Self {
+            Record {
+                name: name.into(),
+                events: Default::default(),
+                step: 0,
+            }
+        }
+
+        fn push(&self, event: String) {
+            self.events.lock().unwrap().push(format!("{}: {}", self.name, event));
+        }
+    }
+
+    impl Drop for Record {
+        fn drop(&mut self) {
+            self.push("dropped".into());
+        }
+    }
+
+    impl Progress for Record {
+        type SubProgress = Record;
+
+        fn add_child(&mut self, name: impl Into<String>) -> Self::SubProgress {
+            let name = name.into();
+            self.push(format!("add child {}", name));
+            Record {
+                name,
+                events: Arc::clone(&self.events),
+                step: 0,
+            }
+        }
+
+        fn init(&mut self, max: Option<usize>, _unit: Option<Unit>) {
+            self.push(format!("init {:?}", max));
+        }
+
+        fn set(&mut self, step: usize) {
+            self.step = step;
+            self.push(format!("set {}", step));
+        }
+
+        fn step(&self) -> usize {
+            self.step
+        }
+
+        fn inc_by(&mut self, step: usize) {
+            self.step += step;
+            self.push(format!("inc by {}", step));
+        }
+
+        fn set_name(&mut self, name: impl Into<String>) {
+            self.name = name.into();
+        }
+
+        fn name(&self) -> Option<String> {
+            Some(self.name.clone())
+        }
+
+        fn message(&mut self, level: MessageLevel, message: impl Into<String>) {
+            self.push(format!("{:?} {}", level, message.into()));
+        }
+    }
+
+    #[test]
+    fn calls_from_other_threads_are_replayed_on_the_parent() {
+        let parent = Record::named("parent");
+        let events = Arc::clone(&parent.events);
+        let (mut progress, mut receiver) = progress::channel(parent);
+
+        progress.init(Some(2), None);
+        let mut child = progress.add_child("child");
+        let handle = std::thread::spawn(move || {
+            child.init(None, progress::bytes());
+            child.inc_by(5);
+            child.inc();
+            assert_eq!(child.step(), 6, "steps are tracked locally as well");
+            child.info("done");
+            progress.set(2);
+        });
+        handle.join().expect("no panic");
+        receiver.forward_until_disconnected();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                "parent: init Some(2)",
+                "parent: add child child",
+                "child: init None",
+                "child: inc by 5",
+                "child: inc by 1",
+                "child: Info done",
+                "parent: set 2",
+                "child: dropped",
+            ]
+        );
+        assert_eq!(
+            receiver.step(),
+            2,
+            "the receiver is a progress as well and delegates to its parent"
+        );
+    }
+
+    #[test]
+    fn children_are_dropped_once_all_their_clones_are_dropped() {
+        let parent = Record::named("parent");
+        let events = Arc::clone(&parent.events);
+        let (mut progress, mut receiver) = progress::channel(parent);
+
+        let child = progress.add_child("child");
+        let clone = child.clone();
+        drop(child);
+        receiver.forward_pending();
+        assert_eq!(*events.lock().unwrap(), vec!["parent: add child child"]);
+
+        drop(clone);
+        receiver.forward_pending();
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec!["parent: add child child", "child: dropped"],
+            "the receiver doesn't keep children around that can't receive messages anymore"
+        );
+    }
+
+    #[test]
+    fn clones_refer_to_the_same_progress() {
+        let (mut progress, receiver) = progress::channel(Record::default());
+        let mut clone = progress.clone();
+        progress.init(Some(5), None);
+        clone.inc_by(3);
+        clone.set_name("renamed");
+        assert_eq!(progress.step(), 3);
+        assert_eq!(progress.max(), Some(5), "the maximum is shared");
+        assert_eq!(progress.name().as_deref(), Some("renamed"), "the name is shared");
+        assert_eq!(receiver.into_inner().step(), 3, "pending messages are forwarded");
+    }
+}
+
+mod discard {
+    use git_features::progress::{Discard, Progress};
+
+    #[test]
+    fn it_and_its_children_are_zero_sized() {
+        assert_eq!(std::mem::size_of::<Discard>(), 0);
+        let child = Discard.add_child("child").add_child("grandchild");
+        assert_eq!(
+            std::mem::size_of_val(&child),
+            0,
+            "adding children never allocates as there is nothing to allocate"
+        );
+    }
+}
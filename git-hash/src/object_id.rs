@@ -14,7 +14,7 @@ pub enum ObjectId {
 pub mod decode {
     use std::str::FromStr;
 
-    use crate::object_id::ObjectId;
+    use crate::{object_id::ObjectId, SIZE_OF_SHA1_DIGEST};
 
     /// An error returned by [`ObjectId::from_hex()`][crate::ObjectId::from_hex()]
     #[derive(Debug, thiserror::Error)]
@@ -45,6 +45,31 @@ pub mod decode {
                 len => Err(Error::InvalidHexEncodingLength(len)),
             }
         }
+
+        /// Create an instance from `hex`, a hexadecimal string whose length determines the kind of hash,
+        /// usable in `const` contexts as well.
+        ///
+        /// Currently only 40 characters, the length of a hex-encoded Sha1 hash, are supported and all other lengths
+        /// yield [`Error::InvalidHexEncodingLength`].
+        pub const fn from_hex_str(hex: &str) -> Result<ObjectId, Error> {
+            let hex = hex.as_bytes();
+            if hex.len() != SIZE_OF_SHA1_DIGEST * 2 {
+                return Err(Error::InvalidHexEncodingLength(hex.len()));
+            }
+            let mut id = [0u8; SIZE_OF_SHA1_DIGEST];
+            let mut index = 0;
+            while index < hex.len() {
+                let nibble = match hex[index] {
+                    b @ b'0'..=b'9' => b - b'0',
+                    b @ b'a'..=b'f' => b - b'a' + 10,
+                    b @ b'A'..=b'F' => b - b'A' + 10,
+                    c => return Err(Error::Invalid { c: c as char, index }),
+                };
+                id[index / 2] |= if index % 2 == 0 { nibble << 4 } else { nibble };
+                index += 1;
+            }
+            Ok(ObjectId::Sha1(id))
+        }
     }
 
     impl FromStr for ObjectId {
@@ -41,3 +41,46 @@ mod from_hex {
         }
     }
 }
+
+mod from_hex_str {
+    use git_hash::{decode, ObjectId};
+
+    #[test]
+    fn sha1_length_is_inferred_and_matches_from_hex() {
+        for hex in [
+            "1234567890abcdefaaaaaaaaaaaaaaaaaaaaaaaa",
+            "1234567890ABCDEFAAAAAAAAAAAAAAAAAAAAAAAA",
+        ] {
+            let id = ObjectId::from_hex_str(hex).expect("valid");
+            assert_eq!(id.kind(), git_hash::Kind::Sha1);
+            assert_eq!(id, ObjectId::from_hex(hex.as_bytes()).expect("valid"));
+        }
+    }
+
+    #[test]
+    fn usable_in_const_context() {
+        const EMPTY_TREE: ObjectId = match ObjectId::from_hex_str("4b825dc642cb6eb9a060e54bf8d69288fbee4904") {
+            Ok(id) => id,
+            Err(_) => panic!("valid hex"),
+        };
+        assert_eq!(EMPTY_TREE, ObjectId::empty_tree(git_hash::Kind::Sha1));
+    }
+
+    #[test]
+    fn invalid_characters_are_reported_with_their_position() {
+        assert!(matches!(
+            ObjectId::from_hex_str("123456789zabcdefaaaaaaaaaaaaaaaaaaaaaaaa").unwrap_err(),
+            decode::Error::Invalid { index: 9, c: 'z' }
+        ));
+    }
+
+    #[test]
+    fn unsupported_lengths() {
+        for len in [0, 39, 41, 64] {
+            assert!(matches!(
+                ObjectId::from_hex_str(&"a".repeat(len)).unwrap_err(),
+                decode::Error::InvalidHexEncodingLength(actual) if actual == len
+            ));
+        }
+    }
+}
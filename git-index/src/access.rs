@@ -9,6 +9,14 @@ impl State {
         self.version
     }
 
+    /// Return `true` if the stat information of entries was updated with [`set_stat()`][State::set_stat()] or
+    /// [`refresh_stats()`][State::refresh_stats()] since this state was created, and it should be written back to disk.
+    ///
+    /// Note that other changes, like the ones made through [`entries_mut()`][State::entries_mut()], are not tracked.
+    pub fn is_changed(&self) -> bool {
+        self.is_changed
+    }
+
     /// Return the kind of hashes used in this instance.
     pub fn object_hash(&self) -> git_hash::Kind {
         self.object_hash
@@ -57,6 +65,16 @@ impl State {
     pub fn entries_mut(&mut self) -> &mut [Entry] {
         &mut self.entries
     }
+    /// Sort our entries by path and stage, the order git requires when [writing][State::write_to()] them.
+    ///
+    /// Note that this doesn't remove entries with the same path and stage, which would still fail [verification][State::verify_entries()].
+    pub fn sort_entries(&mut self) {
+        let path_backing = &self.path_backing;
+        self.entries.sort_by(|a, b| {
+            Entry::cmp_filepaths(a.path_in(path_backing), b.path_in(path_backing))
+                .then_with(|| a.stage().cmp(&b.stage()))
+        });
+    }
     /// Return mutable entries along with their paths in an iterator.
     pub fn entries_mut_with_paths(&mut self) -> impl Iterator<Item = (&mut Entry, &BStr)> {
         let paths = &self.path_backing;
@@ -94,6 +112,27 @@ impl State {
             .map(|idx| &self.entries[idx])
     }
 
+    /// Return all entries within the directory `prefix`, like `src` or `src/`, as a subslice of
+    /// [`entries()`][State::entries()], or an empty slice if there is none.
+    ///
+    /// Entries merely sharing the same bytes, like `src-extra/file` or `src.txt`, are not included.
+    /// An empty `prefix` matches all entries.
+    pub fn entries_with_prefix(&self, prefix: &BStr) -> &[Entry] {
+        let dir = prefix.strip_suffix(b"/").unwrap_or(prefix);
+        if dir.is_empty() {
+            return &self.entries;
+        }
+        let mut dir_with_slash = dir.to_owned();
+        dir_with_slash.push(b'/');
+        let dir_with_slash = dir_with_slash.as_slice();
+
+        let start = self
+            .entries
+            .partition_point(|e| e.path(self).as_bytes() < dir_with_slash);
+        let len = self.entries[start..].partition_point(|e| e.path(self).starts_with(dir_with_slash));
+        &self.entries[start..][..len]
+    }
+
     /// Return the entry at `idx` or _panic_ if the index is out of bounds.
     ///
     /// The `idx` is typically returned by [entry_by_path_and_stage()][State::entry_by_path_and_stage()].
@@ -102,12 +141,47 @@ impl State {
     }
 }
 
+/// Mutation
+impl State {
+    /// Set the `stat` information of the unconflicted entry at `path` and mark this state as [changed][State::is_changed()],
+    /// leaving the entry's object id untouched.
+    ///
+    /// Returns `false` if there was no such entry.
+    pub fn set_stat(&mut self, path: &BStr, stat: entry::Stat) -> bool {
+        match self.entry_index_by_path_and_stage(path, 0) {
+            Some(idx) => {
+                self.entries[idx].stat = stat;
+                self.is_changed = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Like [`set_stat()`][State::set_stat()], but for each `(path, stat)` pair in `stats`, returning the amount of
+    /// entries that were updated.
+    pub fn refresh_stats<'a>(&mut self, stats: impl IntoIterator<Item = (&'a BStr, entry::Stat)>) -> usize {
+        stats
+            .into_iter()
+            .filter(|(path, stat)| self.set_stat(path, *stat))
+            .count()
+    }
+}
+
 /// Extensions
 impl State {
     /// Access the `tree` extension.
     pub fn tree(&self) -> Option<&extension::Tree> {
         self.tree.as_ref()
     }
+    /// Return the id of the root tree as cached in the `tree` extension, which is what writing all entries as tree would produce,
+    /// or `None` if there is no such extension or if it or any of its sub-trees was invalidated.
+    pub fn root_tree_id(&self) -> Option<git_hash::ObjectId> {
+        fn is_valid(tree: &extension::Tree) -> bool {
+            tree.num_entries.is_some() && tree.children.iter().all(is_valid)
+        }
+        self.tree.as_ref().filter(|tree| is_valid(tree)).map(|tree| tree.id)
+    }
     /// Access the `link` extension.
     pub fn link(&self) -> Option<&extension::Link> {
         self.link.as_ref()
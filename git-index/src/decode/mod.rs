@@ -215,6 +215,7 @@ impl State {
                 entries,
                 path_backing,
                 is_sparse,
+                is_changed: false,
 
                 tree,
                 link,
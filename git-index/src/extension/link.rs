@@ -1,3 +1,5 @@
+use std::convert::TryFrom;
+
 use crate::{
     extension::{Link, Signature},
     util::split_at_pos,
@@ -60,3 +62,20 @@ pub(crate) fn decode(data: &[u8], object_hash: git_hash::Kind) -> Result<Link, d
         bitmaps: Some(Bitmaps { delete, replace }),
     })
 }
+
+impl Link {
+    /// Serialize this instance to `out`, with the extension header.
+    pub fn write_to(&self, mut out: impl std::io::Write) -> std::io::Result<()> {
+        let mut data = Vec::with_capacity(self.shared_index_checksum.as_bytes().len());
+        data.extend_from_slice(self.shared_index_checksum.as_bytes());
+        if let Some(bitmaps) = &self.bitmaps {
+            bitmaps.delete.write_to(&mut data)?;
+            bitmaps.replace.write_to(&mut data)?;
+        }
+
+        out.write_all(&SIGNATURE)?;
+        out.write_all(&(u32::try_from(data.len()).expect("less than 4GB link extension")).to_be_bytes())?;
+        out.write_all(&data)?;
+        Ok(())
+    }
+}
@@ -45,6 +45,7 @@ impl File {
             Err(err) => return Err(err.into_error().into()),
         };
         self.state.version = version;
+        self.state.is_changed = false;
         self.checksum = Some(digest);
         Ok(())
     }
@@ -43,6 +43,7 @@ mod from_tree {
                 entries,
                 path_backing,
                 is_sparse: false,
+                is_changed: false,
                 tree: None,
                 link: None,
                 resolve_undo: None,
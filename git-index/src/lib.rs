@@ -79,6 +79,8 @@ pub type PathStorageRef = [u8];
 ///
 /// As opposed to a snapshot, it's meant to be altered and eventually be written back to disk or converted into a tree.
 /// We treat index and its state synonymous.
+///
+/// To write its entries to a working tree, use `git_worktree::index::checkout()`.
 #[derive(Clone)]
 pub struct State {
     /// The kind of object hash used when storing the underlying file.
@@ -98,6 +100,8 @@ pub struct State {
     /// True if one entry in the index has a special marker mode
     #[allow(dead_code)]
     is_sparse: bool,
+    /// True if the stat information of entries was updated since the state was created, and it should be written back.
+    is_changed: bool,
 
     // Extensions
     tree: Option<extension::Tree>,
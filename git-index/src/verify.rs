@@ -42,8 +42,23 @@ pub mod extensions {
 impl State {
     /// Assure our entries are consistent.
     pub fn verify_entries(&self) -> Result<(), entries::Error> {
+        self.verify_entry_order(self.entries.iter())
+    }
+
+    /// Assure `entries` are in the order git requires, which is by path and stage, without duplicates.
+    ///
+    /// Entries of a split index which replace entries of the shared index have no path and are skipped,
+    /// as they are referred to by their position.
+    pub(crate) fn verify_entry_order<'a>(
+        &self,
+        entries: impl IntoIterator<Item = &'a crate::Entry>,
+    ) -> Result<(), entries::Error> {
+        let has_replacements_without_path = self.link.as_ref().map_or(false, |link| link.bitmaps.is_some());
         let mut previous = None::<&crate::Entry>;
-        for (idx, entry) in self.entries.iter().enumerate() {
+        for (idx, entry) in entries.into_iter().enumerate() {
+            if has_replacements_without_path && entry.path(self).is_empty() {
+                continue;
+            }
             if let Some(prev) = previous {
                 if prev.cmp(entry, self) != Ordering::Less {
                     return Err(entries::Error::OutOfOrder {
@@ -1,6 +1,6 @@
 use std::{convert::TryInto, io::Write};
 
-use crate::{entry, extension, write::util::CountBytes, State, Version};
+use crate::{entry, extension, write::util::CountBytes, Entry, State, Version};
 
 /// A way to specify which extensions to write.
 #[derive(Debug, Copy, Clone)]
@@ -11,6 +11,8 @@ pub enum Extensions {
     Given {
         /// Write the tree-cache extension, if present.
         tree_cache: bool,
+        /// Write the split-index link extension, if present.
+        link: bool,
         /// Write the end-of-index-entry extension.
         end_of_index_entry: bool,
     },
@@ -32,9 +34,11 @@ impl Extensions {
             Extensions::All => Some(signature),
             Extensions::Given {
                 tree_cache,
+                link,
                 end_of_index_entry,
             } => match signature {
                 extension::tree::SIGNATURE => tree_cache,
+                extension::link::SIGNATURE => link,
                 extension::end_of_index_entry::SIGNATURE => end_of_index_entry,
                 _ => &false,
             }
@@ -43,19 +47,87 @@ impl Extensions {
     }
 }
 
+/// How to deal with the order of entries when writing, as git requires them to be sorted by path and stage.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Sorting {
+    /// [Verify][State::verify_entries()] the order of entries before writing and fail if they are not sorted, to prevent
+    /// producing an index that git would reject.
+    Verify,
+    /// Assume the entries are sorted and write them as they are without checking.
+    AssumeSorted,
+    /// Write the entries in sorted order, even if they are not sorted in memory, leaving the [`State`] itself unchanged.
+    ///
+    /// Entries with the same path and stage are still rejected as git can't represent them.
+    /// Use [`State::sort_entries()`] to sort the entries in memory instead.
+    ///
+    /// If entries had to be reordered, the tree-cache extension isn't written as it can't be trusted to describe the
+    /// entries, and split indices are rejected as the link extension refers to entries by their position.
+    Sort,
+}
+
+impl Default for Sorting {
+    fn default() -> Self {
+        Sorting::Verify
+    }
+}
+
 /// The options for use when [writing an index][State::write_to()].
 ///
-/// Note that default options write either index V2 or V3 depending on the content of the entries.
+/// Note that default options write either index V2 or V3 depending on the content of the entries, see [`version`][Options::version]
+/// to change that.
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Options {
     /// Configures which extensions to write
     pub extensions: Extensions,
+    /// Configures how to handle the order of entries, which are verified by default.
+    pub sorting: Sorting,
+    /// If `Some(version)`, write at least the given index version, or a higher one if the entries require it.
+    ///
+    /// This is useful to write V4 indices which compress entry paths, as these are never chosen automatically.
+    /// If `None`, the lowest version that can represent all entries is used.
+    pub version: Option<Version>,
 }
 
 impl State {
     /// Serialize this instance to `out` with [`options`][Options].
-    pub fn write_to(&self, out: impl std::io::Write, Options { extensions }: Options) -> std::io::Result<Version> {
-        let version = self.detect_required_version();
+    ///
+    /// Unless [`sorting`][Options::sorting] is [`AssumeSorted`][Sorting::AssumeSorted], an error of kind
+    /// [`InvalidInput`][std::io::ErrorKind::InvalidInput] wrapping a [verification error][crate::verify::entries::Error]
+    /// is returned if the entries are not in the order git requires, before anything was written to `out`.
+    pub fn write_to(
+        &self,
+        out: impl std::io::Write,
+        Options {
+            extensions,
+            sorting,
+            version,
+        }: Options,
+    ) -> std::io::Result<Version> {
+        let invalid_input = |err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err);
+        let invalid_input_msg = |msg| std::io::Error::new(std::io::ErrorKind::InvalidInput, msg);
+        let sorted_entries = match sorting {
+            Sorting::AssumeSorted => None,
+            Sorting::Verify => {
+                self.verify_entries().map_err(invalid_input)?;
+                None
+            }
+            Sorting::Sort => match self.verify_entries() {
+                Ok(()) => None,
+                Err(_) => {
+                    if self.link.is_some() && extensions.should_write(extension::link::SIGNATURE).is_some() {
+                        return Err(invalid_input_msg(
+                            "entries of a split index can't be sorted as they are referred to by position",
+                        ));
+                    }
+                    let mut entries: Vec<_> = self.entries.iter().collect();
+                    entries.sort_by(|a, b| a.cmp(b, self));
+                    self.verify_entry_order(entries.iter().copied())
+                        .map_err(invalid_input)?;
+                    Some(entries)
+                }
+            },
+        };
+        let version = self.detect_required_version(version);
 
         let mut write = CountBytes::new(out);
         let num_entries = self
@@ -65,8 +137,12 @@ impl State {
             .expect("definitely not 4billion entries");
 
         let offset_to_entries = header(&mut write, version, num_entries)?;
-        let offset_to_extensions = entries(&mut write, self, offset_to_entries)?;
-        let (extension_toc, out) = self.write_extensions(write, offset_to_extensions, extensions)?;
+        let entries_reordered = sorted_entries.is_some();
+        let offset_to_extensions = match sorted_entries {
+            Some(sorted_entries) => entries(&mut write, self, sorted_entries, version, offset_to_entries)?,
+            None => entries(&mut write, self, self.entries.iter(), version, offset_to_entries)?,
+        };
+        let (extension_toc, out) = self.write_extensions(write, offset_to_extensions, extensions, entries_reordered)?;
 
         if num_entries > 0
             && extensions
@@ -85,16 +161,25 @@ impl State {
         mut write: CountBytes<T>,
         offset_to_extensions: u32,
         extensions: Extensions,
+        entries_reordered: bool,
     ) -> std::io::Result<(Vec<(extension::Signature, u32)>, T)>
     where
         T: std::io::Write,
     {
         type WriteExtFn<'a> = &'a dyn Fn(&mut dyn std::io::Write) -> Option<std::io::Result<extension::Signature>>;
-        let extensions: &[WriteExtFn<'_>] = &[&|write| {
-            extensions
-                .should_write(extension::tree::SIGNATURE)
-                .and_then(|signature| self.tree().map(|tree| tree.write_to(write).map(|_| signature)))
-        }];
+        let extensions: &[WriteExtFn<'_>] = &[
+            &|write| {
+                extensions
+                    .should_write(extension::link::SIGNATURE)
+                    .and_then(|signature| self.link().map(|link| link.write_to(write).map(|_| signature)))
+            },
+            &|write| {
+                extensions
+                    .should_write(extension::tree::SIGNATURE)
+                    .filter(|_| !entries_reordered)
+                    .and_then(|signature| self.tree().map(|tree| tree.write_to(write).map(|_| signature)))
+            },
+        ];
 
         let mut offset_to_previous_ext = offset_to_extensions;
         let mut out = Vec::with_capacity(5);
@@ -111,11 +196,14 @@ impl State {
 }
 
 impl State {
-    fn detect_required_version(&self) -> Version {
-        self.entries
+    /// Return the `desired` version, unless our entries require a higher one.
+    fn detect_required_version(&self, desired: Option<Version>) -> Version {
+        let required = self
+            .entries
             .iter()
             .find_map(|e| e.flags.contains(entry::Flags::EXTENDED).then(|| Version::V3))
-            .unwrap_or(Version::V2)
+            .unwrap_or(Version::V2);
+        desired.map_or(required, |desired| desired.max(required))
     }
 }
 
@@ -137,8 +225,23 @@ fn header<T: std::io::Write>(
     Ok(out.count)
 }
 
-fn entries<T: std::io::Write>(out: &mut CountBytes<T>, state: &State, header_size: u32) -> Result<u32, std::io::Error> {
-    for entry in state.entries() {
+fn entries<'a, T: std::io::Write>(
+    out: &mut CountBytes<T>,
+    state: &State,
+    entries_in_order: impl IntoIterator<Item = &'a Entry>,
+    version: Version,
+    header_size: u32,
+) -> Result<u32, std::io::Error> {
+    if version == Version::V4 {
+        let mut previous_path: &[u8] = &[];
+        for entry in entries_in_order {
+            entry.write_to_with_delta_path(&mut *out, state, previous_path)?;
+            previous_path = entry.path(state);
+        }
+        return Ok(out.count);
+    }
+
+    for entry in entries_in_order {
         entry.write_to(&mut *out, state)?;
         match (out.count - header_size) % 8 {
             0 => {}
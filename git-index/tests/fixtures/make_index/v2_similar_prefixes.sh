@@ -0,0 +1,13 @@
+#!/bin/bash
+set -eu -o pipefail
+
+export GIT_INDEX_VERSION=2;
+git init -q
+git config index.threads 1
+
+touch a src.txt srcz
+mkdir -p src/sub src-extra
+touch src/a src/sub/b src-extra/file
+
+git add .
+git commit -m "empty"
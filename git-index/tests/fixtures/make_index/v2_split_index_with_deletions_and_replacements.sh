@@ -0,0 +1,18 @@
+#!/bin/bash
+set -eu -o pipefail
+
+export GIT_INDEX_VERSION=2
+git init -q
+git config index.threads 1
+git config splitIndex.maxPercentChange 100
+
+# keep files older than the index so racily-clean entries are not replaced as well
+touch -d 2000-01-01 a b c d
+git add .
+git commit -m "empty"
+
+git update-index --split-index
+
+git rm -q --cached b
+echo change > c && touch -d 2000-01-01 c
+git add c
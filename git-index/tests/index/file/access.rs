@@ -1,5 +1,21 @@
+use git_testtools::hex_to_id;
+
 use crate::index::file::read;
 
+#[test]
+fn root_tree_id() {
+    assert_eq!(
+        read::file("v2_more_files").root_tree_id(),
+        Some(hex_to_id("c9b29c3168d8e677450cc650238b23d9390801fb")),
+        "a fully cached tree provides the id of the root tree"
+    );
+    assert_eq!(
+        read::loose_file("very-long-path").root_tree_id(),
+        None,
+        "invalidated trees don't provide an id"
+    );
+}
+
 #[test]
 fn entry_by_path_and_stage() {
     let file = read::file("v4_more_files_IEOT");
@@ -13,3 +29,81 @@ fn entry_by_path_and_stage() {
         assert_eq!(file.entry_by_path_and_stage(path, 0), Some(entry));
     }
 }
+
+#[test]
+fn set_stat_only_changes_the_stat_of_the_matching_entry() {
+    let mut file = read::file("v4_more_files_IEOT");
+    let expected_entries = file.entries().to_vec();
+    let path = expected_entries[1].path(&file).to_owned();
+    assert!(!file.is_changed(), "freshly read states are unchanged");
+    let stat = git_index::entry::Stat {
+        size: 42,
+        ..Default::default()
+    };
+
+    assert!(file.set_stat(path.as_ref(), stat));
+    assert!(
+        !file.set_stat("does-not-exist".into(), stat),
+        "nothing happens if the path doesn't exist"
+    );
+    assert!(file.is_changed(), "the state knows it has to be written back");
+
+    for (idx, (actual, expected)) in file.entries().iter().zip(expected_entries.iter()).enumerate() {
+        if idx == 1 {
+            assert_eq!(actual.stat, stat);
+            assert_eq!(actual.id, expected.id, "the id is never changed");
+            assert_eq!(actual.flags, expected.flags, "flags are untouched");
+        } else {
+            assert_eq!(actual, expected, "other entries are untouched");
+        }
+    }
+}
+
+#[test]
+fn refresh_stats_updates_all_matching_entries() {
+    let mut file = read::file("v4_more_files_IEOT");
+    let paths: Vec<_> = file.entries().iter().map(|e| e.path(&file).to_owned()).collect();
+    let stat = git_index::entry::Stat {
+        size: 42,
+        ..Default::default()
+    };
+
+    let updated = file.refresh_stats(
+        paths
+            .iter()
+            .take(2)
+            .map(|p| (p.as_ref(), stat))
+            .chain(Some(("does-not-exist".into(), stat))),
+    );
+    assert_eq!(updated, 2, "only existing entries are counted");
+    assert!(file.entries()[..2].iter().all(|e| e.stat == stat));
+    assert!(file.entries()[2..].iter().all(|e| e.stat != stat));
+}
+
+#[test]
+fn entries_with_prefix() {
+    let file = read::file("v2_similar_prefixes");
+    let paths = |prefix: &str| -> Vec<_> {
+        file.entries_with_prefix(prefix.into())
+            .iter()
+            .map(|e| e.path(&file).to_string())
+            .collect()
+    };
+
+    assert_eq!(paths("src/"), vec!["src/a", "src/sub/b"]);
+    assert_eq!(paths("src"), paths("src/"), "the trailing slash is optional");
+    assert_eq!(paths("src/sub"), vec!["src/sub/b"]);
+    assert_eq!(
+        paths("src-extra"),
+        vec!["src-extra/file"],
+        "directories sorting before the prefix are not included"
+    );
+    assert!(paths("sr").is_empty(), "only whole directories match");
+    assert!(paths("src/a").is_empty(), "files are not directories");
+    assert!(paths("missing").is_empty());
+    assert_eq!(
+        paths("").len(),
+        file.entries().len(),
+        "the empty prefix matches everything"
+    );
+}
@@ -127,6 +127,28 @@ fn v2_split_index() {
     assert!(file.link().is_some());
 }
 
+#[test]
+fn v2_split_index_with_deletions_and_replacements() {
+    let file = file("v2_split_index_with_deletions_and_replacements");
+    let bitmaps = file
+        .link()
+        .expect("present")
+        .bitmaps
+        .as_ref()
+        .expect("changes to the shared index are recorded");
+
+    fn set_bits(bitmap: &git_bitmap::ewah::Vec) -> Vec<usize> {
+        let mut out = Vec::new();
+        bitmap.for_each_set_bit(|idx| {
+            out.push(idx);
+            Some(())
+        });
+        out
+    }
+    assert_eq!(set_bits(&bitmaps.delete), [1], "'b' was removed from the index");
+    assert_eq!(set_bits(&bitmaps.replace), [2], "'c' was changed");
+}
+
 #[test]
 fn v3_extended_flags() {
     let file = loose_file("extended-flags");
@@ -15,6 +15,11 @@ fn roundtrips() -> crate::Result {
         (Generated("V2_empty"), Default::default()),
         (Generated("v2_more_files"), all_ext_but_eoie()),
         (Generated("v2_all_file_kinds"), all_ext_but_eoie()),
+        (Generated("v2_split_index"), all_ext_but_eoie()),
+        (
+            Generated("v2_split_index_with_deletions_and_replacements"),
+            all_ext_but_eoie(),
+        ),
     ];
 
     for (fixture, options) in input {
@@ -44,7 +49,10 @@ fn roundtrips() -> crate::Result {
 #[test]
 fn state_comparisons_with_various_extension_configurations() {
     fn options_with(extensions: write::Extensions) -> Options {
-        Options { extensions }
+        Options {
+            extensions,
+            ..Default::default()
+        }
     }
 
     for fixture in [
@@ -67,10 +75,12 @@ fn state_comparisons_with_various_extension_configurations() {
             options_with(write::Extensions::All),
             options_with(write::Extensions::Given {
                 tree_cache: true,
+                link: true,
                 end_of_index_entry: true,
             }),
             options_with(write::Extensions::Given {
                 tree_cache: false,
+                link: false,
                 end_of_index_entry: true,
             }),
         ] {
@@ -101,6 +111,234 @@ fn extended_flags_automatically_upgrade_the_version_to_avoid_data_loss() -> crat
     Ok(())
 }
 
+#[test]
+fn version_overrides_never_downgrade_below_what_entries_require() -> crate::Result {
+    fn options_with(version: Option<Version>) -> Options {
+        Options {
+            version,
+            ..Default::default()
+        }
+    }
+    let mut index = git_index::File::at(
+        fixture_index_path("v2_more_files"),
+        git_hash::Kind::Sha1,
+        Default::default(),
+    )?;
+    assert_eq!(index.version(), Version::V2);
+
+    for (has_extended_flags, version, expected_version) in [
+        (false, None, Version::V2),
+        (false, Some(Version::V2), Version::V2),
+        (false, Some(Version::V3), Version::V3),
+        (false, Some(Version::V4), Version::V4),
+        (true, None, Version::V3),
+        (true, Some(Version::V2), Version::V3),
+        (true, Some(Version::V3), Version::V3),
+        (true, Some(Version::V4), Version::V4),
+    ] {
+        if has_extended_flags {
+            index.entries_mut()[0].flags.insert(entry::Flags::EXTENDED);
+        }
+        let options = options_with(version);
+        let mut buf = Vec::new();
+        let (actual_version, _digest) = index.write_to(&mut buf, options)?;
+        assert_eq!(
+            actual_version, expected_version,
+            "extended flags: {}, desired version: {:?}",
+            has_extended_flags, version
+        );
+
+        let (actual, _) = State::from_bytes(&buf, FileTime::now(), git_hash::Kind::Sha1, Default::default())?;
+        compare_states(&actual, actual_version, &index, options, "v2_more_files");
+    }
+    Ok(())
+}
+
+#[test]
+fn v4_indices_can_be_written_with_delta_paths() -> crate::Result {
+    let expected = git_index::File::at(
+        fixture_index_path("v4_more_files_IEOT"),
+        git_hash::Kind::Sha1,
+        Default::default(),
+    )?;
+    assert_eq!(expected.version(), Version::V4);
+
+    let options = Options {
+        version: Some(Version::V4),
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    let (actual_version, _digest) = expected.write_to(&mut buf, options)?;
+    assert_eq!(actual_version, Version::V4);
+
+    let (actual, _) = State::from_bytes(&buf, FileTime::now(), git_hash::Kind::Sha1, Default::default())?;
+    compare_states(&actual, actual_version, &expected, options, "v4_more_files_IEOT");
+
+    let mut v2_buf = Vec::new();
+    expected.write_to(&mut v2_buf, Default::default())?;
+    assert!(buf.len() < v2_buf.len(), "paths are compressed");
+    Ok(())
+}
+
+#[test]
+fn entry_ids_and_trailer_have_the_width_of_the_object_hash() -> crate::Result {
+    let index = git_index::File::at(fixture_index_path("v2"), git_hash::Kind::Sha1, Default::default())?;
+    let object_hash = index.object_hash();
+    assert_eq!(index.entries().len(), 1, "the entry is the only one in the file");
+
+    let mut buf = Vec::new();
+    let (_version, checksum) = index.write_to(&mut buf, Default::default())?;
+    assert_eq!(checksum.as_bytes().len(), object_hash.len_in_bytes());
+    assert_eq!(
+        &buf[buf.len() - object_hash.len_in_bytes()..],
+        checksum.as_bytes(),
+        "the trailer is the checksum over all prior bytes"
+    );
+
+    let entry_id_start = 12 /* header */ + 40 /* stat and mode */;
+    assert_eq!(
+        &buf[entry_id_start..][..object_hash.len_in_bytes()],
+        index.entries()[0].id.as_bytes(),
+        "ids are written in full"
+    );
+
+    let (actual, _) = State::from_bytes(&buf, FileTime::now(), object_hash, Default::default())?;
+    assert_eq!(actual.entries(), index.entries());
+    Ok(())
+}
+
+#[test]
+fn split_index_bitmaps_are_written_back_as_they_were_read() -> crate::Result {
+    let fixture = "v2_split_index_with_deletions_and_replacements";
+    let path = fixture_index_path(fixture);
+    let index = git_index::File::at(&path, git_hash::Kind::Sha1, Default::default())?;
+    assert!(index.link().and_then(|link| link.bitmaps.as_ref()).is_some());
+
+    let mut buf = Vec::new();
+    index.write_to(&mut buf, all_ext_but_eoie())?;
+    compare_raw_bytes(&buf, &std::fs::read(&path)?, fixture);
+    Ok(())
+}
+
+#[test]
+fn unsorted_entries_are_rejected_unless_they_are_assumed_to_be_sorted() -> crate::Result {
+    let mut index = git_index::File::at(
+        fixture_index_path("v2_more_files"),
+        git_hash::Kind::Sha1,
+        Default::default(),
+    )?;
+    assert!(index.entries().len() > 1, "need multiple entries to change their order");
+    index.entries_mut().swap(0, 1);
+
+    let mut buf = Vec::new();
+    let err = index
+        .write_to(&mut buf, Default::default())
+        .expect_err("entries are out of order");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert!(
+        err.get_ref()
+            .and_then(|err| err.downcast_ref::<git_index::verify::entries::Error>())
+            .is_some(),
+        "the underlying verification error is retained"
+    );
+    assert!(buf.is_empty(), "nothing is written if the check fails");
+
+    index.write_to(
+        &mut buf,
+        Options {
+            sorting: write::Sorting::AssumeSorted,
+            ..Default::default()
+        },
+    )?;
+    assert!(!buf.is_empty(), "the check can be skipped to write entries as they are");
+    Ok(())
+}
+
+#[test]
+fn unsorted_entries_can_be_written_in_sorted_order() -> crate::Result {
+    let expected = git_index::File::at(
+        fixture_index_path("v2_more_files"),
+        git_hash::Kind::Sha1,
+        Default::default(),
+    )?;
+    let mut expected_bytes = Vec::new();
+    expected.write_to(&mut expected_bytes, Default::default())?;
+    assert!(expected.tree().is_some(), "the fixture needs a tree cache");
+    let mut expected_bytes_without_tree = Vec::new();
+    expected.write_to(
+        &mut expected_bytes_without_tree,
+        Options {
+            extensions: write::Extensions::Given {
+                tree_cache: false,
+                link: true,
+                end_of_index_entry: true,
+            },
+            ..Default::default()
+        },
+    )?;
+
+    let mut index = git_index::File::at(
+        fixture_index_path("v2_more_files"),
+        git_hash::Kind::Sha1,
+        Default::default(),
+    )?;
+    index.entries_mut().reverse();
+    assert!(index.verify_entries().is_err(), "entries are out of order now");
+
+    let sort = Options {
+        sorting: write::Sorting::Sort,
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    index.write_to(&mut buf, sort)?;
+    assert_eq!(
+        buf, expected_bytes_without_tree,
+        "entries are written in the order git requires, without the tree cache which may not match them anymore"
+    );
+    assert!(
+        index.verify_entries().is_err(),
+        "the order of entries in memory is left unchanged"
+    );
+
+    index.sort_entries();
+    index.verify_entries()?;
+    assert_eq!(
+        index.entries(),
+        expected.entries(),
+        "entries can also be sorted in memory"
+    );
+
+    let mut buf = Vec::new();
+    index.write_to(&mut buf, Default::default())?;
+    assert_eq!(buf, expected_bytes);
+    Ok(())
+}
+
+#[test]
+fn entries_with_the_same_path_and_stage_are_rejected_even_when_sorting() -> crate::Result {
+    let mut index = git_index::File::at(
+        fixture_index_path("v2_more_files"),
+        git_hash::Kind::Sha1,
+        Default::default(),
+    )?;
+    let first = index.entries()[0].clone();
+    index.entries_mut()[1] = first;
+
+    let mut buf = Vec::new();
+    let err = index
+        .write_to(
+            &mut buf,
+            Options {
+                sorting: write::Sorting::Sort,
+                ..Default::default()
+            },
+        )
+        .expect_err("duplicate entries can't be sorted");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert!(buf.is_empty());
+    Ok(())
+}
+
 fn compare_states(actual: &State, actual_version: Version, expected: &State, options: Options, fixture: &str) {
     actual.verify_entries().expect("valid");
     actual.verify_extensions(false, no_find).expect("valid");
@@ -115,6 +353,15 @@ fn compare_states(actual: &State, actual_version: Version, expected: &State, opt
         "tree extension mismatch in {}",
         fixture
     );
+    assert_eq!(
+        actual.link().map(|link| link.shared_index_checksum),
+        options
+            .extensions
+            .should_write(extension::link::SIGNATURE)
+            .and_then(|_| expected.link().map(|link| link.shared_index_checksum)),
+        "link extension mismatch in {}",
+        fixture
+    );
     assert_eq!(
         actual.entries().len(),
         expected.entries().len(),
@@ -154,6 +401,7 @@ fn all_ext_but_eoie() -> Options {
         extensions: write::Extensions::Given {
             end_of_index_entry: false,
             tree_cache: true,
+            link: true,
         },
         ..Default::default()
     }
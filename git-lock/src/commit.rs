@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
 use crate::{File, Marker};
 
@@ -76,4 +76,87 @@ impl File {
             }),
         }
     }
+
+    /// Like [`commit()`][File::commit()], but retry up to `retries` times with `delay` between attempts if moving the lock file
+    /// into place fails with a transient error, as it can happen on network filesystems that report the resource as busy.
+    ///
+    /// If all attempts fail, the changes are copied into the resource in place and synced to disk, and the lock file is removed.
+    /// As opposed to moving the lock file into place this isn't atomic, so readers may observe a partially written resource
+    /// while copying, and if copying fails the resource may be left truncated. The error is returned in that case.
+    ///
+    /// All other errors are returned immediately.
+    pub fn try_commit(self, retries: usize, delay: Duration) -> Result<(PathBuf, Option<std::fs::File>), Error<Self>> {
+        let mut file = self;
+        for attempt in 0..=retries {
+            match file.commit() {
+                Ok(res) => return Ok(res),
+                Err(err) if is_transient(&err.error) => {
+                    file = err.instance;
+                    if attempt < retries {
+                        std::thread::sleep(delay);
+                    }
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        let resource_path = file.resource_path();
+        match copy_into_place(file.lock_path(), &resource_path) {
+            Ok(resource) => Ok((resource_path, Some(resource))),
+            Err(error) => Err(Error { error, instance: file }),
+        }
+    }
+}
+
+fn is_transient(err: &std::io::Error) -> bool {
+    /// `EBUSY` on linux, MacOS and the BSDs.
+    #[cfg(unix)]
+    const BUSY: &[i32] = &[16];
+    /// `ERROR_SHARING_VIOLATION` and `ERROR_LOCK_VIOLATION`.
+    #[cfg(windows)]
+    const BUSY: &[i32] = &[32, 33];
+    #[cfg(not(any(unix, windows)))]
+    const BUSY: &[i32] = &[];
+
+    err.kind() == std::io::ErrorKind::WouldBlock || err.raw_os_error().map_or(false, |code| BUSY.contains(&code))
+}
+
+/// Overwrite the resource at `resource_path` in place with the contents of the lock at `lock_path`, for use if the lock can't be
+/// moved onto the resource, and return the open resource.
+fn copy_into_place(lock_path: &std::path::Path, resource_path: &std::path::Path) -> std::io::Result<std::fs::File> {
+    let mut lock = std::fs::File::open(lock_path)?;
+    let mut resource = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(resource_path)?;
+    std::io::copy(&mut lock, &mut resource)?;
+    resource.sync_all()?;
+    Ok(resource)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_into_place_overwrites_the_resource_in_place() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let resource = dir.path().join("resource");
+        let lock = dir.path().join("resource.lock");
+        let alias = dir.path().join("alias-of-resource");
+        std::fs::write(&resource, b"previous state, which is longer")?;
+        std::fs::hard_link(&resource, &alias)?;
+        std::fs::write(&lock, b"new state")?;
+
+        copy_into_place(&lock, &resource)?;
+        assert_eq!(std::fs::read(&resource)?, b"new state", "previous content is truncated");
+        assert_eq!(
+            std::fs::read(&alias)?,
+            b"new state",
+            "the resource wasn't replaced by another file but written in place"
+        );
+        assert!(lock.is_file(), "the lock is removed by its owner");
+        Ok(())
+    }
 }
@@ -86,6 +86,53 @@ mod commit {
     }
 }
 
+mod try_commit {
+    use std::{io::Write, time::Duration};
+
+    use git_lock::acquire::Fail;
+
+    #[test]
+    fn it_commits_like_commit_if_nothing_is_busy() -> crate::Result {
+        let dir = tempfile::tempdir()?;
+        let resource = dir.path().join("resource-existing.ext");
+        std::fs::write(&resource, b"old state")?;
+        let mut file = git_lock::File::acquire_to_update_resource(&resource, Fail::Immediately, None)?;
+        let lock_path = file.lock_path().to_owned();
+        file.with_mut(|out| out.write_all(b"hello world"))?;
+
+        let (resource_path, open_file) = file.try_commit(3, Duration::from_secs(60))?;
+        assert_eq!(resource_path, resource);
+        assert!(open_file.is_some(), "the lock file was moved into place");
+        assert_eq!(std::fs::read(resource)?, b"hello world");
+        assert!(!lock_path.is_file());
+        Ok(())
+    }
+
+    #[test]
+    fn other_errors_are_returned_immediately_without_retries_or_copying() -> crate::Result {
+        let dir = tempfile::tempdir()?;
+        let resource = dir.path().join("resource-existing.ext");
+        std::fs::create_dir(&resource)?;
+        let file = git_lock::File::acquire_to_update_resource(&resource, Fail::Immediately, None)?;
+        let lock_path = file.lock_path().to_owned();
+
+        let start = std::time::Instant::now();
+        let err = file
+            .try_commit(3, Duration::from_secs(60))
+            .expect_err("cannot commit onto existing directory");
+        assert!(start.elapsed() < Duration::from_secs(60), "there was no retry");
+        assert!(err.instance.lock_path().is_file(), "the lock is still present");
+        assert!(resource.is_dir(), "the resource wasn't touched");
+
+        drop(err);
+        assert!(
+            !lock_path.is_file(),
+            "the lock file is still owned by the lock instance"
+        );
+        Ok(())
+    }
+}
+
 mod acquire {
     use std::io::{ErrorKind, Write};
 
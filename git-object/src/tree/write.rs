@@ -4,16 +4,18 @@ use bstr::{BString, ByteSlice};
 
 use crate::{
     encode::SPACE,
-    tree::{Entry, EntryRef},
+    tree::{Entry, EntryMode, EntryRef},
     Kind, Tree, TreeRef,
 };
 
-/// The Error used in [`Tree::write_to()`][crate::WriteTo::write_to()].
+/// The Error used in [`Tree::write_to()`][crate::WriteTo::write_to()] and [`Tree::check_gitmodules_safety()`].
 #[derive(Debug, thiserror::Error)]
 #[allow(missing_docs)]
 pub enum Error {
     #[error("Newlines are invalid in file paths: {name:?}")]
     NewlineInFilename { name: BString },
+    #[error("Symbolic links named like '.gitmodules' could be used to inject submodule configuration: {name:?}")]
+    SymlinkedGitmodules { name: BString },
 }
 
 impl From<Error> for io::Error {
@@ -109,3 +111,46 @@ impl<'a> crate::WriteTo for TreeRef<'a> {
         Kind::Tree
     }
 }
+
+/// Validation
+impl Tree {
+    /// Fail if one of our entries is a symbolic link which could be mistaken for `.gitmodules` on any platform, which would
+    /// allow to inject submodule configuration once checked out.
+    ///
+    /// Use it when creating trees from untrusted input, as [serialization][crate::WriteTo::write_to()] writes all
+    /// entries faithfully.
+    pub fn check_gitmodules_safety(&self) -> Result<(), Error> {
+        check_gitmodules_safety(self.entries.iter().map(|e| (e.mode, e.filename.as_bstr())))
+    }
+}
+
+/// Validation
+impl<'a> TreeRef<'a> {
+    /// Fail if one of our entries is a symbolic link which could be mistaken for `.gitmodules` on any platform, which would
+    /// allow to inject submodule configuration once checked out.
+    ///
+    /// Use it when creating trees from untrusted input, as [serialization][crate::WriteTo::write_to()] writes all
+    /// entries faithfully.
+    pub fn check_gitmodules_safety(&self) -> Result<(), Error> {
+        check_gitmodules_safety(self.entries.iter().map(|e| (e.mode, e.filename)))
+    }
+}
+
+fn check_gitmodules_safety<'a>(entries: impl Iterator<Item = (EntryMode, &'a bstr::BStr)>) -> Result<(), Error> {
+    for (mode, filename) in entries {
+        if mode == EntryMode::Link && !is_gitmodules_safe_everywhere(filename) {
+            return Err(Error::SymlinkedGitmodules {
+                name: filename.to_owned(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Return true if `name` can't be mistaken for `.gitmodules` on any platform, as trees are shared across all of them.
+fn is_gitmodules_safe_everywhere(name: &bstr::BStr) -> bool {
+    use git_validate::path::{is_gitmodules_safe, Platform};
+    [Platform::Unix, Platform::Windows, Platform::MacOs]
+        .iter()
+        .all(|os| is_gitmodules_safe(name, *os))
+}
@@ -88,7 +88,59 @@ mod commit {
 }
 
 mod tree {
+    use git_object::{
+        tree::{Entry, EntryMode},
+        Tree, WriteTo,
+    };
+
     round_trip!(git_object::Tree, git_object::TreeRef, "tree/everything.tree");
+
+    fn tree_with(mode: EntryMode, filename: &str) -> Tree {
+        Tree {
+            entries: vec![Entry {
+                mode,
+                filename: filename.into(),
+                oid: git_hash::Kind::Sha1.null(),
+            }],
+        }
+    }
+
+    #[test]
+    fn symlinks_aliasing_gitmodules_on_any_platform_are_rejected_by_the_safety_check() {
+        for name in [".gitmodules", ".GITMODULES", "GITMOD~1", ".gitmodules\u{200c}"] {
+            let tree = tree_with(EntryMode::Link, name);
+            let err = tree
+                .check_gitmodules_safety()
+                .expect_err("symlinks can't be named like .gitmodules");
+            assert!(
+                err.to_string().starts_with(
+                    "Symbolic links named like '.gitmodules' could be used to inject submodule configuration"
+                ),
+                "{}",
+                err
+            );
+
+            let mut buf = Vec::new();
+            tree.write_to(&mut buf)
+                .expect("serialization is faithful and doesn't check");
+            let tree_ref = git_object::TreeRef::from_bytes(&buf).expect("valid");
+            assert!(
+                tree_ref.check_gitmodules_safety().is_err(),
+                "the check is available on borrowed trees as well"
+            );
+            assert_eq!(Tree::from(tree_ref), tree, "such trees round-trip");
+        }
+    }
+
+    #[test]
+    fn gitmodules_files_and_other_symlinks_are_fine() {
+        assert!(tree_with(EntryMode::Blob, ".gitmodules")
+            .check_gitmodules_safety()
+            .is_ok());
+        assert!(tree_with(EntryMode::Link, ".gitmodules-link")
+            .check_gitmodules_safety()
+            .is_ok());
+    }
 }
 
 mod blob {
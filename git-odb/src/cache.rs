@@ -89,6 +89,11 @@ impl<S> Cache<S> {
         self.object_cache = None;
         self.new_object_cache = None;
     }
+    /// Replace the current pack and object caches with new ones created by their constructors, discarding all cached data.
+    pub fn clear_caches(&mut self) {
+        self.pack_cache = self.new_pack_cache.as_ref().map(|create| RefCell::new(create()));
+        self.object_cache = self.new_object_cache.as_ref().map(|create| RefCell::new(create()));
+    }
 }
 
 impl<S> From<S> for Cache<S>
@@ -52,6 +52,16 @@ pub use error::Error;
 use crate::store::types::{Generation, IndexAndPacks, MutableIndexAndPack, SlotMapIndex};
 
 impl super::Store {
+    /// Scan the objects directory and its alternates to pick up new packs and drop those that were removed, for example after
+    /// a pack was received or after garbage collection.
+    ///
+    /// This happens automatically if an object can't be found, unless [`refresh_never()`][super::Handle::refresh_never()]
+    /// was called on the handle performing the lookup.
+    pub fn refresh(&self) -> Result<(), Error> {
+        self.consolidate_with_disk_state(false /* needs init */, false /*load one new index*/)
+            .map(|_| ())
+    }
+
     /// Load all indices, refreshing from disk only if needed.
     pub(crate) fn load_all_indices(&self) -> Result<Snapshot, Error> {
         let mut snapshot = self.collect_snapshot();
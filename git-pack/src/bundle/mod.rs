@@ -3,6 +3,8 @@ pub mod init;
 
 mod find;
 ///
+pub mod objects;
+///
 pub mod write;
 
 ///
@@ -0,0 +1,64 @@
+use git_hash::ObjectId;
+
+use crate::{cache, data, index};
+
+/// The item returned by [`Iter`], a fully resolved object along with its id and kind.
+pub type Item = Result<(ObjectId, git_object::Kind, Vec<u8>), data::decode_entry::Error>;
+
+/// An iterator over all objects in a [bundle][crate::Bundle] in the order they appear in the pack, created by
+/// [`Bundle::objects_in_pack_order()`][crate::Bundle::objects_in_pack_order()].
+pub struct Iter<'a, C> {
+    bundle: &'a crate::Bundle,
+    cache: C,
+    indices_by_offset: std::vec::IntoIter<index::EntryIndex>,
+}
+
+impl<'a, C> Iterator for Iter<'a, C>
+where
+    C: cache::DecodeEntry,
+{
+    type Item = Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.indices_by_offset.next()?;
+        let mut buf = Vec::new();
+        Some(
+            self.bundle
+                .get_object_by_index(idx, &mut buf, &mut self.cache)
+                .map(|(obj, _location)| obj.kind)
+                .map(|kind| (self.bundle.index.oid_at_index(idx).to_owned(), kind, buf)),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices_by_offset.size_hint()
+    }
+}
+
+impl<'a, C> ExactSizeIterator for Iter<'a, C> where C: cache::DecodeEntry {}
+
+impl crate::Bundle {
+    /// Return an iterator over all objects in this bundle, fully resolved and in the order in which they are stored in the pack.
+    ///
+    /// Each object is decoded on its own by applying its whole delta chain, just like [`find()`][crate::Bundle::find()] would,
+    /// so the work done for a base object is only reused if it is still in `cache`. Hence ref-deltas can only be resolved against
+    /// objects within this pack as well.
+    ///
+    /// Prefer [`index::File::traverse()`][crate::index::File::traverse()] with the delta-tree algorithm to decode each object
+    /// only once and in parallel if the order in which objects are seen doesn't matter.
+    pub fn objects_in_pack_order<C: cache::DecodeEntry>(&self, cache: C) -> Iter<'_, C> {
+        let mut offsets_and_indices: Vec<_> = (0..self.index.num_objects())
+            .map(|idx| (self.index.pack_offset_at_index(idx), idx))
+            .collect();
+        offsets_and_indices.sort_by_key(|(offset, _)| *offset);
+        Iter {
+            bundle: self,
+            cache,
+            indices_by_offset: offsets_and_indices
+                .into_iter()
+                .map(|(_, idx)| idx)
+                .collect::<Vec<_>>()
+                .into_iter(),
+        }
+    }
+}
@@ -14,4 +14,11 @@ pub enum Error {
     Perist(#[from] git_tempfile::handle::persist::Error<Writable>),
     #[error(transparent)]
     IndexWrite(#[from] crate::index::write::Error),
+    #[error("The object id {expected} does not match the actual id {actual} of the object to write")]
+    ObjectIdMismatch {
+        expected: git_hash::ObjectId,
+        actual: git_hash::ObjectId,
+    },
+    #[error("The CRC32 of the pack entry at offset {offset} doesn't match the one of the persisted pack data")]
+    CrcMismatch { offset: u64 },
 }
@@ -28,6 +28,7 @@ impl crate::Bundle {
     ///
     /// In the latter case, the functionality provided here is more akind of pack data stream validation.
     ///
+    /// * `pack_size` is the optional size of the `pack` stream in bytes, used as total for the read progress if known.
     /// * `progress` provides detailed progress information which can be discarded with [`git_features::progress::Discard`].
     /// * `should_interrupt` is checked regularly and when true, the whole operation will stop.
     /// * `thin_pack_base_object_lookup_fn` If set, we expect to see a thin-pack with objects that reference their base object by object id which is
@@ -41,6 +42,7 @@ impl crate::Bundle {
     ///   - Empty packs always have the same name and not handling this case will result in at most one superfluous pack.
     pub fn write_to_directory<P>(
         pack: impl io::BufRead,
+        pack_size: Option<u64>,
         directory: Option<impl AsRef<Path>>,
         mut progress: P,
         should_interrupt: &AtomicBool,
@@ -50,8 +52,9 @@ impl crate::Bundle {
     where
         P: Progress,
     {
+        assure_index_version_is_supported(options.index_version)?;
         let mut read_progress = progress.add_child("read pack");
-        read_progress.init(None, progress::bytes());
+        read_progress.init(pack_size.map(|s| s as usize), progress::bytes());
         let pack = progress::Read {
             inner: pack,
             progress: progress::ThroughputOnDrop::new(read_progress),
@@ -60,10 +63,13 @@ impl crate::Bundle {
         let object_hash = options.object_hash;
         let data_file = Arc::new(parking_lot::Mutex::new(io::BufWriter::with_capacity(
             64 * 1024,
-            match directory.as_ref() {
-                Some(directory) => git_tempfile::new(directory, ContainingDirectory::Exists, AutoRemove::Tempfile)?,
-                None => git_tempfile::new(std::env::temp_dir(), ContainingDirectory::Exists, AutoRemove::Tempfile)?,
-            },
+            new_tempfile(
+                directory
+                    .as_ref()
+                    .map_or_else(std::env::temp_dir, |d| d.as_ref().to_owned()),
+                options.temp_file_name.as_deref(),
+                "pack",
+            )?,
         )));
         let (pack_entries_iter, pack_version): (
             Box<dyn Iterator<Item = Result<data::input::Entry, data::input::Error>>>,
@@ -75,7 +81,7 @@ impl crate::Bundle {
                     should_interrupt,
                 };
                 let buffered_pack = io::BufReader::new(pack);
-                let pack_entries_iter = data::input::LookupRefDeltaObjectsIter::new(
+                let pack_entries_iter = data::input::LookupRefDeltaObjectsIter::new_with_progress(
                     data::input::BytesToEntriesIter::new_from_header(
                         buffered_pack,
                         options.iteration_mode,
@@ -83,6 +89,7 @@ impl crate::Bundle {
                         object_hash,
                     )?,
                     thin_pack_lookup_fn,
+                    progress.add_child("resolve thin-pack bases"),
                 );
                 let pack_version = pack_entries_iter.inner.version();
                 let pack_entries_iter = data::input::EntriesToBytesIter::new(
@@ -123,11 +130,12 @@ impl crate::Bundle {
             data_path,
             index_path,
             keep_path,
+            reverse_index_path,
         } = crate::Bundle::inner_write(
             directory,
             progress,
             options,
-            data_file,
+            DataFile::Temporary(data_file),
             pack_entries_iter,
             should_interrupt,
             pack_version,
@@ -140,6 +148,7 @@ impl crate::Bundle {
             data_path,
             index_path,
             keep_path,
+            reverse_index_path,
         })
     }
 
@@ -159,6 +168,7 @@ impl crate::Bundle {
         thin_pack_base_object_lookup_fn: Option<ThinPackLookupFnSend>,
         options: Options,
     ) -> Result<Outcome, Error> {
+        assure_index_version_is_supported(options.index_version)?;
         let mut read_progress = progress.add_child("read pack");
         read_progress.init(pack_size.map(|s| s as usize), progress::bytes());
         let pack = progress::Read {
@@ -166,10 +176,13 @@ impl crate::Bundle {
             progress: progress::ThroughputOnDrop::new(read_progress),
         };
 
-        let data_file = Arc::new(parking_lot::Mutex::new(io::BufWriter::new(match directory.as_ref() {
-            Some(directory) => git_tempfile::new(directory, ContainingDirectory::Exists, AutoRemove::Tempfile)?,
-            None => git_tempfile::new(std::env::temp_dir(), ContainingDirectory::Exists, AutoRemove::Tempfile)?,
-        })));
+        let data_file = Arc::new(parking_lot::Mutex::new(io::BufWriter::new(new_tempfile(
+            directory
+                .as_ref()
+                .map_or_else(std::env::temp_dir, |d| d.as_ref().to_owned()),
+            options.temp_file_name.as_deref(),
+            "pack",
+        )?)));
         let object_hash = options.object_hash;
         let eight_pages = 4096 * 8;
         let (pack_entries_iter, pack_version): (
@@ -182,7 +195,7 @@ impl crate::Bundle {
                     should_interrupt,
                 };
                 let buffered_pack = io::BufReader::with_capacity(eight_pages, pack);
-                let pack_entries_iter = data::input::LookupRefDeltaObjectsIter::new(
+                let pack_entries_iter = data::input::LookupRefDeltaObjectsIter::new_with_progress(
                     data::input::BytesToEntriesIter::new_from_header(
                         buffered_pack,
                         options.iteration_mode,
@@ -190,6 +203,7 @@ impl crate::Bundle {
                         object_hash,
                     )?,
                     thin_pack_lookup_fn,
+                    progress.add_child("resolve thin-pack bases"),
                 );
                 let pack_kind = pack_entries_iter.inner.version();
                 (Box::new(pack_entries_iter), pack_kind)
@@ -222,11 +236,90 @@ impl crate::Bundle {
             data_path,
             index_path,
             keep_path,
+            reverse_index_path,
+        } = crate::Bundle::inner_write(
+            directory,
+            progress,
+            options,
+            DataFile::Temporary(data_file),
+            pack_entries_iter,
+            should_interrupt,
+            pack_version,
+        )?;
+
+        Ok(Outcome {
+            index: outcome,
+            object_hash,
+            pack_version,
+            data_path,
+            index_path,
+            keep_path,
+            reverse_index_path,
+        })
+    }
+
+    /// Write a pack containing all `objects` along with a generated index into the `directory` if `Some`, or discard all output if `None`.
+    ///
+    /// This is useful to pack loose or in-memory objects without producing a pack data stream first.
+    ///
+    /// * `objects` are tuples of object id, kind and decompressed data, which are stored as base objects in the order they are received.
+    ///   The id of each object is verified to match its kind and data.
+    /// * `progress` provides detailed progress information which can be discarded with [`git_features::progress::Discard`].
+    /// * `should_interrupt` is checked regularly and when true, the whole operation will stop.
+    ///
+    /// `options` further configure how the task is performed, with [`Options::iteration_mode`] having no effect.
+    pub fn write_objects_to_directory(
+        objects: impl IntoIterator<Item = (git_hash::ObjectId, git_object::Kind, Vec<u8>)>,
+        directory: Option<impl AsRef<Path>>,
+        progress: impl Progress,
+        should_interrupt: &AtomicBool,
+        options: Options,
+    ) -> Result<Outcome, Error> {
+        assure_index_version_is_supported(options.index_version)?;
+        let object_hash = options.object_hash;
+        let pack_version = data::Version::default();
+        let data_file = Arc::new(parking_lot::Mutex::new(io::BufWriter::with_capacity(
+            64 * 1024,
+            new_tempfile(
+                directory
+                    .as_ref()
+                    .map_or_else(std::env::temp_dir, |d| d.as_ref().to_owned()),
+                options.temp_file_name.as_deref(),
+                "pack",
+            )?,
+        )));
+
+        let mut entries = Vec::new();
+        let mut pack_offset = data::header::encode(pack_version, 0).len() as u64;
+        for (id, kind, data) in objects {
+            let actual = compute_hash(kind, &data, object_hash);
+            if actual != id {
+                return Err(Error::ObjectIdMismatch { expected: id, actual });
+            }
+            let entry = data::input::Entry::from_data_obj(&git_object::Data::new(kind, &data), pack_offset)?;
+            pack_offset += entry.bytes_in_pack();
+            entries.push(Ok(entry));
+        }
+        let pack_entries_iter = data::input::EntriesToBytesIter::new(
+            entries.into_iter(),
+            LockWriter {
+                writer: data_file.clone(),
+            },
+            pack_version,
+            object_hash,
+        );
+
+        let WriteOutcome {
+            outcome,
+            data_path,
+            index_path,
+            keep_path,
+            reverse_index_path,
         } = crate::Bundle::inner_write(
             directory,
             progress,
             options,
-            data_file,
+            DataFile::Temporary(data_file),
             pack_entries_iter,
             should_interrupt,
             pack_version,
@@ -239,6 +332,71 @@ impl crate::Bundle {
             data_path,
             index_path,
             keep_path,
+            reverse_index_path,
+        })
+    }
+
+    /// Create an index for the existing pack data file at `pack_path` and write it into `directory` if `Some`, or discard it if `None`.
+    ///
+    /// This is useful to recover a lost index, similar to what `git index-pack` does. The index is named after the pack data file,
+    /// hence `pack-<hash>.pack` yields `pack-<hash>.idx`. A reverse index is written as well if [`Options::write_reverse_index`] is set.
+    /// The pack data file is left untouched and no `.keep` file is created.
+    ///
+    /// * `progress` provides detailed progress information which can be discarded with [`git_features::progress::Discard`].
+    /// * `should_interrupt` is checked regularly and when true, the whole operation will stop.
+    ///
+    /// `options` further configure how the task is performed.
+    pub fn index_from_pack(
+        pack_path: impl AsRef<Path>,
+        directory: Option<impl AsRef<Path>>,
+        mut progress: impl Progress,
+        should_interrupt: &AtomicBool,
+        options: Options,
+    ) -> Result<Outcome, Error> {
+        assure_index_version_is_supported(options.index_version)?;
+        let pack_path = pack_path.as_ref();
+        let pack_file = std::fs::File::open(pack_path)?;
+        let mut read_progress = progress.add_child("read pack");
+        read_progress.init(Some(pack_file.metadata()?.len() as usize), progress::bytes());
+        let pack = io::BufReader::new(interrupt::Read {
+            inner: progress::Read {
+                inner: pack_file,
+                progress: progress::ThroughputOnDrop::new(read_progress),
+            },
+            should_interrupt,
+        });
+        let object_hash = options.object_hash;
+        let pack_entries_iter = data::input::BytesToEntriesIter::new_from_header(
+            pack,
+            options.iteration_mode,
+            data::input::EntryDataMode::Crc32,
+            object_hash,
+        )?;
+        let pack_version = pack_entries_iter.version();
+        let WriteOutcome {
+            outcome,
+            data_path,
+            index_path,
+            keep_path,
+            reverse_index_path,
+        } = crate::Bundle::inner_write(
+            directory,
+            progress,
+            options,
+            DataFile::Existing(pack_path.to_owned()),
+            pack_entries_iter,
+            should_interrupt,
+            pack_version,
+        )?;
+
+        Ok(Outcome {
+            index: outcome,
+            pack_version,
+            object_hash,
+            index_path,
+            data_path,
+            keep_path,
+            reverse_index_path,
         })
     }
 
@@ -250,24 +408,28 @@ impl crate::Bundle {
             iteration_mode: _,
             index_version: index_kind,
             object_hash,
+            write_reverse_index,
+            temp_file_name,
+            use_mmap,
+            verify_crc_on_write,
         }: Options,
-        data_file: SharedTempFile,
+        data_file: DataFile,
         pack_entries_iter: impl Iterator<Item = Result<data::input::Entry, data::input::Error>>,
         should_interrupt: &AtomicBool,
         pack_version: data::Version,
     ) -> Result<WriteOutcome, Error> {
+        let mut entry_crcs = EntryCrcs::default();
+        let pack_entries_iter = record_crc32(pack_entries_iter, verify_crc_on_write, &mut entry_crcs);
         let indexing_progress = progress.add_child("create index file");
         Ok(match directory {
             Some(directory) => {
                 let directory = directory.as_ref();
-                let mut index_file = git_tempfile::new(directory, ContainingDirectory::Exists, AutoRemove::Tempfile)?;
+                let mut index_file = new_tempfile(directory, temp_file_name.as_deref(), "idx")?;
 
-                let outcome = crate::index::File::write_data_iter_to_stream(
+                let mut reverse_index = None;
+                let outcome = crate::index::File::write_data_iter_to_stream_with_checkpoints(
                     index_kind,
-                    {
-                        let data_file = Arc::clone(&data_file);
-                        move || new_pack_file_resolver(data_file)
-                    },
+                    || data_file.resolver(use_mmap),
                     pack_entries_iter,
                     thread_limit,
                     indexing_progress,
@@ -275,19 +437,58 @@ impl crate::Bundle {
                     should_interrupt,
                     object_hash,
                     pack_version,
+                    |checkpoint| {
+                        if let crate::index::write::Checkpoint::TreeResolved {
+                            entries_sorted_by_oid,
+                            pack_hash,
+                        } = checkpoint
+                        {
+                            if write_reverse_index {
+                                let mut buf = Vec::new();
+                                crate::index::write::write_reverse_index_to_stream(
+                                    entries_sorted_by_oid.iter(),
+                                    pack_hash,
+                                    object_hash,
+                                    &mut buf,
+                                )
+                                .expect("writing to memory never fails");
+                                reverse_index = Some(buf);
+                            }
+                        }
+                    },
                 )?;
+                if verify_crc_on_write {
+                    entry_crcs.verify(data_file.resolver(use_mmap)?)?;
+                }
 
-                let data_path = directory.join(format!("pack-{}.pack", outcome.data_hash.to_hex()));
-                let index_path = data_path.with_extension("idx");
-                let keep_path = data_path.with_extension("keep");
-
-                std::fs::write(&keep_path, b"")?;
-                Arc::try_unwrap(data_file)
-                    .expect("only one handle left after pack was consumed")
-                    .into_inner()
-                    .into_inner()
-                    .map_err(|err| Error::from(err.into_error()))?
-                    .persist(&data_path)?;
+                let (data_path, index_path, keep_path) = match data_file {
+                    DataFile::Temporary(data_file) => {
+                        let data_path = directory.join(format!("pack-{}.pack", outcome.data_hash.to_hex()));
+                        let index_path = data_path.with_extension("idx");
+                        let keep_path = data_path.with_extension("keep");
+
+                        std::fs::write(&keep_path, b"")?;
+                        Arc::try_unwrap(data_file)
+                            .expect("only one handle left after pack was consumed")
+                            .into_inner()
+                            .into_inner()
+                            .map_err(|err| Error::from(err.into_error()))?
+                            .persist(&data_path)?;
+                        (data_path, index_path, Some(keep_path))
+                    }
+                    DataFile::Existing(data_path) => {
+                        let index_path = directory
+                            .join(data_path.file_name().ok_or_else(|| {
+                                io::Error::new(io::ErrorKind::InvalidInput, "the pack data path must have a file name")
+                            })?)
+                            .with_extension("idx");
+                        (data_path, index_path, None)
+                    }
+                };
+                // The reverse index goes first so it's present by the time readers discover the pack through its index.
+                let reverse_index_path = reverse_index
+                    .map(|data| new_reverse_index(directory, temp_file_name.as_deref(), &index_path, &data))
+                    .transpose()?;
                 index_file
                     .persist(&index_path)
                     .map_err(|err| {
@@ -301,13 +502,14 @@ impl crate::Bundle {
                     outcome,
                     data_path: Some(data_path),
                     index_path: Some(index_path),
-                    keep_path: Some(keep_path),
+                    keep_path,
+                    reverse_index_path,
                 }
             }
-            None => WriteOutcome {
-                outcome: crate::index::File::write_data_iter_to_stream(
+            None => {
+                let outcome = crate::index::File::write_data_iter_to_stream(
                     index_kind,
-                    move || new_pack_file_resolver(data_file),
+                    || data_file.resolver(use_mmap),
                     pack_entries_iter,
                     thread_limit,
                     indexing_progress,
@@ -315,34 +517,252 @@ impl crate::Bundle {
                     should_interrupt,
                     object_hash,
                     pack_version,
-                )?,
-                data_path: None,
-                index_path: None,
-                keep_path: None,
-            },
+                )?;
+                if verify_crc_on_write {
+                    entry_crcs.verify(data_file.resolver(use_mmap)?)?;
+                }
+                WriteOutcome {
+                    outcome,
+                    data_path: match data_file {
+                        DataFile::Temporary(_) => None,
+                        DataFile::Existing(data_path) => Some(data_path),
+                    },
+                    index_path: None,
+                    keep_path: None,
+                    reverse_index_path: None,
+                }
+            }
         })
     }
 }
 
-fn new_pack_file_resolver(
-    data_file: SharedTempFile,
+/// Fail early if we can't write index files of `version`, before any of the pack is read.
+fn assure_index_version_is_supported(version: crate::index::Version) -> Result<(), Error> {
+    if version != crate::index::Version::default() {
+        return Err(crate::index::write::Error::Unsupported(version).into());
+    }
+    Ok(())
+}
+
+fn compute_hash(kind: git_object::Kind, data: &[u8], object_hash: git_hash::Kind) -> git_hash::ObjectId {
+    let mut hasher = git_features::hash::hasher(object_hash);
+    hasher.update(&git_object::encode::loose_header(kind, data.len()));
+    hasher.update(data);
+    git_hash::ObjectId::from(hasher.digest())
+}
+
+/// Pass all `entries` through, recording the pack offset and CRC32 of each of them in `out` if `record` is true.
+fn record_crc32<'a>(
+    entries: impl Iterator<Item = Result<data::input::Entry, data::input::Error>> + 'a,
+    record: bool,
+    out: &'a mut EntryCrcs,
+) -> impl Iterator<Item = Result<data::input::Entry, data::input::Error>> + 'a {
+    entries.inspect(move |entry| {
+        if let Some(entry) = entry.as_ref().ok().filter(|_| record) {
+            if let Some(crc32) = entry.crc32 {
+                out.push(entry.pack_offset..entry.pack_offset + entry.bytes_in_pack(), crc32);
+            }
+        }
+    })
+}
+
+/// The CRC32 of pack entries in pack order.
+///
+/// As entries are stored back to back, only their pack offsets and the end of the last entry are needed to know where they are.
+#[derive(Default)]
+struct EntryCrcs {
+    pack_offsets: Vec<data::Offset>,
+    crc32s: Vec<u32>,
+    end: data::Offset,
+}
+
+impl EntryCrcs {
+    /// Record the `crc32` of the entry at `range`, which must directly follow the previously recorded entry.
+    fn push(&mut self, range: data::EntryRange, crc32: u32) {
+        debug_assert!(
+            self.pack_offsets.is_empty() || self.end == range.start,
+            "entries are recorded back to back"
+        );
+        self.pack_offsets.push(range.start);
+        self.crc32s.push(crc32);
+        self.end = range.end;
+    }
+
+    /// Recompute the CRC32 of all entries from the pack data provided by `resolve` and fail on the first one that differs.
+    fn verify(&self, resolve: impl Fn(data::EntryRange, &mut Vec<u8>) -> Option<()>) -> Result<(), Error> {
+        let mut buf = Vec::new();
+        let ends = self.pack_offsets.iter().skip(1).chain(std::iter::once(&self.end));
+        for ((start, end), expected) in self.pack_offsets.iter().zip(ends).zip(&self.crc32s) {
+            buf.resize((end - start) as usize, 0);
+            let matches =
+                resolve(*start..*end, &mut buf).map_or(false, |()| git_features::hash::crc32(&buf) == *expected);
+            if !matches {
+                return Err(Error::CrcMismatch { offset: *start });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Create a new tempfile in `directory`, named `<name>.<extension>` if `name` is set, or with a random name otherwise.
+fn new_tempfile(
+    directory: impl AsRef<Path>,
+    name: Option<&str>,
+    extension: &str,
+) -> io::Result<git_tempfile::Handle<git_tempfile::handle::Writable>> {
+    match name {
+        Some(name) => git_tempfile::writable_at(
+            directory.as_ref().join(format!("{}.{}", name, extension)),
+            ContainingDirectory::Exists,
+            AutoRemove::Tempfile,
+        ),
+        None => git_tempfile::new(directory, ContainingDirectory::Exists, AutoRemove::Tempfile),
+    }
+}
+
+/// The way the resolver obtains pack entries from the written pack data file.
+#[derive(Clone)]
+enum PackSource {
+    /// The whole file is memory-mapped.
+    Mapped(Arc<memmap2::Mmap>),
+    /// Entries are read from the file at their position, which allows concurrent reads without shared cursor.
+    File(Arc<PackFile>),
+}
+
+/// A file that can be read at any position from multiple threads at once.
+#[cfg(any(unix, windows))]
+type PackFile = std::fs::File;
+/// Without positional reads, the cursor of the file is shared and needs to be protected while seeking and reading.
+#[cfg(not(any(unix, windows)))]
+type PackFile = parking_lot::Mutex<std::fs::File>;
+
+/// The pack data file to create an index for.
+enum DataFile {
+    /// The pack is written into a tempfile, which is moved into place along with a `.keep` file once its index is complete.
+    Temporary(SharedTempFile),
+    /// The pack already exists at the given path and is left untouched.
+    Existing(PathBuf),
+}
+
+impl DataFile {
+    fn resolver(
+        &self,
+        use_mmap: bool,
+    ) -> io::Result<impl Fn(data::EntryRange, &mut Vec<u8>) -> Option<()> + Send + Clone> {
+        let path = match self {
+            DataFile::Temporary(data_file) => {
+                let mut guard = data_file.lock();
+                guard.flush()?;
+                guard.get_mut().with_mut(|f| f.path().to_owned())?
+            }
+            DataFile::Existing(path) => path.clone(),
+        };
+        pack_file_resolver_at(&path, use_mmap)
+    }
+}
+
+fn pack_file_resolver_at(
+    path: &Path,
+    use_mmap: bool,
 ) -> io::Result<impl Fn(data::EntryRange, &mut Vec<u8>) -> Option<()> + Send + Clone> {
-    let mut guard = data_file.lock();
-    guard.flush()?;
-    let mapped_file = Arc::new(crate::mmap::read_only(
-        &guard.get_mut().with_mut(|f| f.path().to_owned())?,
-    )?);
+    let source = if use_mmap {
+        PackSource::Mapped(Arc::new(crate::mmap::read_only(path)?))
+    } else {
+        PackSource::File(Arc::new(PackFile::from(std::fs::File::open(path)?)))
+    };
     let pack_data_lookup = move |range: std::ops::Range<u64>, out: &mut Vec<u8>| -> Option<()> {
-        mapped_file
-            .get(range.start as usize..range.end as usize)
-            .map(|pack_entry| out.copy_from_slice(pack_entry))
+        match &source {
+            PackSource::Mapped(mapped_file) => mapped_file
+                .get(range.start as usize..range.end as usize)
+                .map(|pack_entry| out.copy_from_slice(pack_entry)),
+            PackSource::File(file) => read_exact_at(file, out, range.start).ok(),
+        }
     };
     Ok(pack_data_lookup)
 }
 
+#[cfg(unix)]
+fn read_exact_at(file: &std::fs::File, buf: &mut [u8], offset: u64) -> io::Result<()> {
+    std::os::unix::fs::FileExt::read_exact_at(file, buf, offset)
+}
+
+#[cfg(windows)]
+fn read_exact_at(file: &std::fs::File, mut buf: &mut [u8], mut offset: u64) -> io::Result<()> {
+    use std::os::windows::fs::FileExt;
+    while !buf.is_empty() {
+        match file.seek_read(buf, offset) {
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => {
+                buf = &mut buf[n..];
+                offset += n as u64;
+            }
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn read_exact_at(file: &PackFile, buf: &mut [u8], offset: u64) -> io::Result<()> {
+    use std::io::{Read, Seek, SeekFrom};
+    let mut file = file.lock();
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(buf)
+}
+
+/// Write the reverse index for the index at `index_path` next to it, and return its path.
+fn new_reverse_index(
+    directory: &Path,
+    temp_file_name: Option<&str>,
+    index_path: &Path,
+    data: &[u8],
+) -> Result<PathBuf, Error> {
+    let reverse_index_path = index_path.with_extension("rev");
+    let mut reverse_index_file = new_tempfile(directory, temp_file_name, "rev")?;
+    reverse_index_file.write_all(data)?;
+    reverse_index_file.persist(&reverse_index_path)?;
+    Ok(reverse_index_path)
+}
+
 struct WriteOutcome {
     outcome: crate::index::write::Outcome,
     data_path: Option<PathBuf>,
     index_path: Option<PathBuf>,
     keep_path: Option<PathBuf>,
+    reverse_index_path: Option<PathBuf>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_crcs_detect_changed_pack_data() {
+        let mut data = b"first entrysecond entry".to_vec();
+        let mut crcs = EntryCrcs::default();
+        crcs.push(0..11, git_features::hash::crc32(&data[..11]));
+        crcs.push(11..23, git_features::hash::crc32(&data[11..]));
+
+        let resolver = |data: &[u8]| {
+            let data = data.to_vec();
+            move |range: data::EntryRange, out: &mut Vec<u8>| {
+                data.get(range.start as usize..range.end as usize)
+                    .map(|entry| out.copy_from_slice(entry))
+            }
+        };
+        assert!(crcs.verify(resolver(&data)).is_ok(), "intact data matches");
+
+        data[15] ^= 1;
+        assert!(matches!(
+            crcs.verify(resolver(&data)),
+            Err(Error::CrcMismatch { offset: 11 })
+        ));
+
+        data.truncate(20);
+        assert!(
+            matches!(crcs.verify(resolver(&data)), Err(Error::CrcMismatch { offset: 11 })),
+            "entries that can't be read anymore are mismatches as well"
+        );
+    }
 }
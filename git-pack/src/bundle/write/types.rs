@@ -2,8 +2,10 @@ use std::{hash::Hash, io, io::SeekFrom, path::PathBuf, sync::Arc};
 
 use git_tempfile::handle::Writable;
 
-/// Configuration for [write_to_directory][crate::Bundle::write_to_directory()] or
-/// [write_to_directory_eagerly][crate::Bundle::write_to_directory_eagerly()]
+/// Configuration for [write_to_directory][crate::Bundle::write_to_directory()],
+/// [write_objects_to_directory][crate::Bundle::write_objects_to_directory()],
+/// [write_to_directory_eagerly][crate::Bundle::write_to_directory_eagerly()] or
+/// [index_from_pack][crate::Bundle::index_from_pack()]
 #[derive(Debug, Clone)]
 pub struct Options {
     /// The amount of threads to use at most when resolving the pack. If `None`, all logical cores are used.
@@ -14,6 +16,19 @@ pub struct Options {
     pub index_version: crate::index::Version,
     /// The kind of hash to use when writing the bundle.
     pub object_hash: git_hash::Kind,
+    /// If true, write a reverse index with the `.rev` extension alongside the pack index, mapping pack positions to index positions.
+    pub write_reverse_index: bool,
+    /// If `Some(name)`, the temporary files for pack data, index and reverse index are named `<name>.pack`, `<name>.idx` and `<name>.rev`
+    /// respectively instead of using random names, which fails if any of these already exist.
+    ///
+    /// This is useful to know where intermediate files are before they are moved into place. If `None`, random names are used.
+    pub temp_file_name: Option<String>,
+    /// If true, the default, the written pack data is memory-mapped to resolve its entries while creating the index.
+    /// Otherwise entries are read from the file directly, for use where memory-mapping files isn't possible or desirable.
+    pub use_mmap: bool,
+    /// If true, once all entries are written the CRC32 of each entry is recomputed from the persisted pack data and compared
+    /// to the one recorded in the index, to detect corruption that happened while writing. Defaults to false.
+    pub verify_crc_on_write: bool,
 }
 
 impl Default for Options {
@@ -24,6 +39,10 @@ impl Default for Options {
             iteration_mode: crate::data::input::Mode::Verify,
             index_version: Default::default(),
             object_hash: Default::default(),
+            write_reverse_index: false,
+            temp_file_name: None,
+            use_mmap: true,
+            verify_crc_on_write: false,
         }
     }
 }
@@ -49,6 +68,8 @@ pub struct Outcome {
     /// The file is created right before moving the pack data and index data into place (i.e. `data_path` and `index_path`)
     /// and is expected to be removed by the caller when ready.
     pub keep_path: Option<PathBuf>,
+    /// The path to the reverse index file with the `.rev` extension, if [`Options::write_reverse_index`] was set.
+    pub reverse_index_path: Option<PathBuf>,
 }
 
 impl Outcome {
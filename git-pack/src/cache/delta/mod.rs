@@ -153,10 +153,8 @@ impl<T> Tree<T> {
 #[cfg(test)]
 mod tests {
     mod tree {
-        mod from_offsets_in_pack {
-            use std::sync::atomic::AtomicBool;
-
-            use git_odb::pack;
+        mod from_sorted_offsets {
+            use crate::cache::delta::{Error, Tree};
 
             const SMALL_PACK_INDEX: &str = "objects/pack/pack-a2bf8e71d8c18879e499335762dd95119d93d9f1.idx";
             const SMALL_PACK: &str = "objects/pack/pack-a2bf8e71d8c18879e499335762dd95119d93d9f1.pack";
@@ -177,18 +175,65 @@ mod tests {
             }
 
             fn tree(index_path: &str, pack_path: &str) -> Result<(), Box<dyn std::error::Error>> {
-                let idx = pack::index::File::at(fixture_path(index_path), git_hash::Kind::Sha1)?;
-                crate::cache::delta::Tree::from_offsets_in_pack(
-                    fixture_path(pack_path),
-                    idx.sorted_offsets().into_iter(),
-                    |ofs| *ofs,
-                    |id| idx.lookup(id).map(|index| idx.pack_offset_at_index(index)),
-                    git_features::progress::Discard,
-                    &AtomicBool::new(false),
-                    git_hash::Kind::Sha1,
+                let idx = crate::index::File::at(fixture_path(index_path), git_hash::Kind::Sha1)?;
+                let pack = crate::data::File::at(fixture_path(pack_path), git_hash::Kind::Sha1)?;
+                let tree = Tree::from_sorted_offsets(idx.sorted_offsets().into_iter().map(|ofs| {
+                    let base_ofs = match pack.entry(ofs).header {
+                        crate::data::entry::Header::OfsDelta { base_distance } => Some(ofs - base_distance),
+                        crate::data::entry::Header::RefDelta { base_id } => {
+                            Some(idx.pack_offset_at_index(idx.lookup(base_id).expect("base in pack")))
+                        }
+                        _ => None,
+                    };
+                    (ofs, base_ofs, ())
+                }))?;
+                assert_eq!(tree.num_items(), idx.num_objects() as usize);
+                Ok(())
+            }
+
+            #[test]
+            fn bases_and_deltas_are_connected() -> Result<(), Box<dyn std::error::Error>> {
+                let tree = Tree::from_sorted_offsets(
+                    vec![
+                        (12, None, 'a'),
+                        (20, Some(12), 'b'),
+                        (25, Some(20), 'c'),
+                        (30, None, 'd'),
+                    ]
+                    .into_iter(),
                 )?;
+                assert_eq!(
+                    tree.root_items.iter().map(|i| (i.offset, i.data)).collect::<Vec<_>>(),
+                    vec![(12, 'a'), (30, 'd')]
+                );
+                assert_eq!(
+                    tree.child_items.iter().map(|i| (i.offset, i.data)).collect::<Vec<_>>(),
+                    vec![(20, 'b'), (25, 'c')]
+                );
+                assert_eq!(
+                    tree.root_items[0].children,
+                    vec![0],
+                    "the first base has the first delta as child"
+                );
+                assert_eq!(tree.child_items[0].children, vec![1], "deltas can be bases themselves");
+                assert!(tree.root_items[1].children.is_empty());
+                assert_eq!(
+                    tree.root_items[0].next_offset, 20,
+                    "the end of each entry is the start of the next one"
+                );
                 Ok(())
             }
+
+            #[test]
+            fn unsorted_input_is_rejected() {
+                assert!(matches!(
+                    Tree::from_sorted_offsets(vec![(20, None, ()), (12, None, ())].into_iter()),
+                    Err(Error::InvariantIncreasingPackOffset {
+                        last_pack_offset: 20,
+                        pack_offset: 12
+                    })
+                ));
+            }
         }
     }
 
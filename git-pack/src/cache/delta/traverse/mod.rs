@@ -1,4 +1,4 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 use git_features::{
     parallel::in_parallel_with_slice,
@@ -67,6 +67,9 @@ pub struct Options<'a, P1, P2> {
     /// specifies what kind of hashes we expect to be stored in oid-delta entries, which is viable to decoding them
     /// with the correct size.
     pub object_hash: git_hash::Kind,
+    /// If `Some`, the amount of bytes that all threads may use to keep resolved base objects in memory until their children
+    /// were processed. Base objects that would exceed this limit are resolved again from the pack instead when needed.
+    pub memory_limit: Option<u64>,
 }
 
 /// The outcome of [`Tree::traverse()`]
@@ -110,6 +113,7 @@ where
             mut size_progress,
             should_interrupt,
             object_hash,
+            memory_limit,
         }: Options<'_, P1, P2>,
     ) -> Result<Outcome<T>, Error>
     where
@@ -131,6 +135,10 @@ where
         size_progress.init(None, progress::bytes());
         let size_counter = size_progress.counter();
         let child_items = self.child_items.as_mut_slice();
+        let memory = resolve::Memory {
+            limit: memory_limit,
+            used: &AtomicUsize::default(),
+        };
 
         let start = std::time::Instant::now();
         in_parallel_with_slice(
@@ -158,6 +166,7 @@ where
                         node,
                         state,
                         object_hash.len_in_bytes(),
+                        &memory,
                     )
                 }
             },
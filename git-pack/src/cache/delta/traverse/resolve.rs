@@ -1,4 +1,8 @@
-use std::{cell::RefCell, collections::BTreeMap, sync::atomic::Ordering};
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
 use git_features::{
     progress::{unit, Progress},
@@ -16,6 +20,39 @@ use crate::{
     data::EntryRange,
 };
 
+/// Keeps track of the memory used by resolved base objects which are kept until all of their children were processed.
+#[derive(Clone, Copy)]
+pub(crate) struct Memory<'a> {
+    /// The amount of bytes all threads may use together, or `None` if memory usage isn't limited.
+    pub limit: Option<u64>,
+    /// The amount of bytes currently used by all threads.
+    pub used: &'a AtomicUsize,
+}
+
+impl Memory<'_> {
+    /// Return true if `num_bytes` could be accounted for without exceeding our limit.
+    fn try_reserve(&self, num_bytes: usize) -> bool {
+        match self.limit {
+            Some(limit) => {
+                let used = self.used.fetch_add(num_bytes, Ordering::SeqCst) + num_bytes;
+                if used as u64 > limit {
+                    self.used.fetch_sub(num_bytes, Ordering::SeqCst);
+                    false
+                } else {
+                    true
+                }
+            }
+            None => true,
+        }
+    }
+
+    fn release(&self, num_bytes: usize) {
+        if self.limit.is_some() {
+            self.used.fetch_sub(num_bytes, Ordering::SeqCst);
+        }
+    }
+}
+
 pub(crate) fn deltas<T, F, P, MBFN, S, E>(
     object_counter: Option<git_features::progress::StepShared>,
     size_counter: Option<git_features::progress::StepShared>,
@@ -29,6 +66,7 @@ pub(crate) fn deltas<T, F, P, MBFN, S, E>(
         ItemSliceSend<Item<T>>,
     ),
     hash_len: usize,
+    memory: &Memory<'_>,
 ) -> Result<(), Error>
 where
     T: Send,
@@ -37,7 +75,8 @@ where
     MBFN: Fn(&mut T, &mut P, Context<'_, S>) -> Result<(), E>,
     E: std::error::Error + Send + Sync + 'static,
 {
-    let mut decompressed_bytes_by_pack_offset = BTreeMap::new();
+    let mut decompressed_bytes_by_pack_offset =
+        BTreeMap::<u64, (Vec<EntryRange>, Option<(crate::data::Entry, u64, Vec<u8>)>)>::new();
     let bytes_buf = RefCell::new(bytes_buf);
     let decompress_from_resolver = |slice: EntryRange| -> Result<(crate::data::Entry, u64, Vec<u8>), Error> {
         let mut bytes_buf = bytes_buf.borrow_mut();
@@ -50,6 +89,21 @@ where
         let decompressed_len = entry.decompressed_size as usize;
         Ok((entry, slice.end, decompress_all_at_once(compressed, decompressed_len)?))
     };
+    // Resolve the object at the end of `chain` by applying all deltas in the chain to the base object at its beginning,
+    // which is how objects are obtained whose bytes didn't fit into memory.
+    let resolve_chain = |chain: &[EntryRange]| -> Result<(crate::data::Entry, u64, Vec<u8>), Error> {
+        let (mut entry, mut entry_end, mut bytes) = decompress_from_resolver(chain[0].clone())?;
+        let mut resolved = Vec::new();
+        for slice in &chain[1..] {
+            let (mut delta_entry, delta_entry_end, delta_bytes) = decompress_from_resolver(slice.clone())?;
+            apply_delta(&bytes, &delta_bytes, &mut resolved);
+            std::mem::swap(&mut bytes, &mut resolved);
+            delta_entry.header = entry.header;
+            entry = delta_entry;
+            entry_end = delta_entry_end;
+        }
+        Ok((entry, entry_end, bytes))
+    };
 
     // Traverse the tree breadth first and loose the data produced for the base as it won't be needed anymore.
     progress.init(
@@ -71,12 +125,25 @@ where
         },
     )];
     while let Some((level, mut base)) = nodes.pop() {
-        let (base_entry, entry_end, base_bytes) = if level == root_level {
-            decompress_from_resolver(base.entry_slice())?
+        // The chain of entries from the root to the base, only needed to resolve objects again if memory is limited.
+        let (base_chain, (base_entry, entry_end, base_bytes)) = if level == root_level {
+            let slice = base.entry_slice();
+            (
+                memory.limit.map(|_| vec![slice.clone()]).unwrap_or_default(),
+                decompress_from_resolver(slice)?,
+            )
         } else {
-            decompressed_bytes_by_pack_offset
+            let (chain, resolved) = decompressed_bytes_by_pack_offset
                 .remove(&base.offset())
-                .expect("we store the resolved delta buffer when done")
+                .expect("we store the resolved delta buffer or its chain when done");
+            let resolved = match resolved {
+                Some(resolved) => {
+                    memory.release(resolved.2.len());
+                    resolved
+                }
+                None => resolve_chain(&chain)?,
+            };
+            (chain, resolved)
         };
 
         // anything done here must be repeated further down for leaf-nodes.
@@ -102,29 +169,26 @@ where
         }
 
         for mut child in base.into_child_iter() {
-            let (mut child_entry, entry_end, delta_bytes) = decompress_from_resolver(child.entry_slice())?;
-            let (base_size, consumed) = crate::data::delta::decode_header_size(&delta_bytes);
-            let mut header_ofs = consumed;
-            assert_eq!(
-                base_bytes.len(),
-                base_size as usize,
-                "recorded base size in delta does not match"
-            );
-            let (result_size, consumed) = crate::data::delta::decode_header_size(&delta_bytes[consumed..]);
-            header_ofs += consumed;
-
+            let child_slice = child.entry_slice();
+            let (mut child_entry, entry_end, delta_bytes) = decompress_from_resolver(child_slice.clone())?;
             let mut fully_resolved_delta_bytes = bytes_buf.borrow_mut();
-            fully_resolved_delta_bytes.resize(result_size as usize, 0);
-            crate::data::delta::apply(&base_bytes, &mut fully_resolved_delta_bytes, &delta_bytes[header_ofs..]);
+            apply_delta(&base_bytes, &delta_bytes, &mut fully_resolved_delta_bytes);
 
             // FIXME: this actually invalidates the "pack_offset()" computation, which is not obvious to consumers
             //        at all
             child_entry.header = base_entry.header; // assign the actual object type, instead of 'delta'
             if child.has_children() {
-                decompressed_bytes_by_pack_offset.insert(
-                    child.offset(),
-                    (child_entry, entry_end, fully_resolved_delta_bytes.to_owned()),
-                );
+                let chain = if memory.limit.is_some() {
+                    let mut chain = base_chain.clone();
+                    chain.push(child_slice);
+                    chain
+                } else {
+                    Vec::new()
+                };
+                let resolved = memory
+                    .try_reserve(fully_resolved_delta_bytes.len())
+                    .then(|| (child_entry, entry_end, fully_resolved_delta_bytes.to_owned()));
+                decompressed_bytes_by_pack_offset.insert(child.offset(), (chain, resolved));
                 nodes.push((level + 1, child));
             } else {
                 modify_base(
@@ -150,6 +214,22 @@ where
     Ok(())
 }
 
+/// Apply `delta` to `base` and write the resolved object into `out`.
+fn apply_delta(base: &[u8], delta: &[u8], out: &mut Vec<u8>) {
+    let (base_size, consumed) = crate::data::delta::decode_header_size(delta);
+    let mut header_ofs = consumed;
+    assert_eq!(
+        base.len(),
+        base_size as usize,
+        "recorded base size in delta does not match"
+    );
+    let (result_size, consumed) = crate::data::delta::decode_header_size(&delta[consumed..]);
+    header_ofs += consumed;
+
+    out.resize(result_size as usize, 0);
+    crate::data::delta::apply(base, out, &delta[header_ofs..]);
+}
+
 fn decompress_all_at_once(b: &[u8], decompressed_len: usize) -> Result<Vec<u8>, Error> {
     let mut out = Vec::new();
     out.resize(decompressed_len, 0);
@@ -20,6 +20,18 @@ pub fn decode(data: &[u8; 12]) -> Result<(data::Version, u32), decode::Error> {
     Ok((kind, num_objects))
 }
 
+impl data::Version {
+    /// Read only the 12 bytes of the pack header from `read` and return the pack version as well as the number of objects
+    /// contained in the pack.
+    ///
+    /// This is useful to validate a pack before committing to iterating all of its entries.
+    pub fn from_reader(read: &mut impl std::io::Read) -> Result<(data::Version, u32), decode::Error> {
+        let mut header = [0u8; 12];
+        read.read_exact(&mut header).map_err(decode::Error::Read)?;
+        decode(&header)
+    }
+}
+
 /// Write a pack data header at `version` with `num_objects` and return a buffer.
 pub fn encode(version: data::Version, num_objects: u32) -> [u8; 12] {
     use crate::data::Version::*;
@@ -38,7 +50,7 @@ pub fn encode(version: data::Version, num_objects: u32) -> [u8; 12] {
 
 ///
 pub mod decode {
-    /// Returned by [`decode()`][super::decode()].
+    /// Returned by [`decode()`][super::decode()] and [`Version::from_reader()`][crate::data::Version::from_reader()].
     #[derive(thiserror::Error, Debug)]
     #[allow(missing_docs)]
     pub enum Error {
@@ -47,6 +59,8 @@ pub mod decode {
             source: std::io::Error,
             path: std::path::PathBuf,
         },
+        #[error("Could not read the pack header")]
+        Read(#[source] std::io::Error),
         #[error("{0}")]
         Corrupt(String),
         #[error("Unsupported pack version: {0}")]
@@ -1,14 +1,17 @@
 use std::convert::TryInto;
 
+use git_features::progress::{self, Progress};
 use git_hash::ObjectId;
 
 use crate::data::{entry::Header, input};
 
 /// An iterator to resolve thin packs on the fly.
-pub struct LookupRefDeltaObjectsIter<I, LFn> {
+pub struct LookupRefDeltaObjectsIter<I, LFn, P = progress::Discard> {
     /// The inner iterator whose entries we will resolve.
     pub inner: I,
     lookup: LFn,
+    /// Informed about each base object that was looked up.
+    progress: P,
     /// The cached delta to provide next time we are called, it's the delta to go with the base we just resolved in its place.
     next_delta: Option<input::Entry>,
     /// Fuse to stop iteration after first missing object.
@@ -21,7 +24,7 @@ pub struct LookupRefDeltaObjectsIter<I, LFn> {
     buf: Vec<u8>,
 }
 
-impl<I, LFn> LookupRefDeltaObjectsIter<I, LFn>
+impl<I, LFn> LookupRefDeltaObjectsIter<I, LFn, progress::Discard>
 where
     I: Iterator<Item = Result<input::Entry, input::Error>>,
     LFn: for<'a> FnMut(ObjectId, &'a mut Vec<u8>) -> Option<git_object::Data<'a>>,
@@ -29,9 +32,23 @@ where
     /// Create a new instance wrapping `iter` and using `lookup` as function to retrieve objects that will serve as bases
     /// for ref deltas seen while traversing `iter`.
     pub fn new(iter: I, lookup: LFn) -> Self {
+        Self::new_with_progress(iter, lookup, progress::Discard)
+    }
+}
+
+impl<I, LFn, P> LookupRefDeltaObjectsIter<I, LFn, P>
+where
+    I: Iterator<Item = Result<input::Entry, input::Error>>,
+    LFn: for<'a> FnMut(ObjectId, &'a mut Vec<u8>) -> Option<git_object::Data<'a>>,
+    P: Progress,
+{
+    /// Like [`new()`][LookupRefDeltaObjectsIter::new()], but inform `progress` about each base object that was looked up.
+    pub fn new_with_progress(iter: I, lookup: LFn, mut progress: P) -> Self {
+        progress.init(None, progress::count("base objects"));
         LookupRefDeltaObjectsIter {
             inner: iter,
             lookup,
+            progress,
             error: false,
             inserted_entry_length_at_offset: Vec::new(),
             inserted_entries_length_in_bytes: 0,
@@ -81,10 +98,11 @@ where
     }
 }
 
-impl<I, LFn> Iterator for LookupRefDeltaObjectsIter<I, LFn>
+impl<I, LFn, P> Iterator for LookupRefDeltaObjectsIter<I, LFn, P>
 where
     I: Iterator<Item = Result<input::Entry, input::Error>>,
     LFn: for<'a> FnMut(ObjectId, &'a mut Vec<u8>) -> Option<git_object::Data<'a>>,
+    P: Progress,
 {
     type Item = Result<input::Entry, input::Error>;
 
@@ -114,6 +132,7 @@ where
                                         entry.bytes_in_pack() as i64,
                                         base_id,
                                     );
+                                    self.progress.inc();
                                     entry
                                 }
                                 None => {
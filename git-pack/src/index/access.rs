@@ -154,22 +154,58 @@ impl index::File {
         }
     }
 
-    /// Return a vector of ascending offsets into our respective pack data file.
+    /// An iterator over all [`Entries`][Entry] of this index file whose object id starts with `first_byte`, in sorted order.
     ///
-    /// Useful to control an iteration over all pack entries in a cache-friendly way.
-    pub fn sorted_offsets(&self) -> Vec<data::Offset> {
-        let mut ofs: Vec<_> = match self.version {
-            index::Version::V1 => self.iter().map(|e| e.pack_offset).collect(),
+    /// The fan-out table is used to jump right to the matching entries without visiting any of the preceding ones.
+    pub fn iter_prefix(&self, first_byte: u8) -> impl Iterator<Item = Entry> + '_ {
+        let first_byte = first_byte as usize;
+        let start = if first_byte != 0 { self.fan[first_byte - 1] } else { 0 };
+        let end = self.fan[first_byte];
+        (start..end).map(move |idx| Entry {
+            oid: self.oid_at_index(idx).to_owned(),
+            pack_offset: self.pack_offset_at_index(idx),
+            crc32: self.crc32_at_index(idx),
+        })
+    }
+
+    /// Return the pack offsets of the entries in the given range of entry indices, `[start_idx, end_idx)`, in index order.
+    ///
+    /// This is equivalent to calling [`pack_offset_at_index()`][index::File::pack_offset_at_index()] for each index in the range,
+    /// but decodes the offset table in one go.
+    ///
+    /// # Panics
+    ///
+    /// If `end_idx` is larger than the amount of objects, or if `start_idx` is larger than `end_idx`.
+    pub fn offsets_in_range(&self, start_idx: EntryIndex, end_idx: EntryIndex) -> Vec<data::Offset> {
+        assert!(
+            start_idx <= end_idx && end_idx <= self.num_objects,
+            "range {}..{} is out of bounds for an index with {} objects",
+            start_idx,
+            end_idx,
+            self.num_objects
+        );
+        let (start_idx, end_idx) = (start_idx as usize, end_idx as usize);
+        match self.version {
+            index::Version::V1 => self.data[V1_HEADER_SIZE + start_idx * (N32_SIZE + self.hash_len)..]
+                .chunks(N32_SIZE + self.hash_len)
+                .take(end_idx - start_idx)
+                .map(|c| crate::read_u32(&c[..N32_SIZE]) as u64)
+                .collect(),
             index::Version::V2 => {
-                let offset32_start = &self.data[self.offset_pack_offset_v2()..];
-                let pack_offset_64_start = self.offset_pack_offset64_v2();
-                offset32_start
+                let pack64_offset = self.offset_pack_offset64_v2();
+                self.data[self.offset_pack_offset_v2() + start_idx * N32_SIZE..][..(end_idx - start_idx) * N32_SIZE]
                     .chunks(N32_SIZE)
-                    .take(self.num_objects as usize)
-                    .map(|offset| self.pack_offset_from_offset_v2(offset, pack_offset_64_start))
+                    .map(|offset| self.pack_offset_from_offset_v2(offset, pack64_offset))
                     .collect()
             }
-        };
+        }
+    }
+
+    /// Return a vector of ascending offsets into our respective pack data file.
+    ///
+    /// Useful to control an iteration over all pack entries in a cache-friendly way.
+    pub fn sorted_offsets(&self) -> Vec<data::Offset> {
+        let mut ofs = self.offsets_in_range(0, self.num_objects);
         ofs.sort_unstable();
         ofs
     }
@@ -140,6 +140,7 @@ impl File {
 }
 
 const V2_SIGNATURE: &[u8] = b"\xfftOc";
+const REVERSE_INDEX_SIGNATURE: &[u8] = b"RIDX";
 ///
 pub mod init;
 
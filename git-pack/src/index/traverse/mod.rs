@@ -116,7 +116,11 @@ impl index::File {
                 new_processor,
                 progress,
                 should_interrupt,
-                crate::index::traverse::with_index::Options { check, thread_limit },
+                crate::index::traverse::with_index::Options {
+                    check,
+                    thread_limit,
+                    memory_limit: None,
+                },
             ),
         }
     }
@@ -132,7 +136,7 @@ impl index::File {
     where
         E: std::error::Error + Send + Sync + 'static,
     {
-        Ok(if check.file_checksum() {
+        Ok(if check.pack_checksum() || check.index_checksum() {
             if self.pack_checksum() != pack.checksum() {
                 return Err(Error::PackMismatch {
                     actual: pack.checksum(),
@@ -140,8 +144,20 @@ impl index::File {
                 });
             }
             let (pack_res, id) = parallel::join(
-                move || pack.verify_checksum(pack_progress, should_interrupt),
-                move || self.verify_checksum(index_progress, should_interrupt),
+                move || {
+                    if check.pack_checksum() {
+                        pack.verify_checksum(pack_progress, should_interrupt).map(|_| ())
+                    } else {
+                        Ok(())
+                    }
+                },
+                move || {
+                    if check.index_checksum() {
+                        self.verify_checksum(index_progress, should_interrupt)
+                    } else {
+                        Ok(self.index_checksum())
+                    }
+                },
             );
             pack_res?;
             id?
@@ -160,7 +176,7 @@ impl index::File {
         progress: &mut P,
         index_entry: &crate::index::Entry,
         processor: &mut impl FnMut(git_object::Kind, &[u8], &index::Entry, &mut P) -> Result<(), E>,
-    ) -> Result<crate::data::decode_entry::Outcome, Error<E>>
+    ) -> Result<(crate::data::decode_entry::Outcome, crate::data::entry::Header), Error<E>>
     where
         C: crate::cache::DecodeEntry,
         P: Progress,
@@ -168,6 +184,7 @@ impl index::File {
     {
         let pack_entry = pack.entry(index_entry.pack_offset);
         let pack_entry_data_offset = pack_entry.data_offset;
+        let header = pack_entry.header;
         let entry_stats = pack
             .decode_entry(
                 pack_entry,
@@ -196,7 +213,7 @@ impl index::File {
             || pack.entry_crc32(index_entry.pack_offset, entry_len),
             processor,
         )?;
-        Ok(entry_stats)
+        Ok((entry_stats, header))
     }
 }
 
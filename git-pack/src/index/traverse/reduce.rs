@@ -1,4 +1,5 @@
 use std::{
+    convert::TryInto,
     sync::atomic::{AtomicBool, Ordering},
     time::Instant,
 };
@@ -68,7 +69,8 @@ where
     P: Progress,
     E: std::error::Error + Send + Sync + 'static,
 {
-    type Input = Result<Vec<data::decode_entry::Outcome>, traverse::Error<E>>;
+    type Input =
+        Result<Vec<(data::decode_entry::Outcome, data::entry::Header, git_hash::ObjectId)>, traverse::Error<E>>;
     type FeedProduce = ();
     type Output = traverse::Statistics;
     type Error = traverse::Error<E>;
@@ -85,7 +87,12 @@ where
 
         let chunk_total = chunk_stats.into_iter().fold(
             data::decode_entry::Outcome::default_from_kind(git_object::Kind::Tree),
-            |mut total, stats| {
+            |mut total, (stats, header, id)| {
+                match header {
+                    data::entry::Header::OfsDelta { .. } => self.stats.num_ofs_deltas += 1,
+                    data::entry::Header::RefDelta { .. } => self.stats.num_ref_deltas += 1,
+                    _ => {}
+                }
                 *self.stats.objects_per_chain_length.entry(stats.num_deltas).or_insert(0) += 1;
                 self.stats.total_decompressed_entries_size += stats.decompressed_size;
                 self.stats.total_compressed_entries_size += stats.compressed_size as u64;
@@ -97,6 +104,17 @@ where
                     Blob => self.stats.num_blobs += 1,
                     Tag => self.stats.num_tags += 1,
                 }
+                traverse::types::add_to_size_bucket(
+                    &mut self.stats.objects_per_size_bucket,
+                    stats.kind,
+                    stats.object_size,
+                );
+                traverse::types::track_outliers(
+                    &mut self.stats,
+                    id,
+                    stats.object_size,
+                    stats.num_deltas.try_into().unwrap_or(u16::MAX),
+                );
                 add_decode_result(&mut total, stats);
                 total
             },
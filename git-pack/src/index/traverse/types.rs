@@ -27,6 +27,63 @@ pub struct Statistics {
     pub num_tags: u32,
     /// The amount of objects encountered that where blobs
     pub num_blobs: u32,
+    /// A histogram of undeltified object sizes per object kind, mapping the smallest power of two
+    /// greater than or equal to the object size to the amount of objects in that bucket.
+    ///
+    /// Thus the amount of buckets per kind is bounded by the amount of bits in the object size.
+    pub objects_per_size_bucket: BTreeMap<git_object::Kind, BTreeMap<u64, u32>>,
+    /// The amount of entries stored as delta against a base at a given offset in the same pack.
+    pub num_ofs_deltas: u32,
+    /// The amount of entries stored as delta against a base identified by its object id.
+    ///
+    /// These are suboptimal in packs that contain all of their bases, as offset deltas are smaller and faster to resolve.
+    pub num_ref_deltas: u32,
+    /// The id and undeltified size in bytes of the largest object, or `None` if there were no objects.
+    ///
+    /// If multiple objects have the same size, the one with the smallest id is chosen to be independent of traversal order.
+    pub largest_object: Option<(git_hash::ObjectId, u64)>,
+    /// The id and delta chain length of the object at the end of the longest delta chain, or `None` if there were no objects.
+    ///
+    /// If multiple objects have the same chain length, the one with the smallest id is chosen to be independent of traversal order.
+    pub deepest_chain: Option<(git_hash::ObjectId, u16)>,
+}
+
+/// Remember the object with `id` in `largest_object` or `deepest_chain` if its undeltified `size` or its `chain_length` exceed
+/// the ones seen so far.
+pub(crate) fn track_outliers(
+    Statistics {
+        largest_object,
+        deepest_chain,
+        ..
+    }: &mut Statistics,
+    id: git_hash::ObjectId,
+    size: u64,
+    chain_length: u16,
+) {
+    fn exceeds<T: Ord + Copy>(current: &Option<(git_hash::ObjectId, T)>, id: git_hash::ObjectId, value: T) -> bool {
+        current.map_or(true, |(current_id, current_value)| {
+            (value, std::cmp::Reverse(id)) > (current_value, std::cmp::Reverse(current_id))
+        })
+    }
+    if exceeds(largest_object, id, size) {
+        *largest_object = Some((id, size));
+    }
+    if exceeds(deepest_chain, id, chain_length) {
+        *deepest_chain = Some((id, chain_length));
+    }
+}
+
+/// Count the object of `kind` with the given undeltified `size` into the respective bucket of `objects_per_size_bucket`.
+pub(crate) fn add_to_size_bucket(
+    objects_per_size_bucket: &mut BTreeMap<git_object::Kind, BTreeMap<u64, u32>>,
+    kind: git_object::Kind,
+    size: u64,
+) {
+    *objects_per_size_bucket
+        .entry(kind)
+        .or_default()
+        .entry(size.checked_next_power_of_two().unwrap_or(u64::MAX))
+        .or_insert(0) += 1;
 }
 
 impl Default for Statistics {
@@ -42,6 +99,11 @@ impl Default for Statistics {
             num_commits: 0,
             num_trees: 0,
             num_tags: 0,
+            objects_per_size_bucket: Default::default(),
+            num_ofs_deltas: 0,
+            num_ref_deltas: 0,
+            largest_object: None,
+            deepest_chain: None,
         }
     }
 }
@@ -53,6 +115,16 @@ pub enum SafetyCheck {
     /// Don't verify the validity of the checksums stored in the index and pack file
     SkipFileChecksumVerification,
 
+    /// Don't verify the validity of the checksum stored in the index file, but perform all other checks.
+    ///
+    /// Useful if the index file was verified separately.
+    SkipIndexChecksumVerification,
+
+    /// Don't verify the validity of the checksum stored in the pack file, but perform all other checks.
+    ///
+    /// Useful if the pack file was verified separately.
+    SkipPackChecksumVerification,
+
     /// All of the above, and also don't perform any object checksum verification
     SkipFileAndObjectChecksumVerification,
 
@@ -67,16 +139,27 @@ pub enum SafetyCheck {
 }
 
 impl SafetyCheck {
-    pub(crate) fn file_checksum(&self) -> bool {
-        matches!(self, SafetyCheck::All)
+    pub(crate) fn pack_checksum(&self) -> bool {
+        matches!(self, SafetyCheck::All | SafetyCheck::SkipIndexChecksumVerification)
+    }
+    pub(crate) fn index_checksum(&self) -> bool {
+        matches!(self, SafetyCheck::All | SafetyCheck::SkipPackChecksumVerification)
     }
     pub(crate) fn object_checksum(&self) -> bool {
-        matches!(self, SafetyCheck::All | SafetyCheck::SkipFileChecksumVerification)
+        matches!(
+            self,
+            SafetyCheck::All
+                | SafetyCheck::SkipFileChecksumVerification
+                | SafetyCheck::SkipIndexChecksumVerification
+                | SafetyCheck::SkipPackChecksumVerification
+        )
     }
     pub(crate) fn fatal_decode_error(&self) -> bool {
         match self {
             SafetyCheck::All
             | SafetyCheck::SkipFileChecksumVerification
+            | SafetyCheck::SkipIndexChecksumVerification
+            | SafetyCheck::SkipPackChecksumVerification
             | SafetyCheck::SkipFileAndObjectChecksumVerification => true,
             SafetyCheck::SkipFileAndObjectChecksumVerificationAndNoAbortOnDecodeError => false,
         }
@@ -16,6 +16,9 @@ pub struct Options {
     pub thread_limit: Option<usize>,
     /// The kinds of safety checks to perform.
     pub check: crate::index::traverse::SafetyCheck,
+    /// If `Some`, the amount of bytes of resolved base objects to keep in memory while traversing the delta tree.
+    /// Base objects that don't fit are resolved again from the pack when needed, trading memory for speed.
+    pub memory_limit: Option<u64>,
 }
 
 /// Traversal with index
@@ -30,7 +33,11 @@ impl index::File {
         new_processor: impl Fn() -> Processor + Send + Clone,
         mut progress: P,
         should_interrupt: &AtomicBool,
-        Options { check, thread_limit }: Options,
+        Options {
+            check,
+            thread_limit,
+            memory_limit,
+        }: Options,
     ) -> Result<Outcome<P>, Error<E>>
     where
         P: Progress,
@@ -63,15 +70,58 @@ impl index::File {
             || -> Result<_, Error<_>> {
                 let sorted_entries =
                     index_entries_sorted_by_offset_ascending(self, progress.add_child("collecting sorted index"));
-                let tree = crate::cache::delta::Tree::from_offsets_in_pack(
-                    pack.path(),
-                    sorted_entries.into_iter().map(Entry::from),
-                    |e| e.index_entry.pack_offset,
-                    |id| self.lookup(id).map(|idx| self.pack_offset_at_index(idx)),
-                    progress.add_child("indexing"),
-                    should_interrupt,
-                    self.object_hash,
-                )?;
+                let mut indexing_progress = progress.add_child("indexing");
+                indexing_progress.init(Some(sorted_entries.len()), git_features::progress::count("objects"));
+                let start = std::time::Instant::now();
+                let pack_end = pack.pack_end() as u64;
+                let mut error = None;
+                let tree = crate::cache::delta::Tree::from_sorted_offsets(
+                    sorted_entries.into_iter().enumerate().map_while(|(idx, index_entry)| {
+                        use crate::cache::delta::from_offsets::Error as TreeError;
+                        let pack_offset = index_entry.pack_offset;
+                        if idx % 10_000 == 0 && should_interrupt.load(Ordering::SeqCst) {
+                            error = Some(TreeError::Interrupted);
+                            return None;
+                        }
+                        if pack_offset >= pack_end {
+                            error = Some(TreeError::PackOffsetOutOfBounds { pack_offset, pack_end });
+                            return None;
+                        }
+                        let (base_pack_offset, delta) = match pack.entry(pack_offset).header {
+                            crate::data::entry::Header::OfsDelta { base_distance } => {
+                                match pack_offset.checked_sub(base_distance) {
+                                    Some(base_pack_offset) => (Some(base_pack_offset), Delta::Ofs),
+                                    None => {
+                                        error = Some(TreeError::BaseOutOfBounds {
+                                            pack_offset,
+                                            base_distance,
+                                        });
+                                        return None;
+                                    }
+                                }
+                            }
+                            crate::data::entry::Header::RefDelta { base_id } => {
+                                match self.lookup(base_id).map(|idx| self.pack_offset_at_index(idx)) {
+                                    Some(base_pack_offset) => (Some(base_pack_offset), Delta::Ref),
+                                    None => {
+                                        error = Some(TreeError::UnresolvedRefDelta { id: base_id });
+                                        return None;
+                                    }
+                                }
+                            }
+                            _ => (None, Delta::None),
+                        };
+                        let mut entry = Entry::from(index_entry);
+                        entry.delta = delta;
+                        indexing_progress.inc();
+                        Some((pack_offset, base_pack_offset, entry))
+                    }),
+                )
+                .map_err(crate::cache::delta::from_offsets::Error::from)?;
+                if let Some(err) = error {
+                    return Err(err.into());
+                }
+                indexing_progress.show_throughput(start);
                 let mut outcome = digest_statistics(tree.traverse(
                     |slice, out| pack.entry_slice(slice).map(|entry| out.copy_from_slice(entry)),
                     pack.pack_end() as u64,
@@ -122,6 +172,7 @@ impl index::File {
                         thread_limit,
                         should_interrupt,
                         object_hash: self.object_hash,
+                        memory_limit,
                     },
                 )?);
                 outcome.pack_size = pack.data_len() as u64;
@@ -138,6 +189,8 @@ impl index::File {
 
 struct Entry {
     index_entry: crate::index::Entry,
+    /// The kind of delta as stored in the pack, as the header passed during traversal is the one of the resolved object.
+    delta: Delta,
     object_kind: git_object::Kind,
     object_size: u64,
     decompressed_size: u64,
@@ -145,10 +198,22 @@ struct Entry {
     level: u16,
 }
 
+/// The way an entry is stored in the pack.
+#[derive(Clone, Copy)]
+enum Delta {
+    /// The entry is a base object.
+    None,
+    /// The entry is an ofs-delta.
+    Ofs,
+    /// The entry is a ref-delta.
+    Ref,
+}
+
 impl From<crate::index::Entry> for Entry {
     fn from(index_entry: crate::index::Entry) -> Self {
         Entry {
             index_entry,
+            delta: Delta::None,
             level: 0,
             object_kind: git_object::Kind::Tree,
             object_size: 0,
@@ -160,13 +225,18 @@ impl From<crate::index::Entry> for Entry {
 
 fn digest_statistics(traverse::Outcome { roots, children }: traverse::Outcome<Entry>) -> index::traverse::Statistics {
     let mut res = index::traverse::Statistics::default();
-    let average = &mut res.average;
     for item in roots.iter().chain(children.iter()) {
         res.total_compressed_entries_size += item.data.compressed_size;
         res.total_decompressed_entries_size += item.data.decompressed_size;
         res.total_object_size += item.data.object_size;
         *res.objects_per_chain_length.entry(item.data.level as u32).or_insert(0) += 1;
+        match item.data.delta {
+            Delta::Ofs => res.num_ofs_deltas += 1,
+            Delta::Ref => res.num_ref_deltas += 1,
+            Delta::None => {}
+        }
 
+        let average = &mut res.average;
         average.decompressed_size += item.data.decompressed_size;
         average.compressed_size += item.data.compressed_size as usize;
         average.object_size += item.data.object_size;
@@ -178,9 +248,21 @@ fn digest_statistics(traverse::Outcome { roots, children }: traverse::Outcome<En
             Tag => res.num_tags += 1,
             Commit => res.num_commits += 1,
         };
+        super::types::add_to_size_bucket(
+            &mut res.objects_per_size_bucket,
+            item.data.object_kind,
+            item.data.object_size,
+        );
+        super::types::track_outliers(
+            &mut res,
+            item.data.index_entry.oid,
+            item.data.object_size,
+            item.data.level,
+        );
     }
 
     let num_nodes = roots.len() + children.len();
+    let average = &mut res.average;
     average.decompressed_size /= num_nodes as u64;
     average.compressed_size /= num_nodes;
     average.object_size /= num_nodes as u64;
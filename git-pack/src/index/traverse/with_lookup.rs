@@ -113,7 +113,10 @@ impl index::File {
                     state_per_thread,
                     |entries: &[index::Entry],
                      (cache, ref mut processor, buf, progress)|
-                     -> Result<Vec<data::decode_entry::Outcome>, Error<_>> {
+                     -> Result<
+                        Vec<(data::decode_entry::Outcome, data::entry::Header, git_hash::ObjectId)>,
+                        Error<_>,
+                    > {
                         progress.init(
                             Some(entries.len()),
                             Some(unit::dynamic(unit::Human::new(
@@ -134,14 +137,14 @@ impl index::File {
                                 processor,
                             );
                             progress.inc();
-                            let stat = match result {
+                            let (stat, header) = match result {
                                 Err(err @ Error::PackDecode { .. }) if !check.fatal_decode_error() => {
                                     progress.info(format!("Ignoring decode error: {}", err));
                                     continue;
                                 }
                                 res => res,
                             }?;
-                            stats.push(stat);
+                            stats.push((stat, header, index_entry.oid));
                         }
                         Ok(stats)
                     },
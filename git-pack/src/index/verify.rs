@@ -1,6 +1,6 @@
 use std::sync::atomic::AtomicBool;
 
-use git_features::progress::Progress;
+use git_features::{parallel, progress::Progress};
 use git_object::{bstr::ByteSlice, WriteTo};
 
 use crate::index;
@@ -65,6 +65,30 @@ pub mod integrity {
     }
 }
 
+///
+pub mod crc32 {
+    /// Returned by [`index::File::verify_crcs()`][crate::index::File::verify_crcs()].
+    #[derive(thiserror::Error, Debug)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Index files of version {version:?} don't store CRC32 checksums of pack entries")]
+        Unsupported { version: crate::index::Version },
+        #[error("The index belongs to the pack with checksum {expected}, but the given pack has checksum {actual}")]
+        PackMismatch {
+            expected: git_hash::ObjectId,
+            actual: git_hash::ObjectId,
+        },
+        #[error("The entry at offset {offset} starts past the end of the pack")]
+        OffsetOutOfBounds { offset: crate::data::Offset },
+        #[error("The CRC32 of the entry at offset {offset} is {actual:#x}, but the index stored {expected:#x}")]
+        Mismatch {
+            offset: crate::data::Offset,
+            expected: u32,
+            actual: u32,
+        },
+    }
+}
+
 ///
 pub mod checksum {
     /// Returned by [`index::File::verify_checksum()`][crate::index::File::verify_checksum()].
@@ -132,6 +156,66 @@ impl index::File {
         )
     }
 
+    /// Verify the CRC32 checksums stored in this index against the compressed bytes of the corresponding entries in `pack`,
+    /// using up to `thread_limit` threads, or all available cores if `None`.
+    ///
+    /// Unlike a [traversal][index::File::traverse()], no object is decompressed or resolved, which makes this a fast way to
+    /// check the pack data for corruption. Note that only version 2 index files store CRC32 checksums.
+    pub fn verify_crcs(&self, pack: &crate::data::File, thread_limit: Option<usize>) -> Result<(), crc32::Error> {
+        if self.version != index::Version::V2 {
+            return Err(crc32::Error::Unsupported { version: self.version });
+        }
+        let (expected, actual) = (self.pack_checksum(), pack.checksum());
+        if expected != actual {
+            return Err(crc32::Error::PackMismatch { expected, actual });
+        }
+
+        let mut entries: Vec<_> = self
+            .iter()
+            .map(|entry| {
+                (
+                    entry.pack_offset,
+                    entry.crc32.expect("v2 index files have a crc32 for each entry"),
+                )
+            })
+            .collect();
+        entries.sort_unstable_by_key(|(offset, _)| *offset);
+        let pack_end = pack.pack_end() as u64;
+        let entry_ends: Vec<_> = entries
+            .iter()
+            .skip(1)
+            .map(|(offset, _)| *offset)
+            .chain(std::iter::once(pack_end))
+            .collect();
+
+        let (chunk_size, thread_limit, available_cores) =
+            parallel::optimize_chunk_size_and_thread_limit(1000, Some(entries.len()), thread_limit, None);
+        let there_are_enough_entries_to_process = || entries.len() > chunk_size * available_cores;
+        parallel::in_parallel_if(
+            there_are_enough_entries_to_process,
+            entries.chunks(chunk_size).zip(entry_ends.chunks(chunk_size)),
+            thread_limit,
+            |_thread_id| (),
+            |(entries, entry_ends), _state| -> Result<(), crc32::Error> {
+                for (&(offset, expected), &end) in entries.iter().zip(entry_ends) {
+                    if end > pack_end || offset >= end {
+                        return Err(crc32::Error::OffsetOutOfBounds { offset });
+                    }
+                    let actual = pack.entry_crc32(offset, (end - offset) as usize);
+                    if actual != expected {
+                        return Err(crc32::Error::Mismatch {
+                            offset,
+                            expected,
+                            actual,
+                        });
+                    }
+                }
+                Ok(())
+            },
+            parallel::reduce::IdentityWithResult::<(), _>::default(),
+        )
+    }
+
     /// The most thorough validation of integrity of both index file and the corresponding pack data file, if provided.
     /// Returns the checksum of the index file, the traversal outcome and the given progress if the integrity check is successful.
     ///
@@ -4,17 +4,22 @@ pub(crate) const LARGE_OFFSET_THRESHOLD: u64 = 0x7fff_ffff;
 pub(crate) const HIGH_BIT: u32 = 0x8000_0000;
 
 use git_features::{
-    hash,
+    hash, parallel,
     progress::{self, Progress},
 };
 
 use crate::index::{util::Count, V2_SIGNATURE};
 
+/// Write an index file of `kind` for all `entries_sorted_by_oid`, each being a tuple of `(id, crc32, pack_offset)`.
+///
+/// If `thread_limit` allows more than one thread, the ids, crc32 and offsets tables are encoded into buffers in parallel
+/// before they are written, trading memory for speed. The output is the same either way.
 pub(crate) fn write_to(
     out: impl io::Write,
-    entries_sorted_by_oid: Vec<crate::cache::delta::Item<crate::index::write::TreeEntry>>,
+    entries_sorted_by_oid: impl ExactSizeIterator<Item = (git_hash::ObjectId, u32, crate::data::Offset)> + Clone + Send,
     pack_hash: &git_hash::ObjectId,
     kind: crate::index::Version,
+    thread_limit: Option<usize>,
     mut progress: impl Progress,
 ) -> io::Result<git_hash::ObjectId> {
     use io::Write;
@@ -35,44 +40,52 @@ pub(crate) fn write_to(
     progress.init(Some(4), progress::steps());
     let start = std::time::Instant::now();
     let _info = progress.add_child("writing fan-out table");
-    let fan_out = fanout(entries_sorted_by_oid.iter().map(|e| e.data.id.first_byte()));
+    let fan_out = fanout(entries_sorted_by_oid.clone().map(|(id, _, _)| id.first_byte()));
 
     for value in fan_out.iter() {
         out.write_all(&value.to_be_bytes())?;
     }
 
-    progress.inc();
-    let _info = progress.add_child("writing ids");
-    for entry in &entries_sorted_by_oid {
-        out.write_all(entry.data.id.as_slice())?;
-    }
-
-    progress.inc();
-    let _info = progress.add_child("writing crc32");
-    for entry in &entries_sorted_by_oid {
-        out.write_all(&entry.data.crc32.to_be_bytes())?;
-    }
-
-    progress.inc();
-    let _info = progress.add_child("writing offsets");
-    {
-        let mut offsets64 = Vec::<u64>::new();
-        for entry in &entries_sorted_by_oid {
-            let offset: u32 = if entry.offset > LARGE_OFFSET_THRESHOLD {
-                assert!(
-                    offsets64.len() < LARGE_OFFSET_THRESHOLD as usize,
-                    "Encoding breakdown - way too many 64bit offsets"
-                );
-                offsets64.push(entry.offset);
-                ((offsets64.len() - 1) as u32) | HIGH_BIT
-            } else {
-                entry.offset as u32
-            };
-            out.write_all(&offset.to_be_bytes())?;
-        }
-        for value in offsets64 {
-            out.write_all(&value.to_be_bytes())?;
-        }
+    if parallel::num_threads(thread_limit) > 1 {
+        let (ids, (crc32s, offsets)) = parallel::join(
+            {
+                let entries = entries_sorted_by_oid.clone();
+                move || encode_to_vec(|buf| write_ids(buf, entries))
+            },
+            || {
+                parallel::join(
+                    {
+                        let entries = entries_sorted_by_oid.clone();
+                        move || encode_to_vec(|buf| write_crc32s(buf, entries))
+                    },
+                    move || encode_to_vec(|buf| write_offsets(buf, entries_sorted_by_oid)),
+                )
+            },
+        );
+
+        progress.inc();
+        let _info = progress.add_child("writing ids");
+        out.write_all(&ids)?;
+
+        progress.inc();
+        let _info = progress.add_child("writing crc32");
+        out.write_all(&crc32s)?;
+
+        progress.inc();
+        let _info = progress.add_child("writing offsets");
+        out.write_all(&offsets)?;
+    } else {
+        progress.inc();
+        let _info = progress.add_child("writing ids");
+        write_ids(&mut out, entries_sorted_by_oid.clone())?;
+
+        progress.inc();
+        let _info = progress.add_child("writing crc32");
+        write_crc32s(&mut out, entries_sorted_by_oid.clone())?;
+
+        progress.inc();
+        let _info = progress.add_child("writing offsets");
+        write_offsets(&mut out, entries_sorted_by_oid)?;
     }
 
     out.write_all(pack_hash.as_slice())?;
@@ -94,6 +107,84 @@ pub(crate) fn write_to(
     Ok(index_hash)
 }
 
+/// Write a reverse index in version 1 with the `index_positions_in_pack_order`, i.e. the position of each object
+/// in the pack index, ordered by the object's offset in the pack.
+pub(crate) fn write_reverse_index_to(
+    out: impl io::Write,
+    index_positions_in_pack_order: impl Iterator<Item = u32>,
+    pack_hash: &git_hash::oid,
+    object_hash: git_hash::Kind,
+) -> io::Result<git_hash::ObjectId> {
+    use io::Write;
+    let mut out = std::io::BufWriter::with_capacity(8 * 4096, hash::Write::new(out, object_hash));
+    out.write_all(crate::index::REVERSE_INDEX_SIGNATURE)?;
+    out.write_all(&1u32.to_be_bytes())?;
+    let hash_id: u32 = match object_hash {
+        git_hash::Kind::Sha1 => 1,
+    };
+    out.write_all(&hash_id.to_be_bytes())?;
+    for index_position in index_positions_in_pack_order {
+        out.write_all(&index_position.to_be_bytes())?;
+    }
+    out.write_all(pack_hash.as_bytes())?;
+
+    let mut out = out.into_inner()?;
+    let checksum: git_hash::ObjectId = out.hash.digest().into();
+    out.inner.write_all(checksum.as_slice())?;
+    out.inner.flush()?;
+    Ok(checksum)
+}
+
+fn encode_to_vec(write: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write(&mut buf).expect("writing to memory never fails");
+    buf
+}
+
+fn write_ids(
+    out: &mut impl io::Write,
+    entries: impl Iterator<Item = (git_hash::ObjectId, u32, crate::data::Offset)>,
+) -> io::Result<()> {
+    for (id, _, _) in entries {
+        out.write_all(id.as_slice())?;
+    }
+    Ok(())
+}
+
+fn write_crc32s(
+    out: &mut impl io::Write,
+    entries: impl Iterator<Item = (git_hash::ObjectId, u32, crate::data::Offset)>,
+) -> io::Result<()> {
+    for (_, crc32, _) in entries {
+        out.write_all(&crc32.to_be_bytes())?;
+    }
+    Ok(())
+}
+
+fn write_offsets(
+    out: &mut impl io::Write,
+    entries: impl Iterator<Item = (git_hash::ObjectId, u32, crate::data::Offset)>,
+) -> io::Result<()> {
+    let mut offsets64 = Vec::<u64>::new();
+    for (_, _, pack_offset) in entries {
+        let offset: u32 = if pack_offset > LARGE_OFFSET_THRESHOLD {
+            assert!(
+                offsets64.len() < LARGE_OFFSET_THRESHOLD as usize,
+                "Encoding breakdown - way too many 64bit offsets"
+            );
+            offsets64.push(pack_offset);
+            ((offsets64.len() - 1) as u32) | HIGH_BIT
+        } else {
+            pack_offset as u32
+        };
+        out.write_all(&offset.to_be_bytes())?;
+    }
+    for value in offsets64 {
+        out.write_all(&value.to_be_bytes())?;
+    }
+    Ok(())
+}
+
 pub(crate) fn fanout(iter: impl ExactSizeIterator<Item = u8>) -> [u32; 256] {
     let mut fan_out = [0u32; 256];
     let entries_len = iter.len() as u32;
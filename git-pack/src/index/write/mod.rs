@@ -28,6 +28,72 @@ pub struct Outcome {
     pub num_objects: u32,
 }
 
+/// A phase boundary reached while executing
+/// [`write_data_iter_to_stream_with_checkpoints()`][crate::index::File::write_data_iter_to_stream_with_checkpoints()],
+/// in the order in which they are reached.
+///
+/// The state it carries is enough to resume writing the index with [`write_entries_to_stream()`] without
+/// reading and resolving the pack again.
+#[derive(Debug, Clone, Copy)]
+pub enum Checkpoint<'a> {
+    /// All entries were read from the pack and added to the delta tree, but no object was resolved yet.
+    EntriesCounted {
+        /// The amount of objects in the pack.
+        num_objects: u32,
+        /// The hash of the pack the index is written for.
+        pack_hash: &'a git_hash::ObjectId,
+    },
+    /// All objects in the delta tree were resolved to obtain their id, and are sorted by it.
+    TreeResolved {
+        /// All entries to write into the index, sorted by their id.
+        entries_sorted_by_oid: EntriesSortedByOid<'a>,
+        /// The hash of the pack the index is written for.
+        pack_hash: &'a git_hash::ObjectId,
+    },
+    /// Encoding the index into the output stream is about to start.
+    EncodingStarted,
+}
+
+/// The entries to write into a pack index, sorted by their id, as provided by [`Checkpoint::TreeResolved`].
+///
+/// This is a view into the state used for writing the index to avoid holding all entries in memory twice.
+#[derive(Clone, Copy)]
+pub struct EntriesSortedByOid<'a> {
+    items: &'a [crate::cache::delta::Item<TreeEntry>],
+}
+
+impl<'a> EntriesSortedByOid<'a> {
+    /// Return the amount of entries.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Return `true` if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Return an iterator over all entries, sorted by id.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = crate::index::Entry> + Clone + Send + 'a {
+        self.items.iter().map(|item| crate::index::Entry {
+            oid: item.data.id,
+            pack_offset: item.offset,
+            crc32: Some(item.data.crc32),
+        })
+    }
+
+    /// Copy all entries into a `Vec`, for instance to [resume writing][write_entries_to_stream()] the index later.
+    pub fn to_vec(&self) -> Vec<crate::index::Entry> {
+        self.iter().collect()
+    }
+}
+
+impl std::fmt::Debug for EntriesSortedByOid<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
 /// Various ways of writing an index file from pack entries
 impl crate::index::File {
     /// Write information about `entries` as obtained from a pack data file into a pack index file via the `out` stream.
@@ -51,6 +117,41 @@ impl crate::index::File {
     /// the write operation to fail.
     #[allow(clippy::too_many_arguments)]
     pub fn write_data_iter_to_stream<F, F2>(
+        version: crate::index::Version,
+        make_resolver: F,
+        entries: impl Iterator<Item = Result<crate::data::input::Entry, crate::data::input::Error>>,
+        thread_limit: Option<usize>,
+        root_progress: impl Progress,
+        out: impl io::Write,
+        should_interrupt: &AtomicBool,
+        object_hash: git_hash::Kind,
+        pack_version: crate::data::Version,
+    ) -> Result<Outcome, Error>
+    where
+        F: FnOnce() -> io::Result<F2>,
+        F2: for<'r> Fn(crate::data::EntryRange, &'r mut Vec<u8>) -> Option<()> + Send + Clone,
+    {
+        Self::write_data_iter_to_stream_with_checkpoints(
+            version,
+            make_resolver,
+            entries,
+            thread_limit,
+            root_progress,
+            out,
+            should_interrupt,
+            object_hash,
+            pack_version,
+            |_checkpoint| {},
+        )
+    }
+
+    /// Like [`write_data_iter_to_stream()`][crate::index::File::write_data_iter_to_stream()], but call `checkpoint` at each
+    /// [phase boundary][Checkpoint] with the state obtained so far.
+    ///
+    /// This allows to persist the result of the costly phases to be able to resume writing the index after an interruption,
+    /// and to estimate the cost of restarting the operation.
+    #[allow(clippy::too_many_arguments)]
+    pub fn write_data_iter_to_stream_with_checkpoints<F, F2>(
         version: crate::index::Version,
         make_resolver: F,
         entries: impl Iterator<Item = Result<crate::data::input::Entry, crate::data::input::Error>>,
@@ -60,6 +161,7 @@ impl crate::index::File {
         should_interrupt: &AtomicBool,
         object_hash: git_hash::Kind,
         pack_version: crate::data::Version,
+        mut checkpoint: impl FnMut(Checkpoint<'_>),
     ) -> Result<Outcome, Error>
     where
         F: FnOnce() -> io::Result<F2>,
@@ -151,8 +253,23 @@ impl crate::index::File {
 
         root_progress.inc();
 
+        let pack_hash = match last_seen_trailer {
+            Some(ph) => ph,
+            None if num_objects == 0 => {
+                let header = crate::data::header::encode(pack_version, 0);
+                let mut hasher = git_features::hash::hasher(object_hash);
+                hasher.update(&header);
+                git_hash::ObjectId::from(hasher.digest())
+            }
+            None => return Err(Error::IteratorInvariantTrailer),
+        };
+        checkpoint(Checkpoint::EntriesCounted {
+            num_objects,
+            pack_hash: &pack_hash,
+        });
+
         let resolver = make_resolver()?;
-        let sorted_pack_offsets_by_oid = {
+        let items_sorted_by_oid = {
             let traverse::Outcome { roots, children } = tree.traverse(
                 resolver,
                 pack_entries_end,
@@ -173,6 +290,7 @@ impl crate::index::File {
                     thread_limit,
                     should_interrupt,
                     object_hash,
+                    memory_limit: None,
                 },
             )?;
             root_progress.inc();
@@ -187,22 +305,22 @@ impl crate::index::File {
             root_progress.inc();
             items
         };
+        checkpoint(Checkpoint::TreeResolved {
+            entries_sorted_by_oid: EntriesSortedByOid {
+                items: &items_sorted_by_oid,
+            },
+            pack_hash: &pack_hash,
+        });
 
-        let pack_hash = match last_seen_trailer {
-            Some(ph) => ph,
-            None if num_objects == 0 => {
-                let header = crate::data::header::encode(pack_version, 0);
-                let mut hasher = git_features::hash::hasher(object_hash);
-                hasher.update(&header);
-                git_hash::ObjectId::from(hasher.digest())
-            }
-            None => return Err(Error::IteratorInvariantTrailer),
-        };
+        checkpoint(Checkpoint::EncodingStarted);
         let index_hash = encode::write_to(
             out,
-            sorted_pack_offsets_by_oid,
+            items_sorted_by_oid
+                .iter()
+                .map(|item| (item.data.id, item.data.crc32, item.offset)),
             &pack_hash,
             version,
+            thread_limit,
             root_progress.add_child("writing index file"),
         )?;
         root_progress.show_throughput_with(
@@ -220,6 +338,93 @@ impl crate::index::File {
     }
 }
 
+/// Compute the checksum of the pack index file of `version` that would be written for `entries_sorted_by_oid` belonging to
+/// the pack with `pack_hash`, without writing it anywhere.
+///
+/// The checksum is the same as the one returned by [`write_data_iter_to_stream()`][crate::index::File::write_data_iter_to_stream()]
+/// for the same objects, which is useful to detect duplicate indices.
+/// As the index to compute is [`crate::index::Version::V2`], entries without CRC32, like those from version 1 indices, are
+/// recorded with a CRC32 of 0.
+pub fn index_hash_of(
+    entries_sorted_by_oid: &[crate::index::Entry],
+    pack_hash: &git_hash::ObjectId,
+    version: crate::index::Version,
+) -> Result<git_hash::ObjectId, Error> {
+    write_entries_to_stream(
+        entries_sorted_by_oid,
+        pack_hash,
+        version,
+        io::sink(),
+        None,
+        progress::Discard,
+    )
+}
+
+/// Write the pack index file of `version` for `entries_sorted_by_oid` belonging to the pack with `pack_hash` into `out`,
+/// and return its checksum.
+///
+/// Use `thread_limit` to control the parallelism of encoding the index tables, and `progress` to stay informed.
+/// This is the final phase of [`write_data_iter_to_stream()`][crate::index::File::write_data_iter_to_stream()], and
+/// can be used to resume writing an index from the state provided by [`Checkpoint::TreeResolved`].
+/// Entries without CRC32 are recorded with a CRC32 of 0.
+pub fn write_entries_to_stream(
+    entries_sorted_by_oid: &[crate::index::Entry],
+    pack_hash: &git_hash::ObjectId,
+    version: crate::index::Version,
+    out: impl io::Write,
+    thread_limit: Option<usize>,
+    progress: impl Progress,
+) -> Result<git_hash::ObjectId, Error> {
+    if version != crate::index::Version::default() {
+        return Err(Error::Unsupported(version));
+    }
+    Ok(encode::write_to(
+        out,
+        entries_sorted_by_oid
+            .iter()
+            .map(|entry| (entry.oid, entry.crc32.unwrap_or(0), entry.pack_offset)),
+        pack_hash,
+        version,
+        thread_limit,
+        progress,
+    )?)
+}
+
+/// Write a reverse index (version 1) for `entries_sorted_by_oid` belonging to the pack with `pack_hash` into `out`, mapping
+/// each position in the pack to the position of the object in the index, and return its trailing checksum.
+///
+/// Like [`write_entries_to_stream()`], this works with the entries at hand while writing an index, as provided by
+/// [`Checkpoint::TreeResolved`], without the need to read the index back.
+pub fn write_reverse_index_to_stream(
+    entries_sorted_by_oid: impl Iterator<Item = crate::index::Entry>,
+    pack_hash: &git_hash::oid,
+    object_hash: git_hash::Kind,
+    out: impl io::Write,
+) -> io::Result<git_hash::ObjectId> {
+    let pack_offsets: Vec<_> = entries_sorted_by_oid.map(|entry| entry.pack_offset).collect();
+    let mut index_positions: Vec<u32> = (0..pack_offsets.len() as u32).collect();
+    index_positions.sort_unstable_by_key(|idx| pack_offsets[*idx as usize]);
+    encode::write_reverse_index_to(out, index_positions.into_iter(), pack_hash, object_hash)
+}
+
+/// Writing reverse indices
+impl crate::index::File {
+    /// Write a reverse index (version 1) for this pack index into `out`, mapping each position in the pack to the position
+    /// of the object in this index, and return its trailing checksum.
+    ///
+    /// By convention, the reverse index is stored alongside the pack index with the `.rev` extension.
+    pub fn write_reverse_index_to(&self, out: impl io::Write) -> io::Result<git_hash::ObjectId> {
+        let mut index_positions: Vec<u32> = (0..self.num_objects()).collect();
+        index_positions.sort_unstable_by_key(|idx| self.pack_offset_at_index(*idx));
+        encode::write_reverse_index_to(
+            out,
+            index_positions.into_iter(),
+            &self.pack_checksum(),
+            self.object_hash(),
+        )
+    }
+}
+
 fn modify_base(entry: &mut TreeEntry, pack_entry: &crate::data::Entry, decompressed: &[u8], hash: git_hash::Kind) {
     fn compute_hash(kind: git_object::Kind, bytes: &[u8], object_hash: git_hash::Kind) -> git_hash::ObjectId {
         let mut hasher = git_features::hash::hasher(object_hash);
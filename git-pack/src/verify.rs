@@ -28,6 +28,9 @@ pub fn fan(data: &[u32]) -> Option<usize> {
 /// Calculate the hash of the given kind by trying to read the file from disk at `data_path` or falling back on the mapped content in `data`.
 /// `Ok(desired_hash)` or `Err(Some(actual_hash))` is returned if the hash matches or mismatches.
 /// If the `Err(None)` is returned, the operation was interrupted.
+///
+/// `progress` is initialized with the amount of bytes to hash, which is the size of the file without its trailing hash,
+/// and incremented as bytes are hashed.
 pub fn checksum_on_disk_or_mmap(
     data_path: &Path,
     data: &[u8],
@@ -48,6 +51,7 @@ pub fn checksum_on_disk_or_mmap(
         Err(err) if err.kind() == std::io::ErrorKind::Interrupted => return Err(checksum::Error::Interrupted),
         Err(_io_err) => {
             let start = std::time::Instant::now();
+            progress.init(Some(data_len_without_trailer), git_features::progress::bytes());
             let mut hasher = git_features::hash::hasher(object_hash);
             hasher.update(&data[..data_len_without_trailer]);
             progress.inc_by(data_len_without_trailer);
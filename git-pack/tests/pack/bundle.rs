@@ -75,6 +75,42 @@ mod locate {
     }
 }
 
+mod objects_in_pack_order {
+    use git_odb::pack;
+
+    use crate::{fixture_path, pack::PACKS_AND_INDICES};
+
+    #[test]
+    fn yields_all_objects_resolved_and_sorted_by_pack_offset() -> Result<(), Box<dyn std::error::Error>> {
+        for (index_path, _data_path) in PACKS_AND_INDICES {
+            let bundle = pack::Bundle::at(fixture_path(index_path), git_hash::Kind::Sha1)?;
+            let mut expected_ids: Vec<_> = bundle.index.iter().map(|e| e.oid).collect();
+
+            let mut ids = Vec::new();
+            let mut last_offset = None;
+            for item in bundle.objects_in_pack_order(pack::cache::Never) {
+                let (id, kind, data) = item?;
+                git_object::Data::new(kind, &data).verify_checksum(id)?;
+
+                let offset = bundle
+                    .index
+                    .pack_offset_at_index(bundle.index.lookup(id).expect("id is in index"));
+                assert!(
+                    last_offset.map_or(true, |last| last < offset),
+                    "objects are returned in pack order"
+                );
+                last_offset = Some(offset);
+                ids.push(id);
+            }
+
+            ids.sort();
+            expected_ids.sort();
+            assert_eq!(ids, expected_ids, "each object is returned exactly once");
+        }
+        Ok(())
+    }
+}
+
 mod write_to_directory {
     use std::{fs, path::Path, sync::atomic::AtomicBool};
 
@@ -99,13 +135,14 @@ mod write_to_directory {
             index_path: None,
             data_path: None,
             keep_path: None,
+            reverse_index_path: None,
             object_hash: git_hash::Kind::Sha1,
         })
     }
 
     #[test]
     fn without_providing_one() -> Result<(), Box<dyn std::error::Error>> {
-        let res = write_pack(None::<&Path>, SMALL_PACK)?;
+        let res = write_pack(None::<&Path>, SMALL_PACK, false)?;
         assert_eq!(res, expected_outcome()?);
         assert_eq!(
             res.index.index_hash,
@@ -118,7 +155,7 @@ mod write_to_directory {
     #[test]
     fn given_a_directory() -> Result<(), Box<dyn std::error::Error>> {
         let dir = TempDir::new()?;
-        let mut res = write_pack(Some(&dir), SMALL_PACK)?;
+        let mut res = write_pack(Some(&dir), SMALL_PACK, false)?;
         let (index_path, data_path, keep_path) = (res.index_path.take(), res.data_path.take(), res.keep_path.take());
         assert_eq!(res, expected_outcome()?);
         let mut sorted_entries = fs::read_dir(&dir)?.filter_map(Result::ok).collect::<Vec<_>>();
@@ -142,6 +179,324 @@ mod write_to_directory {
         Ok(())
     }
 
+    #[test]
+    fn given_a_directory_with_reverse_index() -> Result<(), Box<dyn std::error::Error>> {
+        use std::convert::TryInto;
+        let dir = TempDir::new()?;
+        let res = write_pack(Some(&dir), SMALL_PACK, true)?;
+        let reverse_index_path = res.reverse_index_path.clone().expect("reverse index was requested");
+        assert_eq!(
+            file_name_of(&reverse_index_path),
+            format!("pack-{}.rev", res.index.data_hash.to_hex())
+        );
+        assert_eq!(fs::read_dir(&dir)?.count(), 4, "pack, index, keep and reverse index");
+
+        let rev = fs::read(&reverse_index_path)?;
+        let hash_len = git_hash::Kind::Sha1.len_in_bytes();
+        let num_objects = res.index.num_objects as usize;
+        assert_eq!(rev.len(), 12 + num_objects * 4 + hash_len * 2);
+        assert_eq!(&rev[..4], b"RIDX", "signature");
+        assert_eq!(&rev[4..8], &1u32.to_be_bytes(), "version");
+        assert_eq!(&rev[8..12], &1u32.to_be_bytes(), "hash id of sha1");
+        assert_eq!(
+            &rev[12 + num_objects * 4..][..hash_len],
+            res.index.data_hash.as_slice(),
+            "the pack checksum follows the table"
+        );
+        assert_eq!(
+            &rev[rev.len() - hash_len..],
+            {
+                let mut hasher = git_features::hash::hasher(git_hash::Kind::Sha1);
+                hasher.update(&rev[..rev.len() - hash_len]);
+                hasher.digest()
+            },
+            "the trailer is a checksum over all prior bytes"
+        );
+
+        let index = pack::index::File::at(res.index_path.as_ref().expect("index written"), git_hash::Kind::Sha1)?;
+        let mut rev_from_index = Vec::new();
+        index.write_reverse_index_to(&mut rev_from_index)?;
+        assert_eq!(
+            rev, rev_from_index,
+            "the reverse index written from memory is the same as the one computed from the index on disk"
+        );
+        let index_position_at =
+            |pack_position: usize| u32::from_be_bytes(rev[12 + pack_position * 4..][..4].try_into().expect("4 bytes"));
+        let mut sorted_offsets = index.sorted_offsets();
+        sorted_offsets.sort_unstable();
+        let mut seen = vec![false; num_objects];
+        for pack_position in 0..num_objects {
+            let index_position = index_position_at(pack_position);
+            assert!(
+                !std::mem::replace(&mut seen[index_position as usize], true),
+                "each index position is listed exactly once"
+            );
+            assert_eq!(
+                index.pack_offset_at_index(index_position),
+                sorted_offsets[pack_position],
+                "each entry maps a position in the pack to the index position of the object at that offset"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_index_versions_fail_before_reading_the_pack() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = TempDir::new()?;
+        let should_interrupt = AtomicBool::new(false);
+        let err = pack::Bundle::write_to_directory(
+            std::io::BufReader::new(fs::File::open(fixture_path(SMALL_PACK))?),
+            None,
+            Some(&dir),
+            progress::Discard,
+            &should_interrupt,
+            None,
+            pack::bundle::write::Options {
+                index_version: pack::index::Version::V1,
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                pack::bundle::write::Error::IndexWrite(pack::index::write::Error::Unsupported(
+                    pack::index::Version::V1
+                ))
+            ),
+            "{:?}",
+            err
+        );
+        assert_eq!(fs::read_dir(&dir)?.count(), 0, "nothing was written");
+        Ok(())
+    }
+
+    #[test]
+    fn indexing_progress_has_the_object_count_of_the_pack_header_as_total() -> Result<(), Box<dyn std::error::Error>> {
+        let progress = record::Progress::default();
+        let should_interrupt = AtomicBool::new(false);
+        pack::Bundle::write_to_directory(
+            std::io::BufReader::new(fs::File::open(fixture_path(SMALL_PACK))?),
+            None,
+            None::<&Path>,
+            progress.clone(),
+            &should_interrupt,
+            None,
+            Default::default(),
+        )?;
+        assert_eq!(
+            progress.max_by_name("indexing"),
+            Some(expected_outcome()?.index.num_objects as usize),
+            "the amount of objects to receive is known once the pack header was read, even if the pack size isn't"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn read_progress_has_a_total_if_the_pack_size_is_known() -> Result<(), Box<dyn std::error::Error>> {
+        let pack_path = fixture_path(SMALL_PACK);
+        let pack_size = fs::metadata(&pack_path)?.len();
+        let progress = record::Progress::default();
+        let should_interrupt = AtomicBool::new(false);
+        pack::Bundle::write_to_directory(
+            std::io::BufReader::new(fs::File::open(pack_path)?),
+            Some(pack_size),
+            None::<&Path>,
+            progress.clone(),
+            &should_interrupt,
+            None,
+            Default::default(),
+        )?;
+        assert_eq!(
+            progress.max_by_name("read pack"),
+            Some(pack_size as usize),
+            "the read progress knows its total and can display a percentage"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn read_progress_is_forwarded_from_the_reading_thread_when_writing_eagerly(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let pack_path = fixture_path(SMALL_PACK);
+        let pack_size = fs::metadata(&pack_path)?.len();
+        let progress = record::Progress::default();
+        static SHOULD_INTERRUPT: AtomicBool = AtomicBool::new(false);
+        pack::Bundle::write_to_directory_eagerly(
+            fs::File::open(pack_path)?,
+            Some(pack_size),
+            None::<&Path>,
+            progress.clone(),
+            &SHOULD_INTERRUPT,
+            None,
+            Default::default(),
+        )?;
+        assert_eq!(
+            progress.max_by_name("read pack"),
+            Some(pack_size as usize),
+            "all progress of the reading thread arrived by the time the pack was written"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn temporary_files_can_have_deterministic_names() -> Result<(), Box<dyn std::error::Error>> {
+        /// A reader which records whether the temporary pack data file exists while the pack is read.
+        struct AssertTempFile<R> {
+            inner: R,
+            temp_path: std::path::PathBuf,
+            seen: bool,
+        }
+        impl<R: std::io::Read> std::io::Read for &mut AssertTempFile<R> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                self.seen |= self.temp_path.is_file();
+                self.inner.read(buf)
+            }
+        }
+
+        let dir = TempDir::new()?;
+        let mut pack = AssertTempFile {
+            inner: fs::File::open(fixture_path(SMALL_PACK))?,
+            temp_path: dir.path().join("incoming.pack"),
+            seen: false,
+        };
+        let should_interrupt = AtomicBool::new(false);
+        let outcome = pack::Bundle::write_to_directory(
+            std::io::BufReader::new(&mut pack),
+            None,
+            Some(&dir),
+            progress::Discard,
+            &should_interrupt,
+            None,
+            pack::bundle::write::Options {
+                temp_file_name: Some("incoming".into()),
+                ..Default::default()
+            },
+        )?;
+        assert!(pack.seen, "the pack data is written to the named temporary file");
+        assert!(!pack.temp_path.exists(), "it was moved into place");
+        assert!(!dir.path().join("incoming.idx").exists());
+        assert!(outcome.data_path.expect("written").is_file());
+        assert!(outcome.index_path.expect("written").is_file());
+        Ok(())
+    }
+
+    #[test]
+    fn reading_pack_entries_without_mmap_yields_the_same_index() -> Result<(), Box<dyn std::error::Error>> {
+        let mut index_data = Vec::new();
+        for use_mmap in [true, false] {
+            let dir = TempDir::new()?;
+            let should_interrupt = AtomicBool::new(false);
+            let outcome = pack::Bundle::write_to_directory(
+                std::io::BufReader::new(fs::File::open(fixture_path(SMALL_PACK))?),
+                None,
+                Some(&dir),
+                progress::Discard,
+                &should_interrupt,
+                None,
+                pack::bundle::write::Options {
+                    use_mmap,
+                    ..Default::default()
+                },
+            )?;
+            index_data.push((outcome.index, fs::read(outcome.index_path.expect("written"))?));
+        }
+        assert_eq!(
+            index_data[0], index_data[1],
+            "the index is the same no matter how pack entries are read"
+        );
+        assert_eq!(
+            index_data[1].1,
+            fs::read(fixture_path(SMALL_PACK_INDEX))?,
+            "and matches the original index"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn crc_verification_passes_for_intact_pack_data() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = TempDir::new()?;
+        let outcome = pack::Bundle::write_to_directory(
+            std::io::BufReader::new(fs::File::open(fixture_path(SMALL_PACK))?),
+            None,
+            Some(&dir),
+            progress::Discard,
+            &AtomicBool::new(false),
+            None,
+            pack::bundle::write::Options {
+                verify_crc_on_write: true,
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(
+            fs::read(outcome.index_path.expect("written"))?,
+            fs::read(fixture_path(SMALL_PACK_INDEX))?
+        );
+        Ok(())
+    }
+
+    mod record {
+        use std::sync::{Arc, Mutex};
+
+        use git_features::progress::{MessageLevel, Step, Unit};
+
+        /// A progress implementation which records the maximum each named task was initialized with.
+        #[derive(Default, Clone)]
+        pub struct Progress {
+            name: String,
+            max_by_name: Arc<Mutex<Vec<(String, Option<Step>)>>>,
+        }
+
+        impl Progress {
+            pub fn max_by_name(&self, name: &str) -> Option<Step> {
+                self.max_by_name
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .rev()
+                    .find_map(|(n, max)| (n == name).then(|| *max))
+                    .flatten()
+            }
+        }
+
+        impl git_features::progress::Progress for Progress {
+            type SubProgress = Self;
+
+            fn add_child(&mut self, name: impl Into<String>) -> Self::SubProgress {
+                Progress {
+                    name: name.into(),
+                    max_by_name: Arc::clone(&self.max_by_name),
+                }
+            }
+
+            fn init(&mut self, max: Option<Step>, _unit: Option<Unit>) {
+                self.max_by_name.lock().unwrap().push((self.name.clone(), max));
+            }
+
+            fn set(&mut self, _step: Step) {}
+
+            fn step(&self) -> Step {
+                0
+            }
+
+            fn inc_by(&mut self, _step: Step) {}
+
+            fn set_name(&mut self, name: impl Into<String>) {
+                self.name = name.into();
+            }
+
+            fn name(&self) -> Option<String> {
+                Some(self.name.clone())
+            }
+
+            fn message(&mut self, _level: MessageLevel, _message: impl Into<String>) {}
+        }
+    }
+
+    fn file_name_of(path: &Path) -> String {
+        path.file_name().unwrap().to_str().unwrap().to_owned()
+    }
+
     fn file_name(entry: &fs::DirEntry) -> String {
         entry.path().file_name().unwrap().to_str().unwrap().to_owned()
     }
@@ -149,6 +504,7 @@ mod write_to_directory {
     fn write_pack(
         directory: Option<impl AsRef<Path>>,
         pack_file: &str,
+        write_reverse_index: bool,
     ) -> Result<pack::bundle::write::Outcome, Box<dyn std::error::Error>> {
         let pack_file = fs::File::open(fixture_path(pack_file))?;
         static SHOULD_INTERRUPT: AtomicBool = AtomicBool::new(false);
@@ -164,8 +520,175 @@ mod write_to_directory {
                 iteration_mode: pack::data::input::Mode::Verify,
                 index_version: pack::index::Version::V2,
                 object_hash: git_hash::Kind::Sha1,
+                write_reverse_index,
+                temp_file_name: None,
+                use_mmap: true,
+                verify_crc_on_write: false,
             },
         )
         .map_err(Into::into)
     }
 }
+
+mod write_objects_to_directory {
+    use std::sync::atomic::AtomicBool;
+
+    use git_features::progress;
+    use git_odb::pack;
+    use tempfile::TempDir;
+
+    use crate::{fixture_path, pack::SMALL_PACK_INDEX};
+
+    fn object(kind: git_object::Kind, data: &[u8]) -> (git_hash::ObjectId, git_object::Kind, Vec<u8>) {
+        let mut hasher = git_features::hash::hasher(git_hash::Kind::Sha1);
+        hasher.update(&git_object::encode::loose_header(kind, data.len()));
+        hasher.update(data);
+        (hasher.digest().into(), kind, data.to_owned())
+    }
+
+    #[test]
+    fn in_memory_objects_can_be_read_back() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = TempDir::new()?;
+        let objects = vec![
+            object(git_object::Kind::Blob, b"hello"),
+            object(git_object::Kind::Blob, b""),
+            object(git_object::Kind::Blob, &[42; 1024]),
+            object(git_object::Kind::Tree, b""),
+        ];
+        let outcome = pack::Bundle::write_objects_to_directory(
+            objects.clone(),
+            Some(&dir),
+            progress::Discard,
+            &AtomicBool::new(false),
+            Default::default(),
+        )?;
+        assert_eq!(outcome.index.num_objects, 4);
+
+        let bundle = outcome.to_bundle().expect("written to directory")?;
+        let mut buf = Vec::new();
+        for (id, kind, data) in &objects {
+            let (obj, _location) = bundle
+                .find(id, &mut buf, &mut pack::cache::Never)?
+                .expect("object was written");
+            assert_eq!(obj.kind, *kind);
+            assert_eq!(obj.data, data.as_slice());
+        }
+        let read_back: Vec<_> = bundle
+            .objects_in_pack_order(pack::cache::Never)
+            .collect::<Result<_, _>>()?;
+        assert_eq!(read_back, objects, "objects are stored in the order they are received");
+        Ok(())
+    }
+
+    #[test]
+    fn objects_of_an_existing_pack_can_be_repacked() -> Result<(), Box<dyn std::error::Error>> {
+        let bundle = pack::Bundle::at(fixture_path(SMALL_PACK_INDEX), git_hash::Kind::Sha1)?;
+        let objects = bundle
+            .objects_in_pack_order(pack::cache::Never)
+            .collect::<Result<Vec<_>, _>>()?;
+        let outcome = pack::Bundle::write_objects_to_directory(
+            objects,
+            None::<TempDir>,
+            progress::Discard,
+            &AtomicBool::new(false),
+            Default::default(),
+        )?;
+        assert_eq!(outcome.index.num_objects, bundle.index.num_objects());
+        assert!(outcome.to_bundle().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn mismatching_object_ids_are_rejected() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = TempDir::new()?;
+        let (id, _kind, data) = object(git_object::Kind::Blob, b"hello");
+        let err = pack::Bundle::write_objects_to_directory(
+            Some((id, git_object::Kind::Tree, data)),
+            Some(&dir),
+            progress::Discard,
+            &AtomicBool::new(false),
+            Default::default(),
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, pack::bundle::write::Error::ObjectIdMismatch { expected, .. } if expected == id),
+            "{:?}",
+            err
+        );
+        assert_eq!(std::fs::read_dir(&dir)?.count(), 0, "nothing was persisted");
+        Ok(())
+    }
+}
+
+mod index_from_pack {
+    use std::{fs, sync::atomic::AtomicBool};
+
+    use git_features::progress;
+    use git_odb::pack;
+    use tempfile::TempDir;
+
+    use crate::{
+        fixture_path,
+        pack::{SMALL_PACK, SMALL_PACK_INDEX},
+    };
+
+    #[test]
+    fn a_lost_index_can_be_regenerated() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = TempDir::new()?;
+        let pack_path = dir.path().join("pack-a2bf8e71d8c18879e499335762dd95119d93d9f1.pack");
+        fs::copy(fixture_path(SMALL_PACK), &pack_path)?;
+        fs::copy(fixture_path(SMALL_PACK_INDEX), pack_path.with_extension("idx"))?;
+        fs::remove_file(pack_path.with_extension("idx"))?;
+
+        let outcome = pack::Bundle::index_from_pack(
+            &pack_path,
+            Some(dir.path()),
+            progress::Discard,
+            &AtomicBool::new(false),
+            pack::bundle::write::Options {
+                write_reverse_index: true,
+                ..Default::default()
+            },
+        )?;
+        let index_path = outcome.index_path.clone().expect("written");
+        assert_eq!(
+            index_path,
+            pack_path.with_extension("idx"),
+            "it's placed next to the pack"
+        );
+        assert_eq!(outcome.data_path.as_deref(), Some(pack_path.as_path()));
+        assert_eq!(outcome.keep_path, None, "the pack is already in place");
+        assert!(outcome.reverse_index_path.as_ref().expect("requested").is_file());
+        assert_eq!(
+            fs::read(&index_path)?,
+            fs::read(fixture_path(SMALL_PACK_INDEX))?,
+            "the index is exactly the same as the original"
+        );
+
+        let original = pack::Bundle::at(fixture_path(SMALL_PACK_INDEX), git_hash::Kind::Sha1)?;
+        let regenerated = outcome.to_bundle().expect("written to directory")?;
+        assert_eq!(regenerated.index.num_objects(), original.index.num_objects());
+        for entry in original.index.iter() {
+            let index = regenerated.index.lookup(entry.oid).expect("object is present");
+            assert_eq!(regenerated.index.pack_offset_at_index(index), entry.pack_offset);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn without_directory_nothing_is_written() -> Result<(), Box<dyn std::error::Error>> {
+        let outcome = pack::Bundle::index_from_pack(
+            fixture_path(SMALL_PACK),
+            None::<&std::path::Path>,
+            progress::Discard,
+            &AtomicBool::new(false),
+            Default::default(),
+        )?;
+        assert_eq!(outcome.index_path, None);
+        assert_eq!(
+            outcome.index.num_objects,
+            pack::index::File::at(fixture_path(SMALL_PACK_INDEX), git_hash::Kind::Sha1)?.num_objects()
+        );
+        Ok(())
+    }
+}
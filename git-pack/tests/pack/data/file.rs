@@ -30,6 +30,21 @@ mod method {
         Ok(())
     }
 
+    #[test]
+    fn verify_checksum_reports_hashed_bytes_as_progress() -> Result<(), Box<dyn std::error::Error>> {
+        use git_features::progress::Progress;
+
+        let p = pack_at(SMALL_PACK);
+        let (progress, _receiver) = progress::channel(progress::Discard);
+        p.verify_checksum(progress.clone(), &AtomicBool::new(false))?;
+        assert_eq!(
+            progress.step(),
+            p.data_len() - git_hash::Kind::Sha1.len_in_bytes(),
+            "all bytes but the trailing checksum are hashed"
+        );
+        Ok(())
+    }
+
     #[test]
     fn iter() -> Result<(), Box<dyn std::error::Error>> {
         let pack = pack_at(SMALL_PACK);
@@ -13,3 +13,32 @@ fn encode_decode_roundtrip() -> crate::Result {
     assert_eq!(actual_encoded_header, expected_encoded_header);
     Ok(())
 }
+
+#[test]
+fn version_and_object_count_can_be_read_without_consuming_more_than_the_header() -> crate::Result {
+    let buf = std::fs::read(fixture_path(
+        "objects/pack/pack-11fdfa9e156ab73caae3b6da867192221f2089c2.pack",
+    ))?;
+    let mut read = &buf[..];
+    let (version, num_objects) = git_pack::data::Version::from_reader(&mut read)?;
+    assert_eq!(
+        (version, num_objects),
+        git_pack::data::header::decode(buf[..12].try_into()?)?
+    );
+    assert_eq!(read.len(), buf.len() - 12, "only the header was read");
+    Ok(())
+}
+
+#[test]
+fn reading_truncated_or_invalid_headers_fails() {
+    use git_pack::data::{header::decode::Error, Version};
+    assert!(matches!(Version::from_reader(&mut &b"PACK"[..]), Err(Error::Read(_))));
+    assert!(matches!(
+        Version::from_reader(&mut &b"KCAP\0\0\0\x02\0\0\0\0"[..]),
+        Err(Error::Corrupt(_))
+    ));
+    assert!(matches!(
+        Version::from_reader(&mut &git_pack::data::header::encode(Version::V3, 0)[..]),
+        Ok((Version::V3, 0))
+    ));
+}
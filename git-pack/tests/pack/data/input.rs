@@ -166,6 +166,31 @@ mod lookup_ref_delta_objects {
         ))
     }
 
+    #[test]
+    fn each_resolved_base_object_is_counted_as_progress() {
+        use git_features::progress::Progress;
+
+        let id = hex_to_id("ff4c1a33b08a6b5da1b3a3ef2bd6fa2f8b2d2b6d");
+        let input = vec![entry(delta_ref(id), D_A), entry(base(), D_B), entry(delta_ref(id), D_C)];
+        let (progress, _receiver) = git_features::progress::channel::channel(git_features::progress::Discard);
+        let actual = LookupRefDeltaObjectsIter::new_with_progress(
+            into_results_iter(compute_offsets(input)),
+            |_, buf| {
+                buf.clear();
+                buf.extend_from_slice(D_D);
+                Some(git_object::Data {
+                    kind: git_object::Kind::Blob,
+                    data: buf.as_slice(),
+                })
+            },
+            progress.clone(),
+        )
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+        assert_eq!(actual.len(), 4, "the base object is inserted only once");
+        assert_eq!(progress.step(), 1, "only lookups of base objects are counted");
+    }
+
     #[test]
     fn inner_errors_are_passed_on() {
         let input = vec![
@@ -387,6 +387,7 @@ fn write_and_verify(
     let bundle = pack::Bundle::at(
         pack::Bundle::write_to_directory(
             std::io::BufReader::new(std::fs::File::open(pack_file_path)?),
+            None,
             Some(tmp_dir.path()),
             progress::Discard,
             &should_interrupt,
@@ -7,7 +7,7 @@ use git_odb::pack;
 
 use crate::{
     fixture_path, hex_to_id,
-    pack::{INDEX_V1, PACK_FOR_INDEX_V1, SMALL_PACK, SMALL_PACK_INDEX},
+    pack::{INDEX_V1, PACK_FOR_INDEX_V1, SMALL_PACK, SMALL_PACK_INDEX, V2_PACKS_AND_INDICES},
 };
 
 mod version {
@@ -212,6 +212,14 @@ mod version {
                     outcome.index_hash,
                     git_hash::ObjectId::from(&expected[end_of_pack_hash..end_of_index_hash])
                 );
+                let entries: Vec<_> = index::File::at(fixture_path(index_path), git_hash::Kind::Sha1)?
+                    .iter()
+                    .collect();
+                assert_eq!(
+                    index::write::index_hash_of(&entries, &outcome.data_hash, desired_kind)?,
+                    outcome.index_hash,
+                    "the hash can be computed without writing the index"
+                );
                 Ok(())
             }
             for mode in &[input::Mode::AsIs, input::Mode::Verify, input::Mode::Restore] {
@@ -234,6 +242,53 @@ mod version {
             Ok(())
         }
 
+        #[test]
+        fn write_to_stream_is_independent_of_the_thread_limit() -> Result<(), Box<dyn std::error::Error>> {
+            for (_index_path, data_path) in V2_PACKS_AND_INDICES {
+                let write_index = |thread_limit: Option<usize>| -> Result<_, Box<dyn std::error::Error>> {
+                    let pack_iter = pack::data::input::BytesToEntriesIter::new_from_header(
+                        io::BufReader::new(fs::File::open(fixture_path(data_path))?),
+                        input::Mode::Verify,
+                        input::EntryDataMode::Crc32,
+                        git_hash::Kind::Sha1,
+                    )?;
+                    let pack_version = pack_iter.version();
+                    let mut out = Vec::new();
+                    let outcome = pack::index::File::write_data_iter_to_stream(
+                        pack::index::Version::default(),
+                        {
+                            let buf = git_features::threading::OwnShared::new({
+                                let file = std::fs::File::open(fixture_path(data_path))?;
+                                unsafe { memmap2::Mmap::map(&file)? }
+                            });
+                            move || {
+                                Ok(move |entry: EntryRange, out: &mut Vec<u8>| {
+                                    buf.get(entry.start as usize..entry.end as usize)
+                                        .map(|slice| out.copy_from_slice(slice))
+                                })
+                            }
+                        },
+                        pack_iter,
+                        thread_limit,
+                        progress::Discard,
+                        &mut out,
+                        &AtomicBool::new(false),
+                        git_hash::Kind::Sha1,
+                        pack_version,
+                    )?;
+                    Ok((out, outcome))
+                };
+                let (sequential, sequential_outcome) = write_index(Some(1))?;
+                let (parallel, parallel_outcome) = write_index(Some(4))?;
+                assert_eq!(
+                    parallel, sequential,
+                    "sections encoded in parallel are written exactly like the sequential ones"
+                );
+                assert_eq!(parallel_outcome, sequential_outcome);
+            }
+            Ok(())
+        }
+
         #[test]
         fn lookup_missing() {
             let file = index::File::at(&fixture_path(INDEX_V2), git_hash::Kind::Sha1).unwrap();
@@ -273,6 +328,125 @@ fn traverse_with_index_and_forward_ref_deltas() {
     assert_eq!(count.load(Ordering::SeqCst), 9, "we traverse all objects");
 }
 
+#[test]
+fn traverse_with_index_fails_gracefully_on_pack_offsets_out_of_bounds() -> Result<(), Box<dyn std::error::Error>> {
+    let index = index::File::at(&fixture_path(INDEX_V2), git_hash::Kind::Sha1)?;
+    let num_objects = index.num_objects() as usize;
+    let offsets_start = 8 + 256 * 4 + num_objects * (20 + 4);
+    let mut index_data = std::fs::read(fixture_path(INDEX_V2))?;
+    index_data[offsets_start..][..4].copy_from_slice(&0x7fff_fff0_u32.to_be_bytes());
+
+    let tmp = tempfile::tempdir()?;
+    let index_path = tmp.path().join("pack.idx");
+    std::fs::write(&index_path, &index_data)?;
+
+    let index = index::File::at(&index_path, git_hash::Kind::Sha1)?;
+    let data = pack::data::File::at(&fixture_path(PACK_FOR_INDEX_V2), git_hash::Kind::Sha1)?;
+    let res = index.traverse_with_index(
+        &data,
+        || |_, _: &[u8], _: &index::Entry, _: &mut _| Ok::<_, std::io::Error>(()),
+        progress::Discard,
+        &AtomicBool::new(false),
+        index::traverse::with_index::Options {
+            check: index::traverse::SafetyCheck::SkipFileAndObjectChecksumVerification,
+            ..Default::default()
+        },
+    );
+    match res {
+        Err(index::traverse::Error::Tree(err)) => assert_eq!(
+            err.to_string(),
+            format!(
+                "Pack offset {} of an index entry is not within the pack data ending at {}",
+                0x7fff_fff0_u32,
+                data.pack_end()
+            ),
+            "a corrupt index entry is an error, not a panic"
+        ),
+        res => unreachable!("expected out of bounds error, got {:?}", res.map(|_| ())),
+    }
+    Ok(())
+}
+
+#[test]
+fn traverse_with_index_can_skip_index_checksum_verification() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = tempfile::tempdir()?;
+    let index_path = tmp.path().join("pack.idx");
+    let mut index_data = std::fs::read(fixture_path(INDEX_V2))?;
+    *index_data.last_mut().expect("trailer") ^= 0xff;
+    std::fs::write(&index_path, &index_data)?;
+
+    let index = index::File::at(&index_path, git_hash::Kind::Sha1)?;
+    let data = pack::data::File::at(&fixture_path(PACK_FOR_INDEX_V2), git_hash::Kind::Sha1)?;
+    let traverse = |check| {
+        index.traverse_with_index(
+            &data,
+            || |_, _: &[u8], _: &index::Entry, _: &mut _| Ok::<_, std::io::Error>(()),
+            progress::Discard,
+            &AtomicBool::new(false),
+            index::traverse::with_index::Options {
+                check,
+                ..Default::default()
+            },
+        )
+    };
+    assert!(
+        matches!(
+            traverse(index::traverse::SafetyCheck::All),
+            Err(index::traverse::Error::VerifyChecksum(_))
+        ),
+        "the tampered index trailer is detected"
+    );
+    assert_eq!(
+        traverse(index::traverse::SafetyCheck::SkipIndexChecksumVerification)?.actual_index_checksum,
+        index.index_checksum(),
+        "the index isn't hashed, but the pack is"
+    );
+    Ok(())
+}
+
+#[test]
+fn traverse_with_index_and_memory_limit_resolves_bases_again() {
+    let index = index::File::at(&fixture_path(INDEX_V2), git_hash::Kind::Sha1).unwrap();
+    let data = pack::data::File::at(&fixture_path(PACK_FOR_INDEX_V2), git_hash::Kind::Sha1).unwrap();
+    let traverse = |memory_limit| {
+        let objects = std::sync::Mutex::new(Vec::new());
+        let statistics = index
+            .traverse_with_index(
+                &data,
+                || {
+                    |kind, bytes: &[u8], entry: &index::Entry, _: &mut _| {
+                        objects.lock().unwrap().push((
+                            entry.oid,
+                            kind,
+                            git_object::Data::new(kind, bytes).verify_checksum(entry.oid).is_ok(),
+                        ));
+                        Ok::<_, std::io::Error>(())
+                    }
+                },
+                progress::Discard,
+                &AtomicBool::new(false),
+                index::traverse::with_index::Options {
+                    memory_limit,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .statistics;
+        let mut objects = objects.into_inner().unwrap();
+        objects.sort();
+        (statistics, objects)
+    };
+    let (expected_statistics, expected_objects) = traverse(None);
+    let (statistics, objects) = traverse(Some(0));
+    assert_eq!(objects.len(), index.num_objects() as usize);
+    assert!(
+        objects.iter().all(|(_, _, hash_matches)| *hash_matches),
+        "objects resolved from the pack are correct"
+    );
+    assert_eq!(objects, expected_objects);
+    assert_eq!(statistics, expected_statistics, "the limit doesn't affect the outcome");
+}
+
 use git_features::progress;
 use git_pack::{cache, data::decode_entry::Outcome, index};
 use maplit::btreemap;
@@ -321,6 +495,27 @@ fn pack_lookup() -> Result<(), Box<dyn std::error::Error>> {
                 num_tags: 0,
                 num_trees: 15,
                 pack_size: 51875,
+                num_ofs_deltas: 12,
+                num_ref_deltas: 0,
+                largest_object: Some((hex_to_id("15926d8d6d17d1cbdf7f03c457e8ff983270f363"), 30637)),
+                deepest_chain: Some((hex_to_id("18bd3fc20b0565f94bce0a3e94b6a83b26b88627"), 6)),
+                objects_per_size_bucket: btreemap! {
+                    object::Kind::Tree => btreemap! {
+                        16384 => 11,
+                        32768 => 4,
+                    },
+                    object::Kind::Blob => btreemap! {
+                        64 => 1,
+                        1024 => 1,
+                        2048 => 1,
+                        16384 => 1,
+                        32768 => 1,
+                    },
+                    object::Kind::Commit => btreemap! {
+                        256 => 1,
+                        512 => 9,
+                    },
+                },
             },
         ),
         (
@@ -346,6 +541,29 @@ fn pack_lookup() -> Result<(), Box<dyn std::error::Error>> {
                 num_tags: 0,
                 num_trees: 2,
                 pack_size: 49113,
+                num_ofs_deltas: 0,
+                num_ref_deltas: 3,
+                largest_object: Some((hex_to_id("882265122665fe2a876e8b23e3ec6899cd0faa23"), 29200)),
+                deepest_chain: Some((hex_to_id("98cce1b038a908bec51ccd2f7e1c1f648cb429a1"), 1)),
+                objects_per_size_bucket: btreemap! {
+                    object::Kind::Tree => btreemap! {
+                        4096 => 2,
+                    },
+                    object::Kind::Blob => btreemap! {
+                        128 => 2,
+                        256 => 3,
+                        512 => 15,
+                        1024 => 15,
+                        2048 => 8,
+                        4096 => 12,
+                        8192 => 5,
+                        16384 => 2,
+                        32768 => 1,
+                    },
+                    object::Kind::Commit => btreemap! {
+                        256 => 2,
+                    },
+                },
             },
         ),
         (
@@ -372,6 +590,25 @@ fn pack_lookup() -> Result<(), Box<dyn std::error::Error>> {
                 num_tags: 0,
                 num_trees: 14,
                 pack_size: 3732,
+                num_ofs_deltas: 12,
+                num_ref_deltas: 0,
+                largest_object: Some((hex_to_id("78bed30acc35358406b51120a2e7d29a0cebd6c1"), 1181)),
+                deepest_chain: Some((hex_to_id("3ae1a72ef1f1c7a9c3932f494ab216ba39db2854"), 2)),
+                objects_per_size_bucket: btreemap! {
+                    object::Kind::Tree => btreemap! {
+                        64 => 14,
+                    },
+                    object::Kind::Blob => btreemap! {
+                        128 => 3,
+                        256 => 1,
+                        512 => 2,
+                        1024 => 4,
+                        2048 => 4,
+                    },
+                    object::Kind::Commit => btreemap! {
+                        256 => 14,
+                    },
+                },
             },
         ),
     ] {
@@ -411,6 +648,16 @@ fn pack_lookup() -> Result<(), Box<dyn std::error::Error>> {
             .sum::<usize>();
         let sorted_offsets = idx.sorted_offsets();
         assert_eq!(num_objects, sorted_offsets.len());
+        let num_objects_in_size_buckets = |kind| {
+            stats
+                .objects_per_size_bucket
+                .get(&kind)
+                .map_or(0, |buckets| buckets.values().sum::<u32>())
+        };
+        assert_eq!(num_objects_in_size_buckets(object::Kind::Commit), stats.num_commits);
+        assert_eq!(num_objects_in_size_buckets(object::Kind::Tree), stats.num_trees);
+        assert_eq!(num_objects_in_size_buckets(object::Kind::Blob), stats.num_blobs);
+        assert_eq!(num_objects_in_size_buckets(object::Kind::Tag), stats.num_tags);
         for idx_entry in idx.iter() {
             let pack_entry = pack.entry(idx_entry.pack_offset);
             assert_ne!(pack_entry.data_offset, idx_entry.pack_offset);
@@ -461,6 +708,103 @@ fn pack_lookup() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn index_hash_of_matches_the_checksum_of_written_indices() -> Result<(), Box<dyn std::error::Error>> {
+    for (index_path, _data_path) in V2_PACKS_AND_INDICES {
+        let idx = index::File::at(fixture_path(index_path), git_hash::Kind::Sha1)?;
+        let entries: Vec<_> = idx.iter().collect();
+        assert_eq!(
+            index::write::index_hash_of(&entries, &idx.pack_checksum(), index::Version::V2)?,
+            idx.index_checksum()
+        );
+    }
+
+    let idx = index::File::at(fixture_path(INDEX_V1), git_hash::Kind::Sha1)?;
+    assert!(
+        matches!(
+            index::write::index_hash_of(&[], &idx.pack_checksum(), index::Version::V1),
+            Err(index::write::Error::Unsupported(index::Version::V1))
+        ),
+        "only versions we can write are supported"
+    );
+    Ok(())
+}
+
+#[test]
+fn checkpoints_are_reached_in_order_and_allow_resuming() -> Result<(), Box<dyn std::error::Error>> {
+    use git_pack::index::write::Checkpoint;
+    use std::{fs, io};
+
+    let (index_path, data_path) = V2_PACKS_AND_INDICES[0];
+    let expected_index = index::File::at(fixture_path(index_path), git_hash::Kind::Sha1)?;
+    let pack_iter = pack::data::input::BytesToEntriesIter::new_from_header(
+        io::BufReader::new(fs::File::open(fixture_path(data_path))?),
+        pack::data::input::Mode::Verify,
+        pack::data::input::EntryDataMode::Crc32,
+        git_hash::Kind::Sha1,
+    )?;
+    let pack_version = pack_iter.version();
+    let pack_data = fs::read(fixture_path(data_path))?;
+
+    let mut phases = Vec::new();
+    let mut resumable_entries = Vec::new();
+    let mut out = Vec::new();
+    let outcome = index::File::write_data_iter_to_stream_with_checkpoints(
+        index::Version::default(),
+        || {
+            Ok(|entry: pack::data::EntryRange, out: &mut Vec<u8>| {
+                pack_data
+                    .get(entry.start as usize..entry.end as usize)
+                    .map(|slice| out.copy_from_slice(slice))
+            })
+        },
+        pack_iter,
+        None,
+        progress::Discard,
+        &mut out,
+        &AtomicBool::new(false),
+        git_hash::Kind::Sha1,
+        pack_version,
+        |checkpoint| {
+            phases.push(match checkpoint {
+                Checkpoint::EntriesCounted { num_objects, pack_hash } => {
+                    assert_eq!(num_objects, expected_index.num_objects());
+                    assert_eq!(*pack_hash, expected_index.pack_checksum());
+                    "entries counted"
+                }
+                Checkpoint::TreeResolved {
+                    entries_sorted_by_oid,
+                    pack_hash,
+                } => {
+                    assert_eq!(*pack_hash, expected_index.pack_checksum());
+                    resumable_entries = entries_sorted_by_oid.to_vec();
+                    "tree resolved"
+                }
+                Checkpoint::EncodingStarted => "encoding started",
+            });
+        },
+    )?;
+    assert_eq!(phases, ["entries counted", "tree resolved", "encoding started"]);
+    assert_eq!(
+        resumable_entries,
+        expected_index.iter().collect::<Vec<_>>(),
+        "the resolved entries are those of the index"
+    );
+
+    let mut resumed = Vec::new();
+    let index_hash = index::write::write_entries_to_stream(
+        &resumable_entries,
+        &outcome.data_hash,
+        index::Version::default(),
+        &mut resumed,
+        None,
+        progress::Discard,
+    )?;
+    assert_eq!(index_hash, outcome.index_hash);
+    assert_eq!(resumed, out, "writing can be resumed from the state of a checkpoint");
+    Ok(())
+}
+
 #[test]
 fn iter() -> Result<(), Box<dyn std::error::Error>> {
     for (path, kind, num_objects, index_checksum, pack_checksum) in &[
@@ -504,3 +848,81 @@ fn iter() -> Result<(), Box<dyn std::error::Error>> {
     }
     Ok(())
 }
+
+#[test]
+fn iter_prefix() -> Result<(), Box<dyn std::error::Error>> {
+    for path in &[INDEX_V1, INDEX_V2, SMALL_PACK_INDEX] {
+        let idx = index::File::at(&fixture_path(path), git_hash::Kind::Sha1)?;
+        let all: Vec<_> = idx.iter().collect();
+        let mut num_entries = 0;
+        for first_byte in 0..=u8::MAX {
+            let entries: Vec<_> = idx.iter_prefix(first_byte).collect();
+            let expected: Vec<_> = all
+                .iter()
+                .filter(|e| e.oid.as_bytes()[0] == first_byte)
+                .cloned()
+                .collect();
+            assert_eq!(entries, expected, "{}: entries for first byte {:02x}", path, first_byte);
+            num_entries += entries.len();
+        }
+        assert_eq!(num_entries, all.len(), "every entry is part of exactly one prefix");
+    }
+    Ok(())
+}
+
+#[test]
+fn offsets_in_range() -> Result<(), Box<dyn std::error::Error>> {
+    for path in &[INDEX_V1, INDEX_V2, SMALL_PACK_INDEX] {
+        let idx = index::File::at(&fixture_path(path), git_hash::Kind::Sha1)?;
+        let num_objects = idx.num_objects();
+        for (start, end) in [(0, num_objects), (0, 0), (3, 10), (num_objects - 1, num_objects)] {
+            let expected: Vec<_> = (start..end).map(|idx_pos| idx.pack_offset_at_index(idx_pos)).collect();
+            assert_eq!(
+                idx.offsets_in_range(start, end),
+                expected,
+                "{}: {}..{}",
+                path,
+                start,
+                end
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn verify_crcs_detects_a_single_flipped_byte() -> Result<(), Box<dyn std::error::Error>> {
+    let index = index::File::at(&fixture_path(INDEX_V2), git_hash::Kind::Sha1)?;
+    let data = pack::data::File::at(&fixture_path(PACK_FOR_INDEX_V2), git_hash::Kind::Sha1)?;
+    index.verify_crcs(&data, None)?;
+    index.verify_crcs(&data, Some(1))?;
+
+    let tmp = tempfile::tempdir()?;
+    let pack_path = tmp.path().join("pack.pack");
+    let mut pack_data = std::fs::read(fixture_path(PACK_FOR_INDEX_V2))?;
+    let entry_offset = index.pack_offset_at_index(index.num_objects() / 2);
+    pack_data[entry_offset as usize + 2] ^= 0x01;
+    std::fs::write(&pack_path, &pack_data)?;
+
+    let data = pack::data::File::at(&pack_path, git_hash::Kind::Sha1)?;
+    let err = index.verify_crcs(&data, None).unwrap_err();
+    assert!(
+        matches!(err, index::verify::crc32::Error::Mismatch { offset, .. } if offset == entry_offset),
+        "{:?}",
+        err
+    );
+    Ok(())
+}
+
+#[test]
+fn verify_crcs_requires_a_v2_index() -> Result<(), Box<dyn std::error::Error>> {
+    let index = index::File::at(&fixture_path(INDEX_V1), git_hash::Kind::Sha1)?;
+    let data = pack::data::File::at(&fixture_path(PACK_FOR_INDEX_V1), git_hash::Kind::Sha1)?;
+    assert!(matches!(
+        index.verify_crcs(&data, None),
+        Err(index::verify::crc32::Error::Unsupported {
+            version: index::Version::V1
+        })
+    ));
+    Ok(())
+}
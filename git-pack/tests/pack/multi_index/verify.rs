@@ -1,6 +1,7 @@
 use std::sync::atomic::AtomicBool;
 
 use git_features::progress;
+use git_testtools::hex_to_id;
 use maplit::btreemap;
 
 use crate::pack::multi_index::multi_index;
@@ -43,6 +44,37 @@ fn integrity() {
             total_decompressed_entries_size: 40919,
             total_object_size: 131993,
             pack_size: 42856,
+            num_ofs_deltas: 542,
+            num_ref_deltas: 0,
+            largest_object: Some((hex_to_id("38addcd5651bff731e44738a8848ae09191a11d3"), 2880)),
+            deepest_chain: Some((hex_to_id("1e0e3af81c422b412b88058f6f195bd50322d821"), 4)),
+            objects_per_size_bucket: btreemap! {
+                git_object::Kind::Tree => btreemap! {
+                    32 => 1,
+                    64 => 1,
+                    128 => 5,
+                    512 => 5,
+                    1024 => 4,
+                    2048 => 18,
+                    4096 => 6,
+                },
+                git_object::Kind::Blob => btreemap! {
+                    4 => 9,
+                    8 => 30,
+                    16 => 61,
+                    32 => 89,
+                    64 => 141,
+                    128 => 232,
+                    256 => 237,
+                    512 => 12,
+                },
+                git_object::Kind::Commit => btreemap! {
+                    256 => 16,
+                },
+                git_object::Kind::Tag => btreemap! {
+                    256 => 1,
+                },
+            },
             num_commits: 16,
             num_trees: 40,
             num_tags: 1,
@@ -31,6 +31,8 @@ mod error {
         TransportProtocolPolicyViolation { actual_version: git_transport::Protocol },
         #[error(transparent)]
         ParseRefs(#[from] refs::parse::Error),
+        #[error("Credentials for {url:?} didn't provide a username and password, which is the only kind of identity supported by the transport")]
+        IdentityMissing { url: String },
     }
 }
 pub use error::Error;
@@ -80,9 +82,10 @@ pub(crate) mod function {
                     drop(result); // needed to workaround this: https://github.com/rust-lang/rust/issues/76149
                     let url = transport.to_url();
                     progress.set_name("authentication");
-                    let credentials::protocol::Outcome { identity, next } =
-                        authenticate(credentials::helper::Action::get_for_url(url))?
+                    let credentials::protocol::Outcome { identity, next, .. } =
+                        authenticate(credentials::helper::Action::get_for_url(url.clone()))?
                             .expect("FILL provides an identity or errors");
+                    let identity = identity.ok_or(Error::IdentityMissing { url })?;
                     transport.set_identity(identity)?;
                     progress.step();
                     progress.set_name("handshake (authenticated)");
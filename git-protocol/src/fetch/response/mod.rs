@@ -149,6 +149,10 @@ pub struct Response {
 
 impl Response {
     /// Return true if the response has a pack which can be read next.
+    ///
+    /// Note that the response doesn't know how many objects the pack contains as the protocol doesn't transmit this information.
+    /// Instead it's part of the header of the pack itself, which is why the progress of reading the pack
+    /// with `git_pack::Bundle::write_to_directory()` displays the amount of objects to expect.
     pub fn has_pack(&self) -> bool {
         self.has_pack
     }
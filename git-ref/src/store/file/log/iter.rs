@@ -56,24 +56,30 @@ pub mod decode {
 /// This iterator is useful when the ref log file is going to be rewritten which forces processing of the entire file.
 /// It will continue parsing even if individual log entries failed to parse, leaving it to the driver to decide whether to
 /// abort or continue.
+/// A last line without trailing newline that fails to parse is considered the result of an interrupted write and is skipped silently.
 pub fn forward(lines: &[u8]) -> Forward<'_> {
     Forward {
-        inner: lines.as_bstr().lines().enumerate(),
+        inner: lines.as_bstr().lines().enumerate().peekable(),
+        last_line_is_incomplete: !lines.is_empty() && !lines.ends_with(b"\n"),
     }
 }
 
 /// An iterator yielding parsed lines in a file from start to end, oldest to newest.
 pub struct Forward<'a> {
-    inner: std::iter::Enumerate<git_object::bstr::Lines<'a>>,
+    inner: std::iter::Peekable<std::iter::Enumerate<git_object::bstr::Lines<'a>>>,
+    last_line_is_incomplete: bool,
 }
 
 impl<'a> Iterator for Forward<'a> {
     type Item = Result<log::LineRef<'a>, decode::Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next().map(|(ln, line)| {
-            log::LineRef::from_bytes(line).map_err(|err| decode::Error::new(err, decode::LineNumber::FromStart(ln)))
-        })
+        let (ln, line) = self.inner.next()?;
+        match log::LineRef::from_bytes(line) {
+            Ok(line) => Some(Ok(line)),
+            Err(_) if self.last_line_is_incomplete && self.inner.peek().is_none() => None,
+            Err(err) => Some(Err(decode::Error::new(err, decode::LineNumber::FromStart(ln)))),
+        }
     }
 }
 
@@ -89,7 +95,7 @@ pub struct Platform<'a, 's> {
 }
 
 impl<'a, 's> Platform<'a, 's> {
-    /// Return a forward iterator over all log-lines, most recent to oldest.
+    /// Return a reverse iterator over all log-lines, most recent to oldest, or `None` if there is no reflog.
     pub fn rev(&mut self) -> std::io::Result<Option<log::iter::Reverse<'_, std::fs::File>>> {
         self.buf.clear();
         self.buf.resize(512, 0);
@@ -98,7 +104,7 @@ impl<'a, 's> Platform<'a, 's> {
             .map_err(must_be_io_err)
     }
 
-    /// Return a forward iterator over all log-lines, oldest to most recent.
+    /// Return a forward iterator over all log-lines, oldest to most recent, or `None` if there is no reflog.
     pub fn all(&mut self) -> std::io::Result<Option<log::iter::Forward<'_>>> {
         self.buf.clear();
         self.store.reflog_iter(self.name, &mut self.buf).map_err(must_be_io_err)
@@ -111,6 +117,7 @@ pub struct Reverse<'a, F> {
     count: usize,
     read_and_pos: Option<(F, u64)>,
     last_nl_pos: Option<usize>,
+    last_line_is_incomplete: bool,
 }
 
 /// An iterator over entries of the `log` file in reverse, using `buf` as sliding window.
@@ -123,6 +130,7 @@ pub struct Reverse<'a, F> {
 ///
 /// It will continue parsing even if individual log entries failed to parse, leaving it to the driver to decide whether to
 /// abort or continue.
+/// A last line without trailing newline that fails to parse is considered the result of an interrupted write and is skipped silently.
 pub fn reverse<F>(mut log: F, buf: &mut [u8]) -> std::io::Result<Reverse<'_, F>>
 where
     F: std::io::Read + std::io::Seek,
@@ -139,6 +147,7 @@ where
         count: 0,
         read_and_pos: Some((log, pos)),
         last_nl_pos: None,
+        last_line_is_incomplete: false,
     })
 }
 
@@ -183,7 +192,12 @@ where
                 };
 
                 let last_byte = *buf.last().expect("we have read non-zero bytes before");
-                self.last_nl_pos = Some(if last_byte != b'\n' { buf.len() } else { buf.len() - 1 });
+                self.last_line_is_incomplete = last_byte != b'\n';
+                self.last_nl_pos = Some(if self.last_line_is_incomplete {
+                    buf.len()
+                } else {
+                    buf.len() - 1
+                });
                 self.read_and_pos = Some((read, npos));
                 self.next()
             }
@@ -192,28 +206,15 @@ where
                 Some(start) => {
                     self.read_and_pos = Some(read_and_pos);
                     self.last_nl_pos = Some(start);
-                    let buf = &self.buf[start + 1..end];
-                    let res = Some(
-                        log::LineRef::from_bytes(buf)
-                            .map_err(|err| {
-                                reverse::Error::Decode(decode::Error::new(err, LineNumber::FromEnd(self.count)))
-                            })
-                            .map(Into::into),
-                    );
-                    self.count += 1;
-                    res
+                    match self.decode_line(start + 1..end) {
+                        Some(res) => Some(res),
+                        None => self.next(),
+                    }
                 }
                 None => {
                     let (mut read, last_read_pos) = read_and_pos;
                     if last_read_pos == 0 {
-                        let buf = &self.buf[..end];
-                        Some(
-                            log::LineRef::from_bytes(buf)
-                                .map_err(|err| {
-                                    reverse::Error::Decode(decode::Error::new(err, LineNumber::FromEnd(self.count)))
-                                })
-                                .map(Into::into),
-                        )
+                        self.decode_line(0..end)
                     } else {
                         let npos = last_read_pos.saturating_sub((self.buf.len() - end) as u64);
                         if npos == last_read_pos {
@@ -243,3 +244,19 @@ where
         }
     }
 }
+
+impl<'a, F> Reverse<'a, F> {
+    /// Decode the line at `range` of our buffer, or return `None` if it is an incomplete last line that should be skipped.
+    fn decode_line(&mut self, range: std::ops::Range<usize>) -> Option<Result<crate::log::Line, reverse::Error>> {
+        let is_last_line = self.count == 0;
+        self.count += 1;
+        match log::LineRef::from_bytes(&self.buf[range]) {
+            Ok(line) => Some(Ok(line.into())),
+            Err(_) if is_last_line && self.last_line_is_incomplete => None,
+            Err(err) => Some(Err(reverse::Error::Decode(decode::Error::new(
+                err,
+                LineNumber::FromEnd(self.count - 1),
+            )))),
+        }
+    }
+}
@@ -66,7 +66,15 @@ impl Iterator for SortedLoosePaths {
                         continue;
                     }
                 }
-                Err(err) => return Some(Err(err.into_io_error().expect("no symlink related errors"))),
+                Err(err) => {
+                    let is_root = err.depth() == 0;
+                    let err = err.into_io_error().expect("no symlink related errors");
+                    // A missing directory, like the one of a namespace with only packed references, has no loose references.
+                    if is_root && err.kind() == std::io::ErrorKind::NotFound {
+                        return None;
+                    }
+                    return Some(Err(err));
+                }
             }
         }
         None
@@ -89,4 +97,28 @@ impl file::Store {
     pub fn loose_iter_prefixed(&self, prefix: impl AsRef<Path>) -> std::io::Result<LooseThenPacked<'_, '_>> {
         self.iter_prefixed_packed(prefix, None)
     }
+
+    /// Return `true` if there are more than `limit` loose reference files below the `refs/` directory in the
+    /// [common directory][file::Store::common_dir_resolved()], indicating that references should be packed.
+    ///
+    /// Counting stops as soon as the `limit` is exceeded, and directories nested deeper than
+    /// [`MAX_AUTO_PACK_DEPTH`][file::Store::MAX_AUTO_PACK_DEPTH] are not looked at to keep the check cheap.
+    /// Files that can't be read are not counted, and a `limit` of 0 disables the check entirely.
+    pub fn should_auto_pack_refs(&self, limit: usize) -> bool {
+        if limit == 0 {
+            return false;
+        }
+        git_features::fs::walkdir_new(self.common_dir_resolved().join("refs"))
+            .max_depth(Self::MAX_AUTO_PACK_DEPTH)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .take(limit + 1)
+            .count()
+            > limit
+    }
+
+    /// The maximum depth below `refs/` at which [`should_auto_pack_refs()`][file::Store::should_auto_pack_refs()]
+    /// looks for loose references, enough for `refs/remotes/<remote>/<namespace>/<branch>`.
+    pub const MAX_AUTO_PACK_DEPTH: usize = 5;
 }
@@ -23,7 +23,9 @@ pub struct Store {
 
     /// The way to handle reflog edits
     pub write_reflog: WriteReflog,
-    /// The namespace to use for edits and reads
+    /// The namespace to use for edits and reads, like `refs/namespaces/foo/` as obtained by [`namespace::expand()`][crate::namespace::expand()].
+    ///
+    /// If set, names and symbolic targets are prefixed with it when writing and stripped of it when reading, which makes it transparent.
     pub namespace: Option<Namespace>,
     /// A packed buffer which can be mapped in one version and shared as such.
     /// It's updated only in one spot, which is prior to reading it based on file stamps.
@@ -67,6 +69,7 @@ pub struct Transaction<'s> {
     packed_transaction: Option<crate::store_impl::packed::Transaction>,
     updates: Option<Vec<transaction::Edit>>,
     packed_refs: transaction::PackedRefs,
+    record_packed_updates_in_reflog: bool,
 }
 
 pub(in crate::store_impl::file) fn path_to_name<'a>(path: impl Into<Cow<'a, Path>>) -> Cow<'a, BStr> {
@@ -100,5 +103,8 @@ pub mod transaction;
 ///
 pub mod packed;
 
+///
+pub mod rename;
+
 mod raw_ext;
 pub use raw_ext::ReferenceExt;
@@ -202,6 +202,16 @@ impl<'s> Platform<'s> {
         self.store
             .iter_prefixed_packed(prefix, self.packed.as_ref().map(|b| &***b))
     }
+
+    /// Return an iterator over all references within `namespace`, loose or packed, sorted by their name and with the namespace
+    /// stripped from their names, like `refs/heads/main` for `refs/namespaces/foo/refs/heads/main`.
+    ///
+    /// The namespace of the store, if set, is ignored in favor of `namespace`, which is useful to look into the namespaces of
+    /// multiple tenants with the same store. Use [`namespace::expand()`][crate::namespace::expand()] to obtain a namespace.
+    pub fn namespaced<'a>(&'a self, namespace: &'a Namespace) -> std::io::Result<LooseThenPacked<'a, 'a>> {
+        self.store
+            .iter_namespaced_packed(namespace, self.packed.as_ref().map(|b| &***b))
+    }
 }
 
 impl file::Store {
@@ -328,25 +338,39 @@ impl file::Store {
         packed: Option<&'p packed::Buffer>,
     ) -> std::io::Result<LooseThenPacked<'p, 's>> {
         match self.namespace.as_ref() {
-            Some(namespace) => self.iter_from_info(
-                IterInfo::PrefixAndBase {
-                    base: self.git_dir(),
-                    prefix: namespace.to_path(),
-                },
-                self.common_dir().map(|base| IterInfo::PrefixAndBase {
-                    base,
-                    prefix: namespace.to_path(),
-                }),
-                packed,
-            ),
+            Some(namespace) => self.iter_namespaced_packed(namespace, packed),
             None => self.iter_from_info(
                 IterInfo::Base { base: self.git_dir() },
                 self.common_dir().map(|base| IterInfo::Base { base }),
                 packed,
+                None,
             ),
         }
     }
 
+    /// Return an iterator over all references within `namespace`, loose or `packed`, sorted by their name and with
+    /// `namespace` stripped from their names, ignoring the namespace of the store.
+    ///
+    /// Packed references are found by binary search, so only references within `namespace` are visited.
+    pub fn iter_namespaced_packed<'s, 'p>(
+        &'s self,
+        namespace: &'s Namespace,
+        packed: Option<&'p packed::Buffer>,
+    ) -> std::io::Result<LooseThenPacked<'p, 's>> {
+        self.iter_from_info(
+            IterInfo::PrefixAndBase {
+                base: self.git_dir(),
+                prefix: namespace.to_path(),
+            },
+            self.common_dir().map(|base| IterInfo::PrefixAndBase {
+                base,
+                prefix: namespace.to_path(),
+            }),
+            packed,
+            Some(namespace),
+        )
+    }
+
     /// As [`iter(…)`][file::Store::iter()], but filters by `prefix`, i.e. "refs/heads".
     ///
     /// Please note that "refs/heads` or "refs\\heads" is equivalent to "refs/heads/"
@@ -363,7 +387,7 @@ impl file::Store {
                     .common_dir()
                     .map(|base| IterInfo::from_prefix(base, prefix.into()))
                     .transpose()?;
-                self.iter_from_info(git_dir_info, common_dir_info, packed)
+                self.iter_from_info(git_dir_info, common_dir_info, packed, None)
             }
             Some(namespace) => {
                 let prefix = namespace.to_owned().into_namespaced_prefix(prefix);
@@ -372,7 +396,7 @@ impl file::Store {
                     .common_dir()
                     .map(|base| IterInfo::from_prefix(base, prefix.into()))
                     .transpose()?;
-                self.iter_from_info(git_dir_info, common_dir_info, packed)
+                self.iter_from_info(git_dir_info, common_dir_info, packed, self.namespace.as_ref())
             }
         }
     }
@@ -382,6 +406,7 @@ impl file::Store {
         git_dir_info: IterInfo<'_>,
         common_dir_info: Option<IterInfo<'_>>,
         packed: Option<&'p packed::Buffer>,
+        namespace: Option<&'s Namespace>,
     ) -> std::io::Result<LooseThenPacked<'p, 's>> {
         Ok(LooseThenPacked {
             git_dir: self.git_dir(),
@@ -400,7 +425,7 @@ impl file::Store {
             iter_git_dir: git_dir_info.into_iter(),
             iter_common_dir: common_dir_info.map(IterInfo::into_iter),
             buf: Vec::new(),
-            namespace: self.namespace.as_ref(),
+            namespace,
         })
     }
 }
@@ -0,0 +1,137 @@
+use std::convert::TryFrom;
+
+use git_object::bstr::BString;
+
+use crate::{
+    store::WriteReflog,
+    store_impl::file,
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    FullName, PartialNameRef,
+};
+
+/// Options for use in [`file::Store::rename()`].
+#[derive(Debug, Clone)]
+pub struct Options<'a> {
+    /// The signature to use in the reflog entry of the renamed reference.
+    pub committer: git_actor::SignatureRef<'a>,
+    /// The message to put into the reflog of the renamed reference, like `Branch: renamed refs/heads/a to refs/heads/b`.
+    pub message: BString,
+    /// How to handle the case when the lock of any of the loose references can't be obtained right away.
+    pub ref_files_lock_fail_mode: git_lock::acquire::Fail,
+    /// How to handle the case when the lock of the packed-refs file can't be obtained right away.
+    pub packed_refs_lock_fail_mode: git_lock::acquire::Fail,
+}
+
+impl file::Store {
+    /// Rename the reference identified by `old` to the fully qualified name `new` within a single transaction, keeping
+    /// its value and its reflog.
+    ///
+    /// The loose references of `old` and `new` as well as the packed-refs file if `old` is packed are locked first, before
+    /// the reflog of `old` is copied to `new`. Then `new` is created with the value of `old` and a reflog entry configured in
+    /// `options`, and `old` is deleted along with its reflog. Failures before the transaction is committed leave the store
+    /// unchanged, and the copied reflog of `new` is removed if the transaction fails to commit.
+    ///
+    /// Note that symbolic references pointing to `old`, like `HEAD`, are not adjusted.
+    pub fn rename(&self, old: &PartialNameRef, new: &PartialNameRef, options: Options<'_>) -> Result<(), Error> {
+        let old_ref = self.try_find(old)?.ok_or_else(|| Error::NotFound {
+            name: old.as_bstr().to_owned(),
+        })?;
+        let new_name = FullName::try_from(new.as_bstr())?;
+        if self.try_find(new_name.as_ref())?.is_some() {
+            return Err(Error::AlreadyExists { name: new_name });
+        }
+
+        let edits = vec![
+            RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        mode: RefLog::AndReference,
+                        force_create_reflog: false,
+                        message: options.message,
+                    },
+                    expected: PreviousValue::MustNotExist,
+                    new: old_ref.target.clone(),
+                },
+                name: new_name.clone(),
+                deref: false,
+            },
+            RefEdit {
+                change: Change::Delete {
+                    expected: PreviousValue::MustExistAndMatch(old_ref.target),
+                    log: RefLog::AndReference,
+                },
+                name: old_ref.name.clone(),
+                deref: false,
+            },
+        ];
+        let transaction = self
+            .transaction()
+            .prepare(
+                edits,
+                options.ref_files_lock_fail_mode,
+                options.packed_refs_lock_fail_mode,
+            )
+            .map_err(|err| match err {
+                file::transaction::prepare::Error::MustNotExist { .. } => {
+                    Error::AlreadyExists { name: new_name.clone() }
+                }
+                file::transaction::prepare::Error::DeleteReferenceMustExist { .. }
+                | file::transaction::prepare::Error::MustExist { .. } => Error::NotFound {
+                    name: old.as_bstr().to_owned(),
+                },
+                err => err.into(),
+            })?;
+
+        let mut copied_log = None;
+        if self.write_reflog != WriteReflog::Disable {
+            let old_log = self.reflog_path(old_ref.name.as_ref());
+            if old_log.is_file() {
+                let new_log = self.reflog_path(new_name.as_ref());
+                if let Some(directory) = new_log.parent() {
+                    std::fs::create_dir_all(directory).map_err(Error::CopyReflog)?;
+                }
+                if let Err(err) = std::fs::copy(old_log, &new_log) {
+                    std::fs::remove_file(&new_log).ok();
+                    return Err(Error::CopyReflog(err));
+                }
+                copied_log = Some(new_log);
+            }
+        }
+
+        transaction.commit(options.committer).map_err(|err| {
+            if let Some(new_log) = copied_log {
+                // Keep the store unchanged, the original error is more relevant than one during cleanup.
+                std::fs::remove_file(new_log).ok();
+            }
+            err
+        })?;
+        Ok(())
+    }
+}
+
+mod error {
+    use git_object::bstr::BString;
+
+    use crate::{store_impl::file, FullName};
+
+    /// The error returned by [`file::Store::rename()`].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The reference {name:?} to rename does not exist")]
+        NotFound { name: BString },
+        #[error("The reference {} to rename to does already exist", name.as_bstr())]
+        AlreadyExists { name: FullName },
+        #[error("The new name isn't a valid fully qualified reference name")]
+        NewName(#[from] git_validate::refname::Error),
+        #[error(transparent)]
+        Find(#[from] file::find::Error),
+        #[error(transparent)]
+        Prepare(#[from] file::transaction::prepare::Error),
+        #[error(transparent)]
+        Commit(#[from] file::transaction::commit::Error),
+        #[error("The reflog could not be copied to the new reference")]
+        CopyReflog(#[source] std::io::Error),
+    }
+}
+pub use error::Error;
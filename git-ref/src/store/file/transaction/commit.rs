@@ -15,7 +15,7 @@ impl<'s> Transaction<'s> {
     ///
     /// In this stage, we perform the following operations:
     ///
-    /// * update the ref log
+    /// * update the ref log, which doesn't happen for updates to symbolic targets
     /// * move updated refs into place
     /// * delete reflogs and empty parent directories
     /// * delete packed refs
@@ -43,14 +43,17 @@ impl<'s> Transaction<'s> {
                     };
                     if update_reflog {
                         match new {
-                            Target::Symbolic(_) => {} // no reflog for symref changes
+                            // Reflog lines record the previous and new object id, which a symbolic target doesn't have without
+                            // resolving it. Hence changes to symbolic targets intentionally don't produce a reflog entry.
+                            Target::Symbolic(_) => {}
                             Target::Peeled(new_oid) => {
                                 let previous = match expected {
                                     PreviousValue::MustExistAndMatch(Target::Peeled(oid)) => Some(oid.to_owned()),
                                     _ => None,
                                 }
                                 .or(change.leaf_referent_previous_oid);
-                                let do_update = previous.as_ref().map_or(true, |previous| previous != new_oid);
+                                let do_update = (self.record_packed_updates_in_reflog && change.packed)
+                                    || previous.as_ref().map_or(true, |previous| previous != new_oid);
                                 if do_update {
                                     self.store.reflog_create_or_append(
                                         change.update.name.as_ref(),
@@ -154,6 +157,16 @@ impl<'s> Transaction<'s> {
         }
         Ok(updates.into_iter().map(|edit| edit.update).collect())
     }
+
+    /// Return the edits that [`commit(…)`][Transaction::commit()] would return after [preparing][Transaction::prepare()] this
+    /// transaction, including splits of symbolic references and all dependent edits, without making any of them permanent.
+    ///
+    /// All locks acquired during preparation are released, which rolls back the transaction perfectly and leaves the ref store
+    /// untouched. This is useful to preview the effect of a transaction, for instance to implement `--dry-run`.
+    pub fn commit_dry_run(self) -> Vec<RefEdit> {
+        let updates = self.updates.expect("BUG: must call prepare before commit");
+        updates.into_iter().map(|edit| edit.update).collect()
+    }
 }
 mod error {
     use git_object::bstr::BString;
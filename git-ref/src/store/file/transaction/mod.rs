@@ -3,7 +3,8 @@ use git_object::bstr::BString;
 
 use crate::{
     store_impl::{file, file::Transaction},
-    transaction::RefEdit,
+    transaction::{Change, LogChange, PreviousValue, RefEdit},
+    FullName, Target,
 };
 
 /// A function receiving an object id to resolve, returning its decompressed bytes,
@@ -43,6 +44,8 @@ pub(in crate::store_impl::file) struct Edit {
     /// For symbolic refs, this is the previous OID to put into the reflog instead of our own previous value. It's the
     /// peeled value of the leaf referent.
     leaf_referent_previous_oid: Option<ObjectId>,
+    /// Set if this update is also written to the packed-refs file as part of the packed transaction.
+    packed: bool,
 }
 
 impl Edit {
@@ -77,6 +80,7 @@ impl file::Store {
             packed_transaction: None,
             updates: None,
             packed_refs: PackedRefs::default(),
+            record_packed_updates_in_reflog: false,
         }
     }
 }
@@ -87,6 +91,44 @@ impl<'s> Transaction<'s> {
         self.packed_refs = packed_refs;
         self
     }
+
+    /// If `toggle` is true, updates that are written to the packed-refs file as configured with [`packed_refs()`][Self::packed_refs()]
+    /// are recorded in the reflog even if the value of the reference doesn't change, which is the case when packing loose references.
+    ///
+    /// That way packing references shows as distinct event in the reflog, using the message of the respective [`LogChange`].
+    /// Deletions never produce a reflog entry as their reflog is deleted along with them. This is off by default.
+    pub fn record_packed_updates_in_reflog(mut self, toggle: bool) -> Self {
+        self.record_packed_updates_in_reflog = toggle;
+        self
+    }
+
+    /// Prepare this transaction to let `name` point to `target` symbolically, creating `name` if needed, and with `expected`
+    /// constraining its current value. `lock_fail_mode` determines how to handle locks that can't be obtained right away.
+    ///
+    /// This is a shortcut for [`prepare()`][Transaction::prepare()] with a single update to a [`Target::Symbolic`] which doesn't
+    /// dereference `name`, hence existing peeled references are turned into symbolic ones.
+    /// Note that no reflog entry is written for symbolic targets as reflogs only record object ids.
+    pub fn set_symbolic_target(
+        self,
+        name: FullName,
+        target: FullName,
+        expected: PreviousValue,
+        lock_fail_mode: git_lock::acquire::Fail,
+    ) -> Result<Self, prepare::Error> {
+        self.prepare(
+            Some(RefEdit {
+                change: Change::Update {
+                    log: LogChange::default(),
+                    expected,
+                    new: Target::Symbolic(target),
+                },
+                name,
+                deref: false,
+            }),
+            lock_fail_mode,
+            lock_fail_mode,
+        )
+    }
 }
 
 ///
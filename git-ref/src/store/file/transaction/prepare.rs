@@ -43,11 +43,25 @@ impl<'s> Transaction<'s> {
             })
             .and_then(|maybe_loose| match (maybe_loose, packed) {
                 (None, Some(packed)) => packed
-                    .try_find(change.update.name.as_ref())
+                    .try_find(
+                        match &store.namespace {
+                            Some(namespace) => change.update.name.clone().prefix_namespace(namespace).to_owned(),
+                            None => change.update.name.clone(),
+                        }
+                        .as_ref(),
+                    )
                     .map(|opt| opt.map(Into::into))
                     .map_err(Error::from),
                 (None, None) => Ok(None),
                 (maybe_loose, _) => Ok(maybe_loose),
+            })
+            .map(|maybe_ref| {
+                maybe_ref.map(|mut r: Reference| {
+                    if let Some(namespace) = &store.namespace {
+                        r.strip_namespace(namespace);
+                    }
+                    r
+                })
             });
         let lock = match &mut change.update.change {
             Change::Delete { expected, .. } => {
@@ -156,7 +170,10 @@ impl<'s> Transaction<'s> {
 
                 lock.with_mut(|file| match new {
                     Target::Peeled(oid) => write!(file, "{}", oid),
-                    Target::Symbolic(name) => write!(file, "ref: {}", name.0),
+                    Target::Symbolic(name) => match &store.namespace {
+                        Some(namespace) => write!(file, "ref: {}", name.clone().prefix_namespace(namespace).as_bstr()),
+                        None => write!(file, "ref: {}", name.0),
+                    },
                 })?;
 
                 lock.close()?
@@ -188,6 +205,7 @@ impl<'s> Transaction<'s> {
                 lock: None,
                 parent_index: None,
                 leaf_referent_previous_oid: None,
+                packed: false,
             })
             .collect();
         updates
@@ -204,6 +222,7 @@ impl<'s> Transaction<'s> {
                     lock: None,
                     parent_index: Some(idx),
                     leaf_referent_previous_oid: None,
+                    packed: false,
                 },
             )
             .map_err(Error::PreprocessingFailed)?;
@@ -216,7 +235,7 @@ impl<'s> Transaction<'s> {
         if maybe_updates_for_packed_refs.is_some() || self.store.packed_refs_path().is_file() {
             let mut edits_for_packed_transaction = Vec::<RefEdit>::new();
             let mut needs_packed_refs_lookups = false;
-            for edit in updates.iter() {
+            for edit in updates.iter_mut() {
                 let log_mode = match edit.update.change {
                     Change::Update {
                         log: LogChange { mode, .. },
@@ -228,7 +247,12 @@ impl<'s> Transaction<'s> {
                     continue;
                 }
                 let name = match possibly_adjust_name_for_prefixes(edit.update.name.as_ref()) {
-                    Some(n) => n,
+                    Some(mut n) => {
+                        if let Some(namespace) = &store.namespace {
+                            n.prefix_namespace(namespace);
+                        }
+                        n
+                    }
                     None => continue,
                 };
                 if let Some(ref mut num_updates) = maybe_updates_for_packed_refs {
@@ -240,6 +264,7 @@ impl<'s> Transaction<'s> {
                             name,
                             ..edit.update.clone()
                         });
+                        edit.packed = true;
                         *num_updates += 1;
                     }
                     continue;
@@ -68,6 +68,23 @@ impl packed::Buffer {
         }
     }
 
+    /// Find the reference with the fully qualified `name` verbatim and return it along with the offset at which its record starts
+    /// within the records of this buffer, i.e. `self.as_ref()`, which allows range checks without parsing the buffer again.
+    ///
+    /// Unlike with [`find()`][packed::Buffer::find()], partial names aren't supported.
+    pub fn find_existing_by_name(&self, name: &FullNameRef) -> Result<(packed::Reference<'_>, usize), existing::Error> {
+        match self.binary_search_by(name.as_bstr()) {
+            Ok(line_start) => Ok((
+                packed::decode::reference::<()>(&self.as_ref()[line_start..])
+                    .map_err(|_| Error::Parse)?
+                    .1,
+                line_start,
+            )),
+            Err((true, _)) => Err(Error::Parse.into()),
+            Err((false, _)) => Err(existing::Error::NotFound),
+        }
+    }
+
     /// Perform a binary search where `Ok(pos)` is the beginning of the line that matches `name` perfectly and `Err(pos)`
     /// is the beginning of the line at which `name` could be inserted to still be in sort order.
     pub(in crate::store_impl::packed) fn binary_search_by(&self, full_name: &BStr) -> Result<usize, (bool, usize)> {
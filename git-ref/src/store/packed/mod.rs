@@ -66,6 +66,15 @@ impl<'a> Reference<'a> {
             |id| ObjectId::from_hex(id).expect("parser validation"),
         )
     }
+
+    /// Return the offset at which the record of this reference starts within the records of `buffer`, i.e. `buffer.as_ref()`,
+    /// or `None` if this reference wasn't obtained from `buffer`.
+    pub fn offset_in_buffer(&self, buffer: &Buffer) -> Option<usize> {
+        let records = buffer.as_ref();
+        (self.target.as_ptr() as usize)
+            .checked_sub(records.as_ptr() as usize)
+            .filter(|offset| *offset < records.len())
+    }
 }
 
 /// An iterator over references in a packed refs file
@@ -156,6 +156,22 @@ mod iter {
                 }
                 Ok(())
             }
+
+            #[test]
+            fn incomplete_last_line_is_skipped() -> crate::Result {
+                let lines = b"0000000000000000000000000000000000000000 134385f6d781b7e97062102c6a483440bfda2a03 committer <committer@example.com> 946771200 +0000	commit (initial): c1\n134385f6d781b7e97062102c6a483440bfda2a03 234385f6d781b7e97062102c6a48";
+                for buf_size in &[1024usize, 256] {
+                    let mut buf = vec![0; *buf_size];
+                    let mut iter = git_ref::file::log::iter::reverse(std::io::Cursor::new(lines), &mut buf)?;
+                    assert_eq!(
+                        iter.next().expect("a complete line")?.message,
+                        "commit (initial): c1",
+                        "the interrupted write at the end is ignored"
+                    );
+                    assert!(iter.next().is_none(), "iterator depleted");
+                }
+                Ok(())
+            }
         }
     }
     mod forward {
@@ -189,5 +205,32 @@ mod iter {
             assert!(iter.next().expect("a second line").is_ok(), "line parses ok");
             assert!(iter.next().is_none(), "iterator exhausted");
         }
+
+        #[test]
+        fn incomplete_last_line_is_skipped() {
+            let log_last_incomplete = "0000000000000000000000000000000000000000 134385f6d781b7e97062102c6a483440bfda2a03 committer <committer@example.com> 946771200 +0000	commit (initial): c1
+134385f6d781b7e97062102c6a483440bfda2a03 234385f6d781b7e97062102c6a48";
+
+            let mut iter = git_ref::file::log::iter::forward(log_last_incomplete.as_bytes());
+            assert!(iter.next().expect("a first line").is_ok(), "line parses ok");
+            assert!(
+                iter.next().is_none(),
+                "the interrupted write at the end is ignored and the iterator is exhausted"
+            );
+        }
+
+        #[test]
+        fn broken_last_line_with_newline_is_an_error() {
+            let log_last_broken = "0000000000000000000000000000000000000000 134385f6d781b7e97062102c6a483440bfda2a03 committer <committer@example.com> 946771200 +0000	commit (initial): c1
+134385f6d781b7e97062102c6a483440bfda2a03 234385f6d781b7e97062102c6a48\n";
+
+            let mut iter = git_ref::file::log::iter::forward(log_last_broken.as_bytes());
+            assert!(iter.next().expect("a first line").is_ok(), "line parses ok");
+            assert!(
+                iter.next().expect("a second line").is_err(),
+                "complete lines are always decoded"
+            );
+            assert!(iter.next().is_none(), "iterator exhausted");
+        }
     }
 }
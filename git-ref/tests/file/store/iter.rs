@@ -224,6 +224,49 @@ mod with_namespace {
         );
         Ok(())
     }
+
+    #[test]
+    fn namespaced_iteration_strips_the_given_namespace_and_ignores_the_one_of_the_store() -> crate::Result {
+        let mut store = store_at("make_namespaced_packed_ref_repository.sh")?;
+        let ns_one = git_ref::namespace::expand("foo")?;
+        let ns_two = git_ref::namespace::expand("bar")?;
+        let names_in = |store: &git_ref::file::Store, namespace: &git_ref::Namespace| -> crate::Result<Vec<BString>> {
+            Ok(store
+                .iter()?
+                .namespaced(namespace)?
+                .map(|r| r.map(|r| r.name.into_inner()))
+                .collect::<Result<_, _>>()?)
+        };
+
+        let expected_in_ns_two = vec![
+            "refs/heads/multi-link-target1",
+            "refs/multi-link",
+            "refs/remotes/origin/multi-link-target3",
+            "refs/tags/multi-link-target2",
+        ];
+        assert_eq!(
+            names_in(&store, &ns_two)?,
+            expected_in_ns_two,
+            "loose and packed references are found"
+        );
+        assert_eq!(
+            names_in(&store, &ns_one)?,
+            ["refs/d1", "refs/remotes/origin/HEAD", "refs/remotes/origin/main"]
+        );
+
+        store.namespace = ns_one.into();
+        assert_eq!(
+            names_in(&store, &ns_two)?,
+            expected_in_ns_two,
+            "the namespace of the store doesn't affect the outcome"
+        );
+
+        assert!(
+            names_in(&store, &git_ref::namespace::expand("missing")?)?.is_empty(),
+            "unknown namespaces have no references"
+        );
+        Ok(())
+    }
 }
 
 #[test]
@@ -299,6 +342,20 @@ fn loose_iter_with_broken_refs() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn should_auto_pack_refs_compares_the_loose_ref_count_with_the_limit() -> crate::Result {
+    let store = store()?;
+    assert_eq!(store.loose_iter()?.count(), 15, "broken references are counted as well");
+
+    assert!(store.should_auto_pack_refs(14), "the limit is exceeded");
+    assert!(
+        !store.should_auto_pack_refs(15),
+        "the limit is reached, but not exceeded"
+    );
+    assert!(!store.should_auto_pack_refs(0), "0 disables the check");
+    Ok(())
+}
+
 #[test]
 fn loose_iter_with_prefix_wont_allow_absolute_paths() -> crate::Result {
     let store = store()?;
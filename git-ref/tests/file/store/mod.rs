@@ -1,3 +1,4 @@
 mod find;
 mod iter;
 mod reflog;
+mod rename;
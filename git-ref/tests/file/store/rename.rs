@@ -0,0 +1,104 @@
+use std::convert::TryInto;
+
+use git_lock::acquire::Fail;
+use git_ref::{file::rename, PartialNameRef};
+
+use crate::file::{store_writable, transaction::prepare_and_commit::committer};
+
+fn options(committer: &git_actor::Signature) -> rename::Options<'_> {
+    rename::Options {
+        committer: committer.to_ref(),
+        message: "Branch: renamed refs/heads/main to refs/heads/renamed".into(),
+        ref_files_lock_fail_mode: Fail::Immediately,
+        packed_refs_lock_fail_mode: Fail::Immediately,
+    }
+}
+
+fn name(name: &str) -> &PartialNameRef {
+    name.try_into().expect("valid name")
+}
+
+fn reflog_len(store: &crate::file::Store, name: &str) -> crate::Result<Option<usize>> {
+    let mut buf = Vec::new();
+    Ok(store.reflog_iter(name, &mut buf)?.map(|log| log.count()))
+}
+
+#[test]
+fn loose_reference_with_reflog() -> crate::Result {
+    let (_keep, store) = store_writable("make_repo_for_reflog.sh")?;
+    let committer = committer();
+    let previous = store.find_loose("main")?;
+    let previous_log_len = reflog_len(&store, "refs/heads/main")?.expect("main has a reflog");
+
+    store.rename(name("main"), name("refs/heads/renamed"), options(&committer))?;
+
+    assert!(store.try_find("main")?.is_none(), "the old reference is gone");
+    assert_eq!(reflog_len(&store, "refs/heads/main")?, None, "…along with its reflog");
+    let renamed = store.find_loose("renamed")?;
+    assert_eq!(renamed.target, previous.target, "the value is retained");
+
+    let mut buf = [0u8; 256];
+    let last_line = store
+        .reflog_iter_rev("refs/heads/renamed", &mut buf)?
+        .expect("reflog was copied")
+        .next()
+        .expect("at least one line")?;
+    assert_eq!(
+        last_line.message,
+        "Branch: renamed refs/heads/main to refs/heads/renamed"
+    );
+    assert_eq!(
+        reflog_len(&store, "refs/heads/renamed")?,
+        Some(previous_log_len + 1),
+        "the reflog is copied and a line is added for the rename"
+    );
+    Ok(())
+}
+
+#[test]
+fn packed_reference() -> crate::Result {
+    let (_keep, store) = store_writable("make_packed_ref_repository.sh")?;
+    let committer = committer();
+    let previous = store.find("refs/heads/d1")?;
+
+    store.rename(name("refs/heads/d1"), name("refs/heads/a/b"), options(&committer))?;
+
+    assert!(
+        store.try_find("refs/heads/d1")?.is_none(),
+        "the old packed reference is gone"
+    );
+    assert_eq!(store.find("refs/heads/a/b")?.target, previous.target);
+    Ok(())
+}
+
+#[test]
+fn to_an_existing_reference_fails() -> crate::Result {
+    let (_keep, store) = store_writable("make_packed_ref_repository.sh")?;
+    let committer = committer();
+    let err = store
+        .rename(name("refs/heads/d1"), name("refs/heads/main"), options(&committer))
+        .unwrap_err();
+    assert!(
+        matches!(&err, rename::Error::AlreadyExists { name } if name.as_bstr() == "refs/heads/main"),
+        "{:?}",
+        err
+    );
+    assert!(store.try_find("refs/heads/d1")?.is_some(), "nothing was changed");
+    Ok(())
+}
+
+#[test]
+fn a_non_existing_reference_fails() -> crate::Result {
+    let (_keep, store) = store_writable("make_repo_for_reflog.sh")?;
+    let committer = committer();
+    let err = store
+        .rename(name("does-not-exist"), name("refs/heads/new"), options(&committer))
+        .unwrap_err();
+    assert!(
+        matches!(&err, rename::Error::NotFound { name } if name == "does-not-exist"),
+        "{:?}",
+        err
+    );
+    assert!(store.try_find("refs/heads/new")?.is_none());
+    Ok(())
+}
@@ -44,4 +44,6 @@ pub(crate) mod prepare_and_commit {
     mod create_or_update;
 
     mod delete;
+
+    mod set_symbolic_target;
 }
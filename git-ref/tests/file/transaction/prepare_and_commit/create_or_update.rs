@@ -281,6 +281,111 @@ fn namespaced_updates_or_deletions_are_transparent_and_not_observable() -> crate
     Ok(())
 }
 
+#[test]
+fn namespaced_references_are_packed_below_the_namespace() -> crate::Result {
+    let (_keep, mut store) = empty_store()?;
+    store.namespace = git_ref::namespace::expand("foo")?.into();
+    let id = hex_to_id("28ce6a8b26aa170e1de65536fe8abe1832bd3242");
+    store
+        .transaction()
+        .packed_refs(PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReference(
+            Box::new(|_, _| Ok(Some(git_object::Kind::Commit))),
+        ))
+        .prepare(
+            Some(RefEdit {
+                change: Change::Update {
+                    log: LogChange::default(),
+                    new: Target::Peeled(id),
+                    expected: PreviousValue::MustNotExist,
+                },
+                name: "refs/heads/main".try_into()?,
+                deref: false,
+            }),
+            Fail::Immediately,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+
+    let packed = store.open_packed_buffer()?.expect("packed refs were written");
+    assert_eq!(
+        packed.find("refs/namespaces/foo/refs/heads/main")?.target(),
+        id,
+        "the packed reference is placed into the namespace"
+    );
+    assert!(
+        packed.try_find("refs/heads/main")?.is_none(),
+        "nothing is packed outside of the namespace"
+    );
+    assert_eq!(
+        store.find("main")?.target.try_id(),
+        Some(id.as_ref()),
+        "it's found within the namespace"
+    );
+    Ok(())
+}
+
+#[test]
+fn namespaced_references_are_written_below_the_namespace_and_read_back_without_it() -> crate::Result {
+    let (dir, mut store) = empty_store()?;
+    store.namespace = git_ref::namespace::expand("foo")?.into();
+    let id = hex_to_id("28ce6a8b26aa170e1de65536fe8abe1832bd3242");
+    store
+        .transaction()
+        .prepare(
+            vec![
+                RefEdit {
+                    change: Change::Update {
+                        log: LogChange::default(),
+                        new: Target::Peeled(id),
+                        expected: PreviousValue::MustNotExist,
+                    },
+                    name: "refs/heads/main".try_into()?,
+                    deref: false,
+                },
+                RefEdit {
+                    change: Change::Update {
+                        log: LogChange::default(),
+                        new: Target::Symbolic("refs/heads/main".try_into()?),
+                        expected: PreviousValue::MustNotExist,
+                    },
+                    name: "HEAD".try_into()?,
+                    deref: false,
+                },
+            ],
+            Fail::Immediately,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+
+    let ns_dir = dir.path().join("refs").join("namespaces").join("foo");
+    assert!(ns_dir.join("refs").join("heads").join("main").is_file());
+    assert_eq!(
+        std::fs::read(ns_dir.join("HEAD"))?.as_bstr(),
+        "ref: refs/namespaces/foo/refs/heads/main",
+        "symbolic targets are namespaced as well"
+    );
+    assert!(
+        !dir.path().join("HEAD").exists() && !dir.path().join("refs").join("heads").exists(),
+        "nothing is written outside of the namespace"
+    );
+
+    let head = store.find_loose("HEAD")?;
+    assert_eq!(head.name.as_bstr(), "HEAD", "the namespace is stripped when reading");
+    assert_eq!(
+        head.target.to_ref().try_name().map(|n| n.as_bstr()),
+        Some("refs/heads/main".into())
+    );
+    assert_eq!(store.find("main")?.target.try_id(), Some(id.as_ref()));
+
+    store.namespace = None;
+    assert_eq!(
+        store.find("refs/namespaces/foo/HEAD")?.name.as_bstr(),
+        "refs/namespaces/foo/HEAD",
+        "without namespace, the namespaced references are visible by their full name"
+    );
+    Ok(())
+}
+
 #[test]
 fn reference_with_must_exist_constraint_must_exist_already_with_any_value() -> crate::Result {
     let (_keep, store) = store_writable("make_repo_for_reflog.sh")?;
@@ -405,6 +510,66 @@ fn cancellation_after_preparation_leaves_no_change() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn dry_run_after_preparation_returns_the_edits_of_a_commit_without_changes() -> crate::Result {
+    let (dir, store) = store_writable("make_repo_for_reflog.sh")?;
+    let edits = || {
+        Some(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: "dry run".into(),
+                },
+                new: Target::Peeled(hex_to_id("28ce6a8b26aa170e1de65536fe8abe1832bd3242")),
+                expected: PreviousValue::Any,
+            },
+            name: "HEAD".try_into().expect("valid"),
+            deref: true,
+        })
+    };
+    let head_before = store.find_loose("HEAD")?;
+    let referent = head_before.target.to_ref().try_name().expect("symbolic").to_owned();
+    let referent_before = store.find_loose(referent.as_ref())?;
+    let reflog_before = reflog_lines(&store, "HEAD")?;
+
+    let dry_run_edits = store
+        .transaction()
+        .prepare(edits(), Fail::Immediately, Fail::Immediately)?
+        .commit_dry_run();
+    assert_eq!(
+        dry_run_edits.len(),
+        2,
+        "the symbolic HEAD is split into an edit for its referent"
+    );
+    assert_eq!(store.find_loose("HEAD")?, head_before, "HEAD is unchanged");
+    assert_eq!(
+        store.find_loose(referent.as_ref())?,
+        referent_before,
+        "the referent is unchanged"
+    );
+    assert_eq!(reflog_lines(&store, "HEAD")?, reflog_before, "no reflog was written");
+    assert!(
+        !dir.path().join(".git").join("HEAD.lock").exists(),
+        "no lock is left behind"
+    );
+
+    let edits = store
+        .transaction()
+        .prepare(edits(), Fail::Immediately, Fail::Immediately)?
+        .commit(committer().to_ref())?;
+    assert_eq!(
+        dry_run_edits, edits,
+        "dry-runs yield the same edits as an actual commit"
+    );
+    assert_ne!(
+        store.find_loose(referent.as_ref())?,
+        referent_before,
+        "now the change was made"
+    );
+    Ok(())
+}
+
 #[test]
 fn symbolic_head_missing_referent_then_update_referent() -> crate::Result {
     for reflog_writemode in &[WriteReflog::Normal, WriteReflog::Disable, WriteReflog::Always] {
@@ -730,6 +895,136 @@ fn packed_refs_creation_with_packed_refs_mode_prune_removes_original_loose_refs(
     Ok(())
 }
 
+#[test]
+fn packed_refs_creation_can_be_recorded_in_the_reflog() -> crate::Result {
+    let (_keep, store) = store_writable("make_repo_for_reflog.sh")?;
+    let main = store.find_loose("main")?;
+    let previous_reflog_count = reflog_lines(&store, "refs/heads/main")?.len();
+    let odb = git_odb::at(store.git_dir().join("objects"))?;
+
+    let pack_main = |store: &git_ref::file::Store, record_in_reflog: bool| -> crate::Result {
+        let odb = odb.clone();
+        store
+            .transaction()
+            .packed_refs(PackedRefs::DeletionsAndNonSymbolicUpdates(Box::new(move |oid, buf| {
+                odb.try_find(oid, buf)
+                    .map(|obj| obj.map(|obj| obj.kind))
+                    .map_err(|err| Box::new(err) as Box<dyn std::error::Error + Send + Sync>)
+            })))
+            .record_packed_updates_in_reflog(record_in_reflog)
+            .prepare(
+                Some(RefEdit {
+                    change: Change::Update {
+                        log: LogChange {
+                            message: "pack-refs".into(),
+                            ..Default::default()
+                        },
+                        expected: PreviousValue::MustExistAndMatch(main.target.clone()),
+                        new: main.target.clone(),
+                    },
+                    name: main.name.clone(),
+                    deref: false,
+                }),
+                Fail::Immediately,
+                Fail::Immediately,
+            )?
+            .commit(committer().to_ref())?;
+        Ok(())
+    };
+
+    pack_main(&store, false)?;
+    assert_eq!(
+        reflog_lines(&store, "refs/heads/main")?.len(),
+        previous_reflog_count,
+        "by default, packing without change isn't recorded"
+    );
+
+    pack_main(&store, true)?;
+    let lines = reflog_lines(&store, "refs/heads/main")?;
+    assert_eq!(
+        lines.len(),
+        previous_reflog_count + 1,
+        "packing is now a distinct event"
+    );
+    let last = lines.last().expect("present");
+    assert_eq!(last.message, "pack-refs");
+    assert_eq!(last.previous_oid, last.new_oid, "the value didn't change");
+    assert_eq!(
+        store.open_packed_buffer()?.expect("written").find("main")?.target(),
+        main.target.try_id().expect("peeled")
+    );
+    Ok(())
+}
+
+#[test]
+fn packed_refs_recording_in_reflog_ignores_refs_that_are_not_packed() -> crate::Result {
+    let (_keep, store) = store_writable("make_repo_for_reflog.sh")?;
+    let main = store.find_loose("main")?;
+    let previous_reflog_count = reflog_lines(&store, "refs/heads/main")?.len();
+    let odb = git_odb::at(store.git_dir().join("objects"))?;
+
+    let edit = |name: &str, expected: PreviousValue| -> crate::Result<RefEdit> {
+        Ok(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    message: "pack-refs".into(),
+                    force_create_reflog: true,
+                    ..Default::default()
+                },
+                expected,
+                new: main.target.clone(),
+            },
+            name: name.try_into()?,
+            deref: false,
+        })
+    };
+    store
+        .transaction()
+        .prepare(
+            Some(edit("ORIG_HEAD", PreviousValue::MustNotExist)?),
+            Fail::Immediately,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+    assert_eq!(reflog_lines(&store, "ORIG_HEAD")?.len(), 1, "the creation is logged");
+
+    store
+        .transaction()
+        .packed_refs(PackedRefs::DeletionsAndNonSymbolicUpdates(Box::new(move |oid, buf| {
+            odb.try_find(oid, buf)
+                .map(|obj| obj.map(|obj| obj.kind))
+                .map_err(|err| Box::new(err) as Box<dyn std::error::Error + Send + Sync>)
+        })))
+        .record_packed_updates_in_reflog(true)
+        .prepare(
+            vec![
+                edit("ORIG_HEAD", PreviousValue::MustExistAndMatch(main.target.clone()))?,
+                edit(
+                    main.name.as_bstr().to_str()?,
+                    PreviousValue::MustExistAndMatch(main.target.clone()),
+                )?,
+            ],
+            Fail::Immediately,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+
+    assert_eq!(
+        reflog_lines(&store, "refs/heads/main")?.len(),
+        previous_reflog_count + 1,
+        "packing is recorded"
+    );
+    assert_eq!(
+        reflog_lines(&store, "ORIG_HEAD")?.len(),
+        1,
+        "pseudo-refs are never packed, so their unchanged update isn't recorded"
+    );
+    let packed = store.open_packed_buffer()?.expect("written");
+    assert!(packed.try_find("main")?.is_some());
+    assert!(packed.try_find("ORIG_HEAD")?.is_none());
+    Ok(())
+}
+
 #[test]
 fn packed_refs_creation_with_packed_refs_mode_leave_keeps_original_loose_refs() -> crate::Result {
     let (_keep, store) = store_writable("make_packed_ref_repository_for_overlay.sh")?;
@@ -2,8 +2,8 @@ use std::convert::TryInto;
 
 use git_lock::acquire::Fail;
 use git_ref::{
-    file::ReferenceExt,
-    transaction::{Change, PreviousValue, RefEdit, RefLog},
+    file::{transaction::PackedRefs, ReferenceExt},
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
     Reference, Target,
 };
 use git_testtools::hex_to_id;
@@ -380,6 +380,66 @@ fn packed_refs_are_consulted_when_determining_previous_value_of_ref_to_be_delete
     Ok(())
 }
 
+#[test]
+fn namespaced_packed_refs_are_deleted_within_the_namespace_only() -> crate::Result {
+    let (_keep, mut store) = empty_store()?;
+    let id = hex_to_id("134385f6d781b7e97062102c6a483440bfda2a03");
+    store
+        .transaction()
+        .packed_refs(PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReference(
+            Box::new(|_, _| Ok(Some(git_object::Kind::Commit))),
+        ))
+        .prepare(
+            ["refs/heads/main", "refs/namespaces/foo/refs/heads/main"]
+                .iter()
+                .map(|name| -> crate::Result<_> {
+                    Ok(RefEdit {
+                        change: Change::Update {
+                            log: LogChange::default(),
+                            new: Target::Peeled(id),
+                            expected: PreviousValue::MustNotExist,
+                        },
+                        name: (*name).try_into()?,
+                        deref: false,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            Fail::Immediately,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+
+    store.namespace = git_ref::namespace::expand("foo")?.into();
+    let edits = store
+        .transaction()
+        .prepare(
+            Some(RefEdit {
+                change: Change::Delete {
+                    expected: PreviousValue::MustExistAndMatch(Target::Peeled(id)),
+                    log: RefLog::AndReference,
+                },
+                name: "refs/heads/main".try_into()?,
+                deref: false,
+            }),
+            Fail::Immediately,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+    assert_eq!(edits.len(), 1);
+
+    let packed = store.open_packed_buffer()?.expect("packed refs remain");
+    assert!(
+        packed.try_find("refs/namespaces/foo/refs/heads/main")?.is_none(),
+        "the namespaced reference was deleted"
+    );
+    assert_eq!(
+        packed.find("refs/heads/main")?.target(),
+        id,
+        "the reference outside of the namespace is untouched"
+    );
+    Ok(())
+}
+
 #[test]
 fn a_loose_ref_with_old_value_check_and_outdated_packed_refs_value_deletes_both_refs() -> crate::Result {
     let (_keep, store) = store_writable("make_packed_ref_repository_for_overlay.sh")?;
@@ -0,0 +1,114 @@
+use std::convert::TryInto;
+
+use git_lock::acquire::Fail;
+use git_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    Target,
+};
+use git_testtools::hex_to_id;
+
+use crate::file::{
+    store_writable,
+    transaction::prepare_and_commit::{committer, empty_store, reflog_lines},
+};
+
+#[test]
+fn create_new_symbolic_ref() -> crate::Result {
+    let (_keep, store) = empty_store()?;
+    let edits = store
+        .transaction()
+        .set_symbolic_target(
+            "refs/remotes/origin/HEAD".try_into()?,
+            "refs/remotes/origin/main".try_into()?,
+            PreviousValue::MustNotExist,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+    assert_eq!(edits.len(), 1, "there is no split as the referent doesn't matter");
+
+    let head = store.find_loose("refs/remotes/origin/HEAD")?;
+    assert_eq!(head.kind(), git_ref::Kind::Symbolic);
+    assert_eq!(
+        head.target.to_ref().try_name().map(|n| n.as_bstr()),
+        Some("refs/remotes/origin/main".into())
+    );
+    assert!(!head.log_exists(&store), "symbolic refs don't get a reflog");
+    assert!(
+        store.try_find_loose("refs/remotes/origin/main")?.is_none(),
+        "the referent isn't created"
+    );
+    Ok(())
+}
+
+#[test]
+fn overwrite_peeled_ref_with_symbolic_ref() -> crate::Result {
+    let (_keep, store) = store_writable("make_repo_for_reflog.sh")?;
+    let previous = store.find_loose("refs/heads/main")?;
+    let reflog_before = reflog_lines(&store, "refs/heads/main")?;
+
+    store
+        .transaction()
+        .set_symbolic_target(
+            "refs/heads/main".try_into()?,
+            "refs/heads/other".try_into()?,
+            PreviousValue::MustExistAndMatch(previous.target),
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+
+    let main = store.find_loose("refs/heads/main")?;
+    assert_eq!(main.kind(), git_ref::Kind::Symbolic, "the peeled ref is now symbolic");
+    assert_eq!(
+        main.target.to_ref().try_name().map(|n| n.as_bstr()),
+        Some("refs/heads/other".into())
+    );
+    assert_eq!(
+        reflog_lines(&store, "refs/heads/main")?,
+        reflog_before,
+        "the reflog isn't changed for symbolic targets"
+    );
+    Ok(())
+}
+
+#[test]
+fn overwrite_symbolic_ref_with_peeled_ref() -> crate::Result {
+    let (_keep, store) = store_writable("make_repo_for_reflog.sh")?;
+    let head = store.find_loose("HEAD")?;
+    assert_eq!(head.kind(), git_ref::Kind::Symbolic);
+    let num_reflog_lines = reflog_lines(&store, "HEAD")?.len();
+
+    let new_oid = hex_to_id("28ce6a8b26aa170e1de65536fe8abe1832bd3242");
+    store
+        .transaction()
+        .prepare(
+            Some(RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        mode: RefLog::AndReference,
+                        force_create_reflog: false,
+                        message: "detach".into(),
+                    },
+                    expected: PreviousValue::MustExistAndMatch(head.target),
+                    new: Target::Peeled(new_oid),
+                },
+                name: "HEAD".try_into()?,
+                deref: false,
+            }),
+            Fail::Immediately,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+
+    let head = store.find_loose("HEAD")?;
+    assert_eq!(head.kind(), git_ref::Kind::Peeled, "the symbolic ref is now peeled");
+    assert_eq!(head.target.to_ref().try_id(), Some(new_oid.as_ref()));
+    let reflog = reflog_lines(&store, "HEAD")?;
+    assert_eq!(reflog.len(), num_reflog_lines + 1, "peeled values are logged");
+    assert_eq!(reflog.last().expect("present").new_oid, new_oid);
+    assert_eq!(
+        store.find_loose("refs/heads/main")?.kind(),
+        git_ref::Kind::Peeled,
+        "the former referent is untouched"
+    );
+    Ok(())
+}
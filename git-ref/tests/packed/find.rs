@@ -194,3 +194,53 @@ fn find_speed() -> crate::Result {
     );
     Ok(())
 }
+
+#[test]
+fn find_existing_by_name_provides_the_offset_of_the_record() -> crate::Result {
+    let packed_refs = b"# pack-refs with: peeled fully-peeled sorted
+916840c0e2f67d370291042cb5274a597f4fa9bc refs/tags/TEST-0.0.1
+c4cebba92af964f2d126be90b8a6298c4cf84d45 refs/tags/git-actor-v0.1.0
+^13da90b54699a6b500ec5cd7d175f2cd5a1bed06
+0b92c8a256ae06c189e3b9c30b646d62ac8f7d10 refs/tags/git-actor-v0.1.1\n";
+    let (_keep, path) = write_packed_refs_with(packed_refs)?;
+    let buf = packed::Buffer::open(path, 1024)?;
+
+    let mut expected_offset = 0;
+    for (name, record_len) in [
+        ("refs/tags/TEST-0.0.1", 62),
+        ("refs/tags/git-actor-v0.1.0", 68 + 42),
+        ("refs/tags/git-actor-v0.1.1", 68),
+    ] {
+        let (reference, offset) = buf.find_existing_by_name(name.try_into()?)?;
+        assert_eq!(reference.name.as_bstr(), name);
+        assert_eq!(offset, expected_offset, "{} is at the expected position", name);
+        assert_eq!(reference.offset_in_buffer(&buf), Some(offset));
+        assert_eq!(
+            &AsRef::<[u8]>::as_ref(&buf)[offset..][..40],
+            reference.target.as_ref(),
+            "the offset points to the beginning of the record"
+        );
+        expected_offset += record_len;
+    }
+
+    assert!(matches!(
+        buf.find_existing_by_name("refs/tags/does-not-exist".try_into()?),
+        Err(packed::find::existing::Error::NotFound)
+    ));
+    assert!(
+        matches!(
+            buf.find_existing_by_name("refs/tags/TEST".try_into()?),
+            Err(packed::find::existing::Error::NotFound)
+        ),
+        "partial names are not supported"
+    );
+
+    let other = packed::Buffer::open(fixture_path("packed-refs").join("triggers-out-of-bounds"), 32)?;
+    let (reference, _) = buf.find_existing_by_name("refs/tags/TEST-0.0.1".try_into()?)?;
+    assert_eq!(
+        reference.offset_in_buffer(&other),
+        None,
+        "references from other buffers have no offset"
+    );
+    Ok(())
+}
@@ -22,6 +22,8 @@ pub enum Push<'a> {
         allow_non_fast_forward: bool,
     },
     /// Delete the destination ref or glob pattern, with only a single `*` allowed.
+    ///
+    /// Forcing a deletion, as in `+:refs/heads/gone`, is the same as a plain deletion.
     Delete {
         /// The reference or pattern to delete on the remote.
         ref_or_pattern: &'a BStr,
@@ -36,4 +36,4 @@ pub mod match_group;
 pub use match_group::types::MatchGroup;
 
 mod types;
-pub use types::Instruction;
+pub use types::{Instruction, Mode};
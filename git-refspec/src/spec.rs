@@ -90,6 +90,16 @@ mod impls {
 
 /// Access
 impl<'a> RefSpecRef<'a> {
+    /// Return the mode of the spec, which tells if it's forced with a leading `+` or negative with a leading `^`.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Return the operation the spec was parsed for, which determines which side is remote or local.
+    pub fn operation(&self) -> Operation {
+        self.op
+    }
+
     /// Return the left-hand side of the spec, typically the source.
     /// It takes many different forms so don't rely on this being a ref name.
     ///
@@ -133,6 +143,23 @@ impl<'a> RefSpecRef<'a> {
         (!prefix.contains(&b'*')).then(|| prefix)
     }
 
+    /// Return true if both sides of this spec are under `refs/tags/`, like `refs/tags/*:refs/tags/*` which is what `--tags` implies.
+    ///
+    /// Such a spec fetches tags explicitly, which is independent of the automatic tag-following during negotiation
+    /// that only fetches tags pointing to objects that are fetched anyway. If such a spec is present, all matching
+    /// tags are fetched already and automatic tag-following can be skipped.
+    pub fn is_tag_spec(&self) -> bool {
+        let is_tag = |name: Option<&BStr>| name.map_or(false, |name| name.starts_with(b"refs/tags/"));
+        is_tag(self.src) && is_tag(self.dst)
+    }
+
+    /// Return true if this is a push spec that deletes its destination on the remote, like `:refs/heads/gone`.
+    ///
+    /// A leading `+` as in `+:refs/heads/gone` is accepted but has no effect, as deletions don't need to be forced.
+    pub fn is_delete(&self) -> bool {
+        matches!(self.op, Operation::Push) && self.src.is_none() && self.dst.is_some()
+    }
+
     /// Transform the state of the refspec into an instruction making clear what to do with it.
     pub fn instruction(&self) -> Instruction<'a> {
         match self.op {
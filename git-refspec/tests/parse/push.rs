@@ -129,4 +129,30 @@ fn colon_alone_is_for_pushing_matching_refs() {
 fn delete() {
     assert_parse(":a", Instruction::Push(Push::Delete { ref_or_pattern: b("a") }));
     assert_parse("+:a", Instruction::Push(Push::Delete { ref_or_pattern: b("a") }));
+    assert_parse(
+        ":refs/heads/gone",
+        Instruction::Push(Push::Delete {
+            ref_or_pattern: b("refs/heads/gone"),
+        }),
+    );
+}
+
+#[test]
+fn source_only_pushes_to_the_same_name_and_may_be_forced() {
+    assert_parse(
+        "refs/heads/main",
+        Instruction::Push(Push::Matching {
+            src: b("refs/heads/main"),
+            dst: b("refs/heads/main"),
+            allow_non_fast_forward: false,
+        }),
+    );
+    assert_parse(
+        "+refs/heads/main",
+        Instruction::Push(Push::Matching {
+            src: b("refs/heads/main"),
+            dst: b("refs/heads/main"),
+            allow_non_fast_forward: true,
+        }),
+    );
 }
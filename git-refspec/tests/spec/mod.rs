@@ -1,3 +1,82 @@
+mod is_tag_spec {
+    use git_refspec::parse::Operation;
+
+    fn is_tag_spec(spec: &str) -> bool {
+        git_refspec::parse(spec.into(), Operation::Fetch).unwrap().is_tag_spec()
+    }
+
+    #[test]
+    fn both_sides_under_refs_tags() {
+        assert!(is_tag_spec("refs/tags/*:refs/tags/*"));
+        assert!(is_tag_spec("+refs/tags/v1.0:refs/tags/v1.0"));
+    }
+
+    #[test]
+    fn heads_are_no_tag_spec() {
+        assert!(!is_tag_spec("refs/heads/*:refs/remotes/origin/*"));
+    }
+
+    #[test]
+    fn mixed_sides_are_no_tag_spec() {
+        assert!(!is_tag_spec("refs/tags/*:refs/remotes/origin/tags/*"));
+        assert!(!is_tag_spec("refs/heads/*:refs/tags/*"));
+        assert!(!is_tag_spec("refs/tags/v1.0"), "both sides must be present");
+    }
+}
+
+mod access {
+    use git_refspec::{parse::Operation, Mode};
+
+    #[test]
+    fn all_parts_of_a_parsed_spec_are_accessible() {
+        let spec = git_refspec::parse("+refs/heads/*:refs/remotes/origin/*".into(), Operation::Fetch).unwrap();
+        assert_eq!(spec.mode(), Mode::Force);
+        assert_eq!(spec.operation(), Operation::Fetch);
+        assert_eq!(spec.source().expect("present"), "refs/heads/*");
+        assert_eq!(spec.destination().expect("present"), "refs/remotes/origin/*");
+        assert_eq!(spec.remote(), spec.source());
+        assert_eq!(spec.local(), spec.destination());
+    }
+
+    #[test]
+    fn modes_reflect_the_prefix_of_the_spec() {
+        let mode = |spec: &str| git_refspec::parse(spec.into(), Operation::Fetch).unwrap().mode();
+        assert_eq!(mode("refs/heads/main"), Mode::Normal);
+        assert_eq!(mode("^refs/heads/main"), Mode::Negative);
+    }
+}
+
+mod is_delete {
+    use git_refspec::parse::Operation;
+
+    fn is_delete(spec: &str, op: Operation) -> bool {
+        git_refspec::parse(spec.into(), op).unwrap().is_delete()
+    }
+
+    #[test]
+    fn push_specs_without_source_delete_their_destination() {
+        assert!(is_delete(":refs/heads/gone", Operation::Push));
+        assert!(
+            is_delete("+:refs/heads/gone", Operation::Push),
+            "forcing a deletion is allowed and still a deletion"
+        );
+    }
+
+    #[test]
+    fn push_specs_with_source_are_no_deletion() {
+        assert!(!is_delete("+refs/heads/main", Operation::Push));
+        assert!(!is_delete("refs/heads/main", Operation::Push));
+        assert!(!is_delete("refs/heads/main:refs/heads/other", Operation::Push));
+        assert!(!is_delete(":", Operation::Push), "this pushes all matching branches");
+    }
+
+    #[test]
+    fn fetch_specs_are_never_a_deletion() {
+        assert!(!is_delete("refs/heads/main", Operation::Fetch));
+        assert!(!is_delete("refs/heads/*:refs/remotes/origin/*", Operation::Fetch));
+    }
+}
+
 mod prefix {
     use git_refspec::{parse::Operation, RefSpec};
 
@@ -0,0 +1,141 @@
+use crate::{
+    bstr::BString,
+    clone::{Prepare, PrepareCheckout},
+    Repository,
+};
+
+/// The error returned by [`Prepare::fetch_only()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Connect(#[from] crate::remote::connect::Error),
+    #[error(transparent)]
+    PrepareFetch(Box<crate::remote::fetch::prepare::Error>),
+    #[error(transparent)]
+    Fetch(#[from] crate::remote::fetch::Error),
+    #[error(transparent)]
+    RemoteConfiguration(#[from] crate::remote::init::Error),
+    #[error("Default remote configured at `clone.defaultRemoteName` is invalid")]
+    RemoteName(#[from] crate::remote::name::Error),
+    #[error("Failed to load repo-local git configuration before writing")]
+    LoadConfig(#[from] git_config::file::init::from_paths::Error),
+    #[error("Failed to store configured remote in memory")]
+    SaveConfig(#[source] Box<crate::remote::save::AsError>),
+    #[error("Failed to write repository configuration to disk")]
+    SaveConfigIo(#[from] std::io::Error),
+    #[error("The remote HEAD points to the invalid reference name {name:?}")]
+    InvalidHeadRef {
+        name: BString,
+        source: git_validate::refname::Error,
+    },
+    #[error("Failed to update HEAD and the local branch it points to")]
+    HeadUpdate(#[from] crate::reference::edit::Error),
+}
+
+impl From<crate::remote::fetch::prepare::Error> for Error {
+    fn from(err: crate::remote::fetch::prepare::Error) -> Self {
+        Error::PrepareFetch(Box::new(err))
+    }
+}
+
+impl From<crate::remote::save::AsError> for Error {
+    fn from(err: crate::remote::save::AsError) -> Self {
+        Error::SaveConfig(Box::new(err))
+    }
+}
+
+/// Modification
+impl Prepare {
+    /// Fetch a pack and update local branches according to refspecs, providing `progress` and checking `should_interrupt` to stop
+    /// the operation.
+    /// On success, the persisted repository is returned, and this method must not be called again to avoid a **panic**.
+    /// On error, the method may be called again to retry as often as needed.
+    ///
+    /// If the remote advertises its `HEAD`, our `HEAD` will point to the same branch which is created locally, along with
+    /// configuration to track its remote counterpart, or it will be detached if the remote `HEAD` is detached as well.
+    ///
+    /// Note that all data we created will be removed once this instance drops if the operation wasn't successful.
+    pub fn fetch_only(
+        &mut self,
+        progress: impl crate::Progress,
+        should_interrupt: &std::sync::atomic::AtomicBool,
+    ) -> Result<(Repository, crate::remote::fetch::Outcome), Error> {
+        let repo = self
+            .repo
+            .as_mut()
+            .expect("user error: multiple calls are allowed only until it succeeds");
+
+        let remote_name = match self.remote_name.as_deref() {
+            Some(name) => name.to_owned(),
+            None => repo
+                .config
+                .resolved
+                .string("clone", None, "defaultRemoteName")
+                .map(|n| crate::remote::name::validated(n.to_string()))
+                .unwrap_or_else(|| Ok("origin".into()))?,
+        };
+
+        let mut remote = repo
+            .remote_at(self.url.clone())?
+            .with_refspec(
+                format!("+refs/heads/*:refs/remotes/{}/*", remote_name).as_str(),
+                crate::remote::Direction::Fetch,
+            )
+            .expect("valid static spec");
+        if let Some(f) = self.configure_remote.as_mut() {
+            remote = f(remote)?;
+        }
+
+        let mut metadata = git_config::file::Metadata::from(git_config::Source::Local);
+        let config_path = repo.git_dir().join("config");
+        metadata.path = Some(config_path.clone());
+        let mut config =
+            git_config::File::from_paths_metadata(Some(metadata), Default::default())?.expect("one file to load");
+        remote.save_as_to(remote_name.clone(), &mut config)?;
+        std::fs::write(&config_path, config.to_bstring())?;
+
+        let mut fetch_options = self.fetch_options.clone();
+        fetch_options.extra_ref_prefixes.push("HEAD".into());
+        let outcome = remote
+            .connect(crate::remote::Direction::Fetch, progress)?
+            .prepare_fetch(fetch_options)?
+            .receive(should_interrupt)?;
+
+        let reflog_message = format!("clone: from {}", self.url.to_bstring());
+        if util::update_head(
+            repo,
+            &outcome.ref_map.remote_refs,
+            reflog_message.into(),
+            &remote_name,
+            &mut config,
+        )? {
+            std::fs::write(config_path, config.to_bstring())?;
+        }
+
+        let repo_config = git_features::threading::OwnShared::make_mut(&mut repo.config.resolved);
+        let ids_to_remove: Vec<_> = repo_config
+            .sections_and_ids()
+            .filter(|(s, _)| s.meta().source == git_config::Source::Local)
+            .map(|(_, id)| id)
+            .collect();
+        for id in ids_to_remove {
+            repo_config.remove_section_by_id(id);
+        }
+        repo_config.append(config);
+
+        Ok((self.repo.take().expect("still present"), outcome))
+    }
+
+    /// Similar to [`fetch_only()`][Self::fetch_only()], but passes ownership to a utility type to configure a checkout operation.
+    pub fn fetch_then_checkout(
+        &mut self,
+        progress: impl crate::Progress,
+        should_interrupt: &std::sync::atomic::AtomicBool,
+    ) -> Result<(PrepareCheckout, crate::remote::fetch::Outcome), Error> {
+        let (repo, fetch_outcome) = self.fetch_only(progress, should_interrupt)?;
+        Ok((PrepareCheckout { repo: repo.into() }, fetch_outcome))
+    }
+}
+
+mod util;
@@ -0,0 +1,96 @@
+use std::convert::TryInto;
+
+use git_ref::{
+    transaction::{LogChange, PreviousValue, RefEdit, RefLog},
+    FullName, Target,
+};
+
+use super::Error;
+use crate::{bstr::BString, Repository};
+
+/// Make `HEAD` of `repo` match the `HEAD` found in `remote_refs`, creating the branch it points to and configuring it to
+/// track the branch of the same name on the remote called `remote_name` in `config`.
+///
+/// Returns `true` if `config` was changed.
+pub fn update_head(
+    repo: &Repository,
+    remote_refs: &[git_protocol::fetch::Ref],
+    reflog_message: BString,
+    remote_name: &str,
+    config: &mut git_config::File<'static>,
+) -> Result<bool, Error> {
+    let (head_id, head_referent) = match remote_refs.iter().find_map(|r| match r {
+        git_protocol::fetch::Ref::Symbolic {
+            full_ref_name,
+            target,
+            object,
+        } if full_ref_name == "HEAD" => Some((*object, Some(target))),
+        git_protocol::fetch::Ref::Direct { full_ref_name, object } if full_ref_name == "HEAD" => Some((*object, None)),
+        _ => None,
+    }) {
+        Some(t) => t,
+        None => return Ok(false),
+    };
+    let head: FullName = "HEAD".try_into().expect("valid");
+    let log = LogChange {
+        mode: RefLog::AndReference,
+        force_create_reflog: false,
+        message: reflog_message,
+    };
+
+    match head_referent {
+        Some(referent) => {
+            let referent: FullName = referent.clone().try_into().map_err(|err| Error::InvalidHeadRef {
+                name: referent.clone(),
+                source: err,
+            })?;
+            repo.edit_references([
+                RefEdit {
+                    change: git_ref::transaction::Change::Update {
+                        log: log.clone(),
+                        expected: PreviousValue::Any,
+                        new: Target::Peeled(head_id),
+                    },
+                    name: referent.clone(),
+                    deref: false,
+                },
+                RefEdit {
+                    change: git_ref::transaction::Change::Update {
+                        log,
+                        expected: PreviousValue::Any,
+                        new: Target::Symbolic(referent.clone()),
+                    },
+                    name: head,
+                    deref: false,
+                },
+            ])?;
+
+            let short_name = match referent.as_bstr().strip_prefix(b"refs/heads/") {
+                Some(name) => name,
+                None => return Ok(false),
+            };
+            let short_name = match std::str::from_utf8(short_name) {
+                Ok(name) => name,
+                Err(_) => return Ok(false),
+            };
+            let mut section = config
+                .section_mut_or_create_new("branch", Some(short_name))
+                .expect("valid ref names are valid subsection names");
+            section.push("remote".try_into().expect("valid"), Some(remote_name.into()));
+            section.push("merge".try_into().expect("valid"), Some(referent.as_bstr()));
+            Ok(true)
+        }
+        None => {
+            repo.edit_reference(RefEdit {
+                change: git_ref::transaction::Change::Update {
+                    log,
+                    expected: PreviousValue::Any,
+                    new: Target::Peeled(head_id),
+                },
+                name: head,
+                deref: false,
+            })?;
+            Ok(false)
+        }
+    }
+}
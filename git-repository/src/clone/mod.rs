@@ -16,31 +16,20 @@ pub struct Prepare {
     url: git_url::Url,
 }
 
+/// A utility to collect configuration on how to perform a checkout into a working tree, and when dropped without checking out successfully
+/// the fetched repository will be dropped.
+#[must_use]
+pub struct PrepareCheckout {
+    /// A freshly initialized repository which is owned by us, or `None` if it was handed to the user
+    repo: Option<crate::Repository>,
+}
+
 ///
 #[cfg(feature = "blocking-network-client")]
-pub mod fetch {
-    /// The error returned by [`Prepare::fetch_only()`][super::Prepare::fetch_only()].
-    #[derive(Debug, thiserror::Error)]
-    #[allow(missing_docs)]
-    pub enum Error {
-        #[error(transparent)]
-        Connect(#[from] crate::remote::connect::Error),
-        #[error(transparent)]
-        PrepareFetch(#[from] crate::remote::fetch::prepare::Error),
-        #[error(transparent)]
-        Fetch(#[from] crate::remote::fetch::Error),
-        #[error(transparent)]
-        RemoteConfiguration(#[from] crate::remote::init::Error),
-        #[error("Default remote configured at `clone.defaultRemoteName` is invalid")]
-        RemoteName(#[from] crate::remote::name::Error),
-        #[error("Failed to load repo-local git configuration before writing")]
-        LoadConfig(#[from] git_config::file::init::from_paths::Error),
-        #[error("Failed to store configured remote in memory")]
-        SaveConfig(#[from] crate::remote::save::AsError),
-        #[error("Failed to write repository configuration to disk")]
-        SaveConfigIo(#[from] std::io::Error),
-    }
-}
+pub mod fetch;
+
+///
+pub mod checkout;
 
 ///
 pub mod prepare {
@@ -56,6 +45,8 @@ pub mod prepare {
         Init(#[from] crate::init::Error),
         #[error(transparent)]
         UrlParse(#[from] git_url::parse::Error),
+        #[error("Refusing to clone into the non-empty directory at '{}'", .path.display())]
+        DirectoryNotEmpty { path: std::path::PathBuf },
     }
 
     /// Instantiation
@@ -65,6 +56,8 @@ pub mod prepare {
         ///
         /// Note that this is merely a handle to perform the actual connection to the remote, and if any of it fails the freshly initialized repository
         /// will be removed automatically as soon as this instance drops.
+        ///
+        /// `path` may not exist yet or must be an empty directory, as otherwise we couldn't safely remove it on failure.
         pub fn new<Url, E>(
             url: Url,
             path: impl AsRef<std::path::Path>,
@@ -76,6 +69,10 @@ pub mod prepare {
             git_url::parse::Error: From<E>,
         {
             let url = url.try_into().map_err(git_url::parse::Error::from)?;
+            let path = path.as_ref();
+            if std::fs::read_dir(path).map_or(false, |mut entries| entries.next().is_some()) {
+                return Err(Error::DirectoryNotEmpty { path: path.to_owned() });
+            }
             let repo = crate::ThreadSafeRepository::init_opts(path, create_opts, open_opts)?.to_thread_local();
             Ok(Prepare {
                 url,
@@ -88,70 +85,6 @@ pub mod prepare {
         }
     }
 
-    /// Modification
-    impl Prepare {
-        /// Fetch a pack and update local branches according to refspecs, providing `progress` and checking `should_interrupt` to stop
-        /// the operation.
-        /// On success, the persisted repository is returned, and this method must not be called again to avoid a **panic**.
-        /// On error, the method may be called again to retry as often as needed.
-        ///
-        /// Note that all data we created will be removed once this instance drops if the operation wasn't successful.
-        #[cfg(feature = "blocking-network-client")]
-        pub fn fetch_only(
-            &mut self,
-            progress: impl crate::Progress,
-            should_interrupt: &std::sync::atomic::AtomicBool,
-        ) -> Result<(Repository, crate::remote::fetch::Outcome), super::fetch::Error> {
-            let repo = self
-                .repo
-                .as_mut()
-                .expect("user error: multiple calls are allowed only until it succeeds");
-
-            let remote_name = match self.remote_name.as_deref() {
-                Some(name) => name.to_owned(),
-                None => repo
-                    .config
-                    .resolved
-                    .string("clone", None, "defaultRemoteName")
-                    .map(|n| crate::remote::name::validated(n.to_string()))
-                    .unwrap_or_else(|| Ok("origin".into()))?,
-            };
-
-            let mut remote = repo
-                .remote_at(self.url.clone())?
-                .with_refspec("+refs/heads/*:refs/remotes/origin/*", crate::remote::Direction::Fetch)
-                .expect("valid static spec");
-            if let Some(f) = self.configure_remote.as_mut() {
-                remote = f(remote)?;
-            }
-
-            let mut metadata = git_config::file::Metadata::from(git_config::Source::Local);
-            let config_path = repo.git_dir().join("config");
-            metadata.path = Some(config_path.clone());
-            let mut config =
-                git_config::File::from_paths_metadata(Some(metadata), Default::default())?.expect("one file to load");
-            remote.save_as_to(remote_name, &mut config)?;
-            std::fs::write(config_path, config.to_bstring())?;
-
-            let outcome = remote
-                .connect(crate::remote::Direction::Fetch, progress)?
-                .prepare_fetch(self.fetch_options.clone())?
-                .receive(should_interrupt)?;
-
-            let repo_config = git_features::threading::OwnShared::make_mut(&mut repo.config.resolved);
-            let ids_to_remove: Vec<_> = repo_config
-                .sections_and_ids()
-                .filter_map(|(s, id)| (s.meta().source == git_config::Source::Local).then(|| id))
-                .collect();
-            for id in ids_to_remove {
-                repo_config.remove_section_by_id(id);
-            }
-            repo_config.append(config);
-
-            Ok((self.repo.take().expect("still present"), outcome))
-        }
-    }
-
     /// Builder
     impl Prepare {
         /// Set additional options to adjust parts of the fetch operation that are not affected by the git configuration.
@@ -10,8 +10,25 @@ pub use snapshot::{apply_cli_overrides, credential_helpers};
 /// A platform to access configuration values as read from disk.
 ///
 /// Note that these values won't update even if the underlying file(s) change.
+///
+/// ### Trust
+///
+/// If the [trust level][Snapshot::trust_level()] is below [`Full`][git_sec::Trust::Full], all values are subject to
+/// the [section filter][crate::open::Options::filter_config_section()], which by default ignores configuration files
+/// of the repository itself, as these are controlled by someone else.
+/// This affects [`boolean()`][Snapshot::boolean()], [`integer()`][Snapshot::integer()] and [`string()`][Snapshot::string()]
+/// along with their `try_*` variants.
+///
+/// The following are always filtered, independently of the trust level, as they may lead to programs being executed
+/// or files being accessed:
+///
+/// * paths obtained with [`trusted_path()`][Snapshot::trusted_path()],
+/// * `credential.helper` and all other `credential` values used by [`credential_helpers()`][Snapshot::credential_helpers()],
+/// * `remote.<name>.*` and `remote.pushDefault` when instantiating remotes,
+/// * `pack.threads` when fetching.
 pub struct Snapshot<'repo> {
     pub(crate) repo: &'repo Repository,
+    pub(crate) trust_level: git_sec::Trust,
 }
 
 /// A platform to access configuration values and modify them in memory, while making them available when this platform is dropped
@@ -34,6 +51,30 @@ pub struct CommitAutoRollback<'repo> {
     pub(crate) prev_config: crate::Config,
 }
 
+/// A value of keys like `pack.threads` or `fetch.fsckObjects`, which can be a boolean or an integer, as obtained by
+/// [`Snapshot::boolean_or_integer()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BooleanOrInteger {
+    /// The value is a boolean like `true`, `no` or an empty value, or the key is present without value which implies `true`.
+    Boolean(bool),
+    /// The value is an integer like `0` or `4`, possibly with a unit suffix like `k`.
+    Integer(i64),
+}
+
+/// The error returned by [`Snapshot::integer_in_range()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum RangeError {
+    #[error(transparent)]
+    Decode(#[from] git_config::value::Error),
+    #[error("Value {} at key '{}' is out of range, it must be between {} and {}", .value, .key, .range.start(), .range.end())]
+    OutOfRange {
+        key: String,
+        value: i64,
+        range: std::ops::RangeInclusive<i64>,
+    },
+}
+
 pub(crate) mod section {
     pub fn is_trusted(meta: &git_config::file::Metadata) -> bool {
         meta.trust == git_sec::Trust::Full || meta.source.kind() != git_config::source::Kind::Repository
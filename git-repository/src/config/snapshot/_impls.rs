@@ -1,9 +1,13 @@
 use std::{
+    convert::TryFrom,
     fmt::{Debug, Formatter},
     ops::{Deref, DerefMut},
 };
 
-use crate::config::{CommitAutoRollback, Snapshot, SnapshotMut};
+use crate::{
+    bstr::BStr,
+    config::{BooleanOrInteger, CommitAutoRollback, Snapshot, SnapshotMut},
+};
 
 impl Debug for Snapshot<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -60,3 +64,20 @@ impl DerefMut for SnapshotMut<'_> {
         &mut self.config
     }
 }
+
+impl TryFrom<&BStr> for BooleanOrInteger {
+    type Error = git_config::value::Error;
+
+    fn try_from(value: &BStr) -> Result<Self, Self::Error> {
+        // Like git, integers are tried first so `0` and `1` stay integers, and only then the value is interpreted as boolean.
+        match git_config::Integer::try_from(value) {
+            Ok(int) => int
+                .to_decimal()
+                .map(BooleanOrInteger::Integer)
+                .ok_or_else(|| git_config::value::Error::new("Integer overflow", value)),
+            Err(_) => git_config::Boolean::try_from(value)
+                .map(|b| BooleanOrInteger::Boolean(b.into()))
+                .map_err(|_| git_config::value::Error::new("Expected a boolean or an integer", value)),
+        }
+    }
+}
@@ -1,10 +1,14 @@
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    convert::{TryFrom, TryInto},
+    ops::RangeInclusive,
+};
 
 use git_features::threading::OwnShared;
 
 use crate::{
     bstr::BStr,
-    config::{cache::interpolate_context, CommitAutoRollback, Snapshot, SnapshotMut},
+    config::{cache::interpolate_context, BooleanOrInteger, CommitAutoRollback, RangeError, Snapshot, SnapshotMut},
 };
 
 /// Access configuration values, frozen in time, using a `key` which is a `.` separated string of up to
@@ -18,7 +22,7 @@ impl<'repo> Snapshot<'repo> {
     ///
     /// For a non-degenerating version, use [`try_boolean(…)`][Self::try_boolean()].
     ///
-    /// Note that this method takes the most recent value at `key` even if it is from a file with reduced trust.
+    /// Note that values from files with reduced trust are ignored if our [trust level][Self::trust_level()] is reduced.
     pub fn boolean(&self, key: &str) -> Option<bool> {
         self.try_boolean(key).and_then(Result::ok)
     }
@@ -26,10 +30,12 @@ impl<'repo> Snapshot<'repo> {
     /// Like [`boolean()`][Self::boolean()], but it will report an error if the value couldn't be interpreted as boolean.
     pub fn try_boolean(&self, key: &str) -> Option<Result<bool, git_config::value::Error>> {
         let key = git_config::parse::key(key)?;
-        self.repo
-            .config
-            .resolved
-            .boolean(key.section_name, key.subsection_name, key.value_name)
+        self.repo.config.resolved.boolean_filter(
+            key.section_name,
+            key.subsection_name,
+            key.value_name,
+            &mut self.filter_by_trust_level(),
+        )
     }
 
     /// Return the resolved integer at `key`, or `None` if there is no such value or if the value can't be interpreted as
@@ -37,7 +43,7 @@ impl<'repo> Snapshot<'repo> {
     ///
     /// For a non-degenerating version, use [`try_integer(…)`][Self::try_integer()].
     ///
-    /// Note that this method takes the most recent value at `key` even if it is from a file with reduced trust.
+    /// Note that values from files with reduced trust are ignored if our [trust level][Self::trust_level()] is reduced.
     pub fn integer(&self, key: &str) -> Option<i64> {
         self.try_integer(key).and_then(Result::ok)
     }
@@ -45,21 +51,76 @@ impl<'repo> Snapshot<'repo> {
     /// Like [`integer()`][Self::integer()], but it will report an error if the value couldn't be interpreted as boolean.
     pub fn try_integer(&self, key: &str) -> Option<Result<i64, git_config::value::Error>> {
         let key = git_config::parse::key(key)?;
-        self.repo
-            .config
-            .resolved
-            .integer(key.section_name, key.subsection_name, key.value_name)
+        self.repo.config.resolved.integer_filter(
+            key.section_name,
+            key.subsection_name,
+            key.value_name,
+            &mut self.filter_by_trust_level(),
+        )
+    }
+
+    /// Like [`try_integer()`][Self::try_integer()], but it will also report an error if the value is not within `range`,
+    /// for keys like `core.compression` which only allow a limited set of values.
+    pub fn integer_in_range(&self, key: &str, range: RangeInclusive<i64>) -> Option<Result<i64, RangeError>> {
+        Some(self.try_integer(key)?.map_err(Into::into).and_then(|value| {
+            if range.contains(&value) {
+                Ok(value)
+            } else {
+                Err(RangeError::OutOfRange {
+                    key: key.into(),
+                    value,
+                    range,
+                })
+            }
+        }))
+    }
+
+    /// Return the value at `key` as boolean or integer for keys like `pack.threads` that support both, or `None` if there is
+    /// no such value or if it can't be interpreted as either.
+    ///
+    /// Like git, integers take precedence, so `0` is [`Integer(0)`][BooleanOrInteger::Integer] while `false` and keys without
+    /// value are booleans.
+    /// For a non-degenerating version, use [`try_boolean_or_integer(…)`][Self::try_boolean_or_integer()].
+    ///
+    /// Note that values from files with reduced trust are ignored if our [trust level][Self::trust_level()] is reduced.
+    pub fn boolean_or_integer(&self, key: &str) -> Option<BooleanOrInteger> {
+        self.try_boolean_or_integer(key).and_then(Result::ok)
+    }
+
+    /// Like [`boolean_or_integer()`][Self::boolean_or_integer()], but it will report an error if the value couldn't be
+    /// interpreted as boolean or integer.
+    pub fn try_boolean_or_integer(&self, key: &str) -> Option<Result<BooleanOrInteger, git_config::value::Error>> {
+        match self.string(key) {
+            Some(value) => Some(BooleanOrInteger::try_from(value.as_ref())),
+            None => self.try_boolean(key).map(|res| res.map(BooleanOrInteger::Boolean)),
+        }
     }
 
     /// Return the string at `key`, or `None` if there is no such value.
     ///
-    /// Note that this method takes the most recent value at `key` even if it is from a file with reduced trust.
+    /// Note that values from files with reduced trust are ignored if our [trust level][Self::trust_level()] is reduced.
     pub fn string(&self, key: &str) -> Option<Cow<'_, BStr>> {
         let key = git_config::parse::key(key)?;
-        self.repo
-            .config
-            .resolved
-            .string(key.section_name, key.subsection_name, key.value_name)
+        self.repo.config.resolved.string_filter(
+            key.section_name,
+            key.subsection_name,
+            key.value_name,
+            &mut self.filter_by_trust_level(),
+        )
+    }
+
+    /// Return the color at `key`, or `None` if there is no such value or if the value can't be interpreted as color.
+    ///
+    /// For a non-degenerating version, use [`try_color(…)`][Self::try_color()].
+    ///
+    /// Note that values from files with reduced trust are ignored if our [trust level][Self::trust_level()] is reduced.
+    pub fn color(&self, key: &str) -> Option<git_config::Color> {
+        self.try_color(key).and_then(Result::ok)
+    }
+
+    /// Like [`color()`][Self::color()], but it will report an error if the value couldn't be interpreted as color.
+    pub fn try_color(&self, key: &str) -> Option<Result<git_config::Color, git_config::value::Error>> {
+        self.string(key).map(|value| value.as_ref().try_into())
     }
 
     /// Return the trusted and fully interpolated path at `key`, or `None` if there is no such value
@@ -83,8 +144,95 @@ impl<'repo> Snapshot<'repo> {
     }
 }
 
+/// Access configuration values like the methods above, but return the first value found, which is the one set earliest
+/// in the hierarchy of configuration files, aka 'first one wins'.
+///
+/// This is useful for keys whose values are to be inspected in order.
+impl<'repo> Snapshot<'repo> {
+    /// Like [`boolean()`][Self::boolean()], but returns the first value found.
+    pub fn boolean_first(&self, key: &str) -> Option<bool> {
+        self.try_boolean_first(key).and_then(Result::ok)
+    }
+
+    /// Like [`try_boolean()`][Self::try_boolean()], but returns the first value found.
+    pub fn try_boolean_first(&self, key: &str) -> Option<Result<bool, git_config::value::Error>> {
+        Some(match self.first_value(key)? {
+            Some(value) => git_config::Boolean::try_from(value).map(Into::into),
+            None => Ok(true),
+        })
+    }
+
+    /// Like [`integer()`][Self::integer()], but returns the first value found.
+    pub fn integer_first(&self, key: &str) -> Option<i64> {
+        self.try_integer_first(key).and_then(Result::ok)
+    }
+
+    /// Like [`try_integer()`][Self::try_integer()], but returns the first value found.
+    pub fn try_integer_first(&self, key: &str) -> Option<Result<i64, git_config::value::Error>> {
+        let value = self.first_value(key)??;
+        Some(git_config::Integer::try_from(value.as_ref()).and_then(|int| {
+            int.to_decimal()
+                .ok_or_else(|| git_config::value::Error::new("Integer overflow", value.into_owned()))
+        }))
+    }
+
+    /// Like [`string()`][Self::string()], but returns the first value found.
+    pub fn string_first(&self, key: &str) -> Option<Cow<'_, BStr>> {
+        self.first_value(key)?
+    }
+
+    /// Return the first value of `key` in sections passing our trust level, with `Some(None)` indicating a key without value.
+    fn first_value(&self, key: &str) -> Option<Option<Cow<'_, BStr>>> {
+        let key = git_config::parse::key(key)?;
+        let subsection_name = key.subsection_name.map(Into::into);
+        let mut filter = self.filter_by_trust_level();
+        self.repo
+            .config
+            .resolved
+            .sections()
+            .filter(|section| {
+                let header = section.header();
+                header.name().eq_ignore_ascii_case(key.section_name.as_bytes())
+                    && header.subsection_name() == subsection_name
+                    && filter(section.meta())
+            })
+            .find_map(|section| section.first_value_implicit(key.value_name))
+    }
+}
+
 /// Utilities and additional access
 impl<'repo> Snapshot<'repo> {
+    /// The level of trust with which values are returned, which is the trust of the repository's `.git` directory.
+    ///
+    /// See the [type-level documentation][Snapshot] for details.
+    pub fn trust_level(&self) -> git_sec::Trust {
+        self.trust_level
+    }
+
+    fn filter_by_trust_level(&self) -> impl FnMut(&git_config::file::Metadata) -> bool {
+        let filter = self.repo.filter_config_section();
+        let trust_level = self.trust_level;
+        move |meta| trust_level == git_sec::Trust::Full || filter(meta)
+    }
+
+    /// Return the paths of all configuration files that were included by `include.path` or `includeIf.<condition>.path`,
+    /// directly or indirectly, in the order in which they contribute to the configuration.
+    ///
+    /// Conditional includes only appear if their condition matched when the repository was opened, which is useful to
+    /// learn why a value resolved the way it did. Note that includes are only followed if [permitted][crate::permissions::Config::includes].
+    pub fn included_paths(&self) -> Vec<std::path::PathBuf> {
+        let mut paths = Vec::<std::path::PathBuf>::new();
+        for meta in self.repo.config.resolved.sections().map(|section| section.meta()) {
+            if meta.level == 0 {
+                continue;
+            }
+            if let Some(path) = meta.path.as_ref().filter(|path| !paths.contains(path)) {
+                paths.push(path.clone());
+            }
+        }
+        paths
+    }
+
     /// Returns the underlying configuration implementation for a complete API, despite being a little less convenient.
     ///
     /// It's expected that more functionality will move up depending on demand.
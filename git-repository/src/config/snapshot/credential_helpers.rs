@@ -30,6 +30,10 @@ impl Snapshot<'_> {
     /// These can be invoked to obtain credentials. Note that the `url` is expected to be the one used
     /// to connect to a remote, and thus should already have passed the url-rewrite engine.
     ///
+    /// Like `git`, all `credential` sections whose url pattern matches `url` are applied in the order they appear in the configuration,
+    /// not only the most specific one. This means helpers accumulate, while later values like `useHttpPath` or `username`
+    /// override earlier ones, which typically are the more general patterns.
+    ///
     /// # Deviation
     ///
     /// - Invalid urls can't be used to obtain credential helpers as they are rejected early when creating a valid `url` here.
@@ -5,6 +5,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use crate::bstr::BString;
 use git_config::parse::section;
 use git_discover::DOT_GIT_DIR;
 
@@ -38,7 +39,6 @@ const TPL_HOOKS_PRE_RECEIVE: &[u8] = include_bytes!("assets/baseline-init/hooks/
 const TPL_HOOKS_PREPARE_COMMIT_MSG: &[u8] = include_bytes!("assets/baseline-init/hooks/prepare-commit-msg.sample");
 const TPL_HOOKS_UPDATE: &[u8] = include_bytes!("assets/baseline-init/hooks/update.sample");
 const TPL_DESCRIPTION: &[u8] = include_bytes!("assets/baseline-init/description");
-const TPL_HEAD: &[u8] = include_bytes!("assets/baseline-init/HEAD");
 
 struct PathCursor<'a>(&'a mut PathBuf);
 
@@ -110,6 +110,9 @@ pub struct Options {
 
 /// Create a new `.git` repository of `kind` within the possibly non-existing `directory`
 /// and return its path.
+///
+/// `HEAD` will point to the `master` branch, see [`ThreadSafeRepository::init_opts()`][crate::ThreadSafeRepository::init_opts()]
+/// to let it point to the branch configured in `init.defaultBranch` instead.
 pub fn into(
     directory: impl Into<PathBuf>,
     Options { bare, fs_capabilities }: Options,
@@ -173,7 +176,8 @@ pub fn into(
         create_dir(PathCursor(cursor.as_mut()).at("tags"))?;
     }
 
-    for (tpl, filename) in &[(TPL_HEAD, "HEAD"), (TPL_DESCRIPTION, "description")] {
+    let head = head_pointing_to(DEFAULT_BRANCH_NAME.into()).expect("valid branch name");
+    for (tpl, filename) in &[(head.as_slice(), "HEAD"), (TPL_DESCRIPTION, "description")] {
         write_file(tpl, PathCursor(&mut dot_git).at(filename))?;
     }
 
@@ -206,6 +210,37 @@ pub fn into(
     ))
 }
 
+/// The branch `HEAD` points to in new repositories if `init.defaultBranch` isn't set, like in git.
+const DEFAULT_BRANCH_NAME: &str = "master";
+
+/// Let `HEAD` in `git_dir` point to the branch configured in `init.defaultBranch` of `config`, if it is set to a valid name.
+///
+/// Otherwise `HEAD` is left untouched, pointing to the `master` branch.
+pub(crate) fn point_head_to_default_branch(git_dir: &Path, config: &git_config::File<'_>) -> Result<(), Error> {
+    let head = match config
+        .string("init", None, "defaultBranch")
+        .filter(|name| name.as_ref() != DEFAULT_BRANCH_NAME)
+        .and_then(|name| head_pointing_to(name.as_ref()))
+    {
+        Some(head) => head,
+        None => return Ok(()),
+    };
+    let head_path = git_dir.join("HEAD");
+    std::fs::write(&head_path, head).map_err(|err| Error::IoWrite {
+        source: err,
+        path: head_path,
+    })
+}
+
+/// Return the content of a `HEAD` file pointing to the branch `name`, or `None` if it isn't a valid branch name.
+fn head_pointing_to(name: &crate::bstr::BStr) -> Option<BString> {
+    let mut head = BString::from("ref: refs/heads/");
+    head.extend_from_slice(name);
+    git_validate::reference::name(head["ref: ".len()..].as_ref()).ok()?;
+    head.push(b'\n');
+    Some(head)
+}
+
 fn key(name: &'static str) -> section::Key<'static> {
     section::Key::try_from(name).expect("valid key name")
 }
@@ -0,0 +1,9 @@
+/// Options to control when [`Repository::should_auto_gc()`][crate::Repository::should_auto_gc()] suggests
+/// running `git gc --auto`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AutoOptions {
+    /// If there are more than this amount of loose references, garbage collection is suggested to pack them.
+    ///
+    /// A value of 0 disables this check.
+    pub loose_refs_limit: usize,
+}
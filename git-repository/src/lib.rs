@@ -350,6 +350,9 @@ pub mod init {
         }
 
         /// Similar to [`init`][Self::init()], but allows to determine how exactly to open the newly created repository.
+        ///
+        /// `HEAD` will point to the branch configured in `init.defaultBranch` of the configuration loaded according to
+        /// `open_options`, or to `master` if it is unset or not a valid branch name.
         pub fn init_opts(
             directory: impl AsRef<Path>,
             create_options: crate::create::Options,
@@ -358,7 +361,9 @@ pub mod init {
             let path = crate::create::into(directory.as_ref(), create_options)?;
             let (git_dir, worktree_dir) = path.into_repository_and_work_tree_directories();
             open_options.git_dir_trust = Some(git_sec::Trust::Full);
-            ThreadSafeRepository::open_from_paths(git_dir, worktree_dir, open_options).map_err(Into::into)
+            let repo = ThreadSafeRepository::open_from_paths(git_dir, worktree_dir, open_options)?;
+            crate::create::point_head_to_default_branch(repo.git_dir(), &repo.config.resolved)?;
+            Ok(repo)
         }
     }
 }
@@ -395,6 +400,9 @@ pub mod state {
 ///
 pub mod discover;
 
+///
+pub mod gc;
+
 ///
 pub mod env;
 
@@ -1,3 +1,5 @@
+use git_object::bstr::{BStr, BString, ByteVec};
+
 use super::Tree;
 use crate::Repository;
 
@@ -5,6 +7,8 @@ use crate::Repository;
 pub struct EntryRef<'repo, 'a> {
     /// The actual entry ref we are wrapping.
     pub inner: git_object::tree::EntryRef<'a>,
+    /// The path of the tree containing this entry, or empty if it is contained in the tree the iteration started at.
+    pub(crate) parent: &'a BStr,
 
     pub(crate) repo: &'repo Repository,
 }
@@ -20,6 +24,23 @@ impl<'repo, 'a> EntryRef<'repo, 'a> {
         self.inner.filename
     }
 
+    /// The path of the tree containing this entry relative to the tree at which the iteration started, which is empty
+    /// unless the entry was obtained by [recursive traversal][crate::object::tree::traverse::Platform::depthfirst_entries()].
+    pub fn relative_path(&self) -> &BStr {
+        self.parent
+    }
+
+    /// The [relative path][Self::relative_path()] of the parent tree joined with the [filename][Self::filename()],
+    /// separated by a slash.
+    pub fn full_path(&self) -> BString {
+        let mut path = BString::from(self.parent);
+        if !path.is_empty() {
+            path.push_byte(b'/');
+        }
+        path.push_str(self.inner.filename);
+        path
+    }
+
     /// Return the entries id, connected to the underlying repository.
     pub fn id(&self) -> crate::Id<'repo> {
         crate::Id::from_id(self.inner.oid, self.repo)
@@ -48,6 +69,12 @@ impl<'repo> Tree<'repo> {
     /// Return an iterator over tree entries.
     pub fn iter(&self) -> impl Iterator<Item = Result<EntryRef<'repo, '_>, git_object::decode::Error>> {
         let repo = self.repo;
-        git_object::TreeRefIter::from_bytes(&self.data).map(move |e| e.map(|entry| EntryRef { inner: entry, repo }))
+        git_object::TreeRefIter::from_bytes(&self.data).map(move |e| {
+            e.map(|entry| EntryRef {
+                inner: entry,
+                parent: "".into(),
+                repo,
+            })
+        })
     }
 }
@@ -1,6 +1,7 @@
+use git_object::bstr::{BString, ByteVec};
 use git_odb::FindExt;
 
-use crate::Tree;
+use crate::{object::tree::EntryRef, Repository, Tree};
 
 /// Traversal
 impl<'repo> Tree<'repo> {
@@ -59,3 +60,72 @@ impl<'a, 'repo> Platform<'a, 'repo> {
         )
     }
 }
+
+impl<'a, 'repo> Platform<'a, 'repo> {
+    /// Call `for_each` with every entry reachable from the root tree, depth-first and in the order in which entries are
+    /// stored in their tree, with trees being passed before their own entries.
+    ///
+    /// Each entry knows the [path of its parent tree][EntryRef::relative_path()] to be able to produce its
+    /// [full path][EntryRef::full_path()].
+    pub fn depthfirst_entries<E>(
+        &self,
+        mut for_each: impl FnMut(EntryRef<'repo, '_>) -> Result<(), E>,
+    ) -> Result<(), depthfirst::Error>
+    where
+        E: std::error::Error + Sync + Send + 'static,
+    {
+        fn recurse<'repo, E>(
+            repo: &'repo Repository,
+            data: &[u8],
+            parent: &mut BString,
+            for_each: &mut impl FnMut(EntryRef<'repo, '_>) -> Result<(), E>,
+        ) -> Result<(), depthfirst::Error>
+        where
+            E: std::error::Error + Sync + Send + 'static,
+        {
+            for entry in git_object::TreeRefIter::from_bytes(data) {
+                let entry = entry?;
+                let subtree = entry
+                    .mode
+                    .is_tree()
+                    .then(|| (entry.oid.to_owned(), entry.filename.to_owned()));
+                for_each(EntryRef {
+                    inner: entry,
+                    parent: parent.as_ref(),
+                    repo,
+                })
+                .map_err(|err| depthfirst::Error::ForEach(Box::new(err)))?;
+
+                if let Some((id, filename)) = subtree {
+                    let tree = repo.find_object(id)?.try_into_tree()?;
+                    let parent_len = parent.len();
+                    if !parent.is_empty() {
+                        parent.push_byte(b'/');
+                    }
+                    parent.push_str(&filename);
+                    recurse(repo, &tree.data, parent, for_each)?;
+                    parent.truncate(parent_len);
+                }
+            }
+            Ok(())
+        }
+        recurse(self.root.repo, &self.root.data, &mut BString::default(), &mut for_each)
+    }
+}
+
+///
+pub mod depthfirst {
+    /// The error returned by [`depthfirst_entries()`][super::Platform::depthfirst_entries()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        NotATree(#[from] crate::object::try_into::Error),
+        #[error(transparent)]
+        Decode(#[from] git_object::decode::Error),
+        #[error("The callback returned an error")]
+        ForEach(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+    }
+}
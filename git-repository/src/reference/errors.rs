@@ -73,7 +73,16 @@ pub mod find {
         }
     }
 
-    /// The error returned by [try_find_reference(…)][crate::Repository::try_find_reference()].
+    /// Options for use in [find_reference_by_prefix(…)][crate::Repository::find_reference_by_prefix()].
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Options {
+        /// If `true`, return the first reference in lookup order if the name is ambiguous instead of failing, similar to
+        /// `git rev-parse` which merely warns in that case.
+        pub allow_ambiguous: bool,
+    }
+
+    /// The error returned by [try_find_reference(…)][crate::Repository::try_find_reference()] and
+    /// [find_reference_by_prefix(…)][crate::Repository::find_reference_by_prefix()].
     #[derive(Debug, thiserror::Error)]
     #[allow(missing_docs)]
     pub enum Error {
@@ -81,5 +90,7 @@ pub mod find {
         Find(#[from] git_ref::file::find::Error),
         #[error(transparent)]
         PackedRefsOpen(#[from] git_ref::packed::buffer::open::Error),
+        #[error("The partial reference name is ambiguous as it matches {}", candidates.iter().map(|n| n.as_bstr().to_string()).collect::<Vec<_>>().join(", "))]
+        Ambiguous { candidates: Vec<git_ref::FullName> },
     }
 }
@@ -21,6 +21,10 @@ mod error {
         UnknownProtocol { given: BString },
         #[error("Could not verify that file:// url is a valid git directory before attempting to use it")]
         FileUrl(#[from] git_discover::is_git::Error),
+        #[error("Could not interpolate a path used to configure the transport")]
+        PathInterpolation(#[from] git_config::path::interpolate::Error),
+        #[error("The transport could not be configured")]
+        ConfigureTransport(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
     }
 }
 pub use error::Error;
@@ -49,6 +53,8 @@ impl<'repo> Remote<'repo> {
     /// Note that the `protocol.version` configuration key affects the transport protocol used to connect,
     /// with `2` being the default.
     ///
+    /// Connections via `http(s)` use the proxy as configured by [`http_proxy_options()`][Self::http_proxy_options()].
+    ///
     /// The transport used for connection can be configured via `transport_mut().configure()` assuming the actually
     /// used transport is well known. If that's not the case, the transport can be created by hand and passed to
     /// [to_connection_with_transport()][Self::to_connection_with_transport()].
@@ -63,10 +69,65 @@ impl<'repo> Remote<'repo> {
         P: Progress,
     {
         let (url, version) = self.sanitized_url_and_version(direction)?;
-        let transport = git_protocol::transport::connect(url, version).await?;
+        #[cfg(feature = "blocking-http-transport")]
+        let http_options = if matches!(url.scheme, git_url::Scheme::Http | git_url::Scheme::Https) {
+            Some((self.http_proxy_options(&url.scheme), self.http_ssl_options()?))
+        } else {
+            None
+        };
+        #[allow(unused_mut)]
+        let mut transport = git_protocol::transport::connect(url, version, Default::default()).await?;
+        #[cfg(feature = "blocking-http-transport")]
+        if let Some((proxy, ssl)) = http_options {
+            transport.configure(&proxy).map_err(Error::ConfigureTransport)?;
+            transport.configure(&ssl).map_err(Error::ConfigureTransport)?;
+        }
         Ok(self.to_connection_with_transport(transport, progress))
     }
 
+    /// Return the proxy options to use when connecting to a url with the `http` or `https` `scheme`.
+    ///
+    /// The proxy is taken from the `http.proxy` configuration, where an empty value means to not use a proxy at all,
+    /// or from the `https_proxy` and `http_proxy` environment variables or their uppercase variants otherwise.
+    /// Hosts to connect to directly are always read from the `no_proxy` or `NO_PROXY` variables.
+    /// Note that `http.proxy` is ignored in configuration files that aren't trusted.
+    #[cfg(feature = "blocking-http-transport")]
+    pub fn http_proxy_options(
+        &self,
+        scheme: &git_url::Scheme,
+    ) -> git_protocol::transport::client::http::proxy::Options {
+        let mut options =
+            git_protocol::transport::client::http::proxy::Options::from_environment(*scheme == git_url::Scheme::Https);
+        if let Some(url) = self.repo.config_snapshot().string("http.proxy") {
+            options.url = (!url.is_empty()).then(|| url.to_string());
+        }
+        options
+    }
+
+    /// Return the options to verify servers and to identify ourselves when connecting via `https`.
+    ///
+    /// Certificates are not verified if `http.sslVerify` is false, and `http.sslCAInfo` is a file with certificate authorities
+    /// to verify them with. `http.sslCert` and `http.sslKey` are the files with the client certificate and its private key.
+    /// Paths are only read from trusted configuration files.
+    #[cfg(feature = "blocking-http-transport")]
+    pub fn http_ssl_options(
+        &self,
+    ) -> Result<git_protocol::transport::client::http::ssl::Options, git_config::path::interpolate::Error> {
+        let config = self.repo.config_snapshot();
+        let path = |key: &str| {
+            config
+                .trusted_path(key)
+                .transpose()
+                .map(|path| path.map(std::borrow::Cow::into_owned))
+        };
+        Ok(git_protocol::transport::client::http::ssl::Options {
+            verify: config.boolean("http.sslVerify").unwrap_or(true),
+            ca_info: path("http.sslCAInfo")?,
+            client_cert: path("http.sslCert")?,
+            client_key: path("http.sslKey")?,
+        })
+    }
+
     /// Produce the sanitized URL and protocol version to use as obtained by querying the repository configuration.
     ///
     /// This can be useful when using custom transports to allow additional configuration.
@@ -70,6 +70,9 @@ pub struct Outcome {
     pub ref_map: RefMap,
     /// The status of the operation to indicate what happened.
     pub status: Status,
+    /// The tracking references that were deleted as they are not present on the remote anymore, or that would have been deleted
+    /// in dry-run mode. It's only filled in if pruning was enabled with [`Prepare::with_prune()`].
+    pub pruned: Vec<git_ref::transaction::RefEdit>,
 }
 
 ///
@@ -113,6 +116,7 @@ where
             con: Some(self),
             ref_map,
             dry_run: DryRun::No,
+            prune: false,
         })
     }
 }
@@ -185,9 +189,11 @@ where
             ) {
                 Ok(_) if arguments.is_empty() => {
                     git_protocol::fetch::indicate_end_of_interaction(&mut con.transport).ok();
+                    let pruned = self.prune_tracking_refs(repo, con.remote)?;
                     return Ok(Outcome {
                         ref_map: std::mem::take(&mut self.ref_map),
                         status: Status::NoChange,
+                        pruned,
                     });
                 }
                 Ok(is_done) => is_done,
@@ -219,11 +225,16 @@ where
             index_version: config::pack_index_version(repo)?,
             iteration_mode: git_pack::data::input::Mode::Verify,
             object_hash: con.remote.repo.object_hash(),
+            write_reverse_index: false,
+            temp_file_name: None,
+            use_mmap: true,
+            verify_crc_on_write: false,
         };
 
         let mut write_pack_bundle = if matches!(self.dry_run, fetch::DryRun::No) {
             Some(git_pack::Bundle::write_to_directory(
                 reader,
+                None,
                 Some(repo.objects.store_ref().path().join("pack")),
                 con.progress,
                 should_interrupt,
@@ -258,8 +269,10 @@ where
             }
         }
 
+        let pruned = self.prune_tracking_refs(repo, con.remote)?;
         Ok(Outcome {
             ref_map: std::mem::take(&mut self.ref_map),
+            pruned,
             status: match write_pack_bundle {
                 Some(write_pack_bundle) => Status::Change {
                     write_pack_bundle,
@@ -271,6 +284,28 @@ where
     }
 }
 
+/// Utilities
+impl<'remote, 'repo, T, P> Prepare<'remote, 'repo, T, P>
+where
+    T: Transport,
+{
+    fn prune_tracking_refs(
+        &self,
+        repo: &crate::Repository,
+        remote: &crate::Remote<'_>,
+    ) -> Result<Vec<git_ref::transaction::RefEdit>, refs::update::Error> {
+        if !self.prune {
+            return Ok(Vec::new());
+        }
+        refs::prune(
+            repo,
+            &self.ref_map.mappings,
+            remote.refspecs(remote::Direction::Fetch),
+            self.dry_run,
+        )
+    }
+}
+
 fn setup_remote_progress(
     progress: &mut impl Progress,
     reader: &mut Box<dyn git_protocol::transport::client::ExtendedBufRead + Unpin + '_>,
@@ -297,6 +332,7 @@ where
     con: Option<Connection<'remote, 'repo, T, P>>,
     ref_map: RefMap,
     dry_run: DryRun,
+    prune: bool,
 }
 
 /// Builder
@@ -311,6 +347,15 @@ where
         self.dry_run = enabled.then(|| DryRun::Yes).unwrap_or(DryRun::No);
         self
     }
+
+    /// If enabled, tracking references matching the destination of a ref-spec will be deleted if they don't exist on the remote
+    /// anymore, similar to `git fetch --prune`. Defaults to `false`.
+    ///
+    /// In dry-run mode, the references that would be deleted are listed but not actually deleted.
+    pub fn with_prune(mut self, enabled: bool) -> Self {
+        self.prune = enabled;
+        self
+    }
 }
 
 impl<'remote, 'repo, T, P> Drop for Prepare<'remote, 'repo, T, P>
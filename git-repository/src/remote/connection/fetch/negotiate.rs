@@ -33,6 +33,7 @@ pub(crate) fn one_round(
                     repo.find_reference(name)
                         .ok()
                         .and_then(|r| r.target().try_id().map(ToOwned::to_owned))
+                        .filter(|id| repo.has_object(id))
                 });
                 match have_id {
                     Some(have_id) if mapping.remote.as_id() != have_id => {
@@ -49,7 +50,7 @@ pub(crate) fn one_round(
 
             if has_missing_tracking_branch {
                 if let Ok(Some(r)) = repo.head_ref() {
-                    if let Some(id) = r.target().try_id() {
+                    if let Some(id) = r.target().try_id().filter(|id| repo.has_object(id)) {
                         arguments.have(id);
                     }
                 }
@@ -8,6 +8,7 @@ use git_ref::{
 };
 
 use crate::{
+    bstr::{BStr, ByteSlice},
     ext::ObjectIdExt,
     remote::{fetch, fetch::refs::update::Mode},
     Repository,
@@ -185,6 +186,61 @@ pub(crate) fn update(
     Ok(update::Outcome { edits, updates })
 }
 
+/// Delete all tracking references in `refs/remotes/` that match the destination of one of the `refspecs`, but that don't
+/// have a corresponding remote reference in `mappings` anymore, and return the edits that were performed.
+/// Symbolic references like `refs/remotes/origin/HEAD` are never deleted.
+/// If `dry_run` is true, the returned edits are not actually applied.
+pub(crate) fn prune(
+    repo: &Repository,
+    mappings: &[fetch::Mapping],
+    refspecs: &[git_refspec::RefSpec],
+    dry_run: fetch::DryRun,
+) -> Result<Vec<RefEdit>, update::Error> {
+    let mut edits = Vec::new();
+    for reference in repo.references()?.remote_branches()? {
+        let reference = reference.map_err(update::Error::ReadTrackingReference)?;
+        let name = reference.name().as_bstr();
+        let is_tracked = refspecs.iter().map(|spec| spec.to_ref()).any(|spec| {
+            spec.mode() != git_refspec::Mode::Negative
+                && spec
+                    .destination()
+                    .map_or(false, |destination| matches_destination(destination, name))
+        });
+        let still_exists = mappings
+            .iter()
+            .any(|m| m.local.as_ref().map_or(false, |local| local == name));
+        if !is_tracked || still_exists {
+            continue;
+        }
+        if let Target::Peeled(id) = &reference.inner.target {
+            edits.push(RefEdit {
+                change: Change::Delete {
+                    expected: PreviousValue::MustExistAndMatch(Target::Peeled(*id)),
+                    log: RefLog::AndReference,
+                },
+                name: reference.inner.name.clone(),
+                deref: false,
+            });
+        }
+    }
+
+    Ok(match dry_run {
+        fetch::DryRun::No => repo.edit_references(edits)?,
+        fetch::DryRun::Yes => edits,
+    })
+}
+
+/// Return true if `name` matches the `destination` of a ref-spec, which may contain a single `*` glob.
+fn matches_destination(destination: &BStr, name: &BStr) -> bool {
+    match destination.find_byte(b'*') {
+        Some(pos) => {
+            let (prefix, suffix) = (&destination[..pos], &destination[pos + 1..]);
+            name.len() > prefix.len() + suffix.len() && name.starts_with(prefix) && name.ends_with(suffix)
+        }
+        None => destination == name,
+    }
+}
+
 fn worktree_branches(repo: &Repository) -> Result<BTreeMap<git_ref::FullName, PathBuf>, update::Error> {
     let mut map = BTreeMap::new();
     if let Some((wt_dir, head_ref)) = repo.work_dir().zip(repo.head_ref().ok().flatten()) {
@@ -285,6 +285,54 @@ mod update {
         }
     }
 
+    #[test]
+    fn prune_deletes_tracking_refs_without_remote_counterpart() {
+        let (repo, _tmp) = repo_rw("two-origins");
+        let (mappings, specs) = mapping_from_spec("refs/heads/*:refs/remotes/origin/*", &repo);
+        let stale: Vec<_> = ('a'..='j').map(|c| format!("refs/remotes/origin/{}", c)).collect();
+
+        let out = fetch::refs::prune(&repo, &mappings, &specs, fetch::DryRun::Yes).unwrap();
+        assert_eq!(
+            out.iter()
+                .map(|edit| edit.name.as_bstr().to_string())
+                .collect::<Vec<_>>(),
+            stale,
+            "origin/main still exists on the remote, and the symbolic origin/HEAD is never deleted"
+        );
+        assert!(
+            out.iter().all(|edit| matches!(edit.change, Change::Delete { .. })),
+            "only deletions are performed"
+        );
+        assert!(
+            repo.try_find_reference("refs/remotes/origin/a").unwrap().is_some(),
+            "nothing is deleted in dry-run mode"
+        );
+
+        let out = fetch::refs::prune(&repo, &mappings, &specs, fetch::DryRun::No).unwrap();
+        assert_eq!(out.len(), stale.len());
+        for name in &stale {
+            assert!(
+                repo.try_find_reference(name.as_str()).unwrap().is_none(),
+                "{} was deleted",
+                name
+            );
+        }
+        for name in ["refs/remotes/origin/main", "refs/remotes/origin/HEAD"] {
+            assert!(repo.try_find_reference(name).unwrap().is_some(), "{} was kept", name);
+        }
+    }
+
+    #[test]
+    fn prune_ignores_tracking_refs_not_matching_any_destination() {
+        let repo = repo("two-origins");
+        let (mappings, specs) = mapping_from_spec("refs/heads/main:refs/remotes/origin/main", &repo);
+        let out = fetch::refs::prune(&repo, &mappings, &specs, fetch::DryRun::Yes).unwrap();
+        assert!(
+            out.is_empty(),
+            "only refs that could have been written by a ref-spec are candidates"
+        );
+    }
+
     fn mapping_from_spec(spec: &str, repo: &git::Repository) -> (Vec<fetch::Mapping>, Vec<git::refspec::RefSpec>) {
         let spec = git_refspec::parse(spec.into(), git_refspec::parse::Operation::Fetch).unwrap();
         let group = git_refspec::MatchGroup::from_fetch_specs(Some(spec));
@@ -19,6 +19,12 @@ mod error {
         OpenWorktreeRepo(#[from] crate::open::Error),
         #[error("Could not find local commit for fast-forward ancestor check")]
         FindCommit(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        OpenPackedRefs(#[from] crate::reference::iter::Error),
+        #[error(transparent)]
+        IterateReferences(#[from] crate::reference::iter::init::Error),
+        #[error("Failed to read a tracking reference while looking for references to prune")]
+        ReadTrackingReference(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
     }
 }
 
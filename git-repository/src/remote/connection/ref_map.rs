@@ -34,6 +34,11 @@ pub struct Options {
     ///
     /// This is useful in case of custom servers.
     pub handshake_parameters: Vec<(String, Option<String>)>,
+    /// Additional prefixes like `HEAD` to let the server include refs not matched by any ref-spec, if
+    /// [`prefix_from_spec_as_filter_on_remote`][Self::prefix_from_spec_as_filter_on_remote] is set.
+    ///
+    /// These refs will be listed in [`remote_refs`][fetch::RefMap::remote_refs], but are never fetched unless they match a ref-spec.
+    pub extra_ref_prefixes: Vec<BString>,
 }
 
 impl Default for Options {
@@ -41,6 +46,7 @@ impl Default for Options {
         Options {
             prefix_from_spec_as_filter_on_remote: true,
             handshake_parameters: Default::default(),
+            extra_ref_prefixes: Default::default(),
         }
     }
 }
@@ -77,10 +83,15 @@ where
         Options {
             prefix_from_spec_as_filter_on_remote,
             handshake_parameters,
+            extra_ref_prefixes,
         }: Options,
     ) -> Result<fetch::RefMap, Error> {
         let remote = self
-            .fetch_refs(prefix_from_spec_as_filter_on_remote, handshake_parameters)
+            .fetch_refs(
+                prefix_from_spec_as_filter_on_remote,
+                handshake_parameters,
+                &extra_ref_prefixes,
+            )
             .await?;
         let group = git_refspec::MatchGroup::from_fetch_specs(self.remote.fetch_specs.iter().map(|s| s.to_ref()));
         let (res, fixes) = group
@@ -122,6 +133,7 @@ where
         &mut self,
         filter_by_prefix: bool,
         extra_parameters: Vec<(String, Option<String>)>,
+        extra_ref_prefixes: &[BString],
     ) -> Result<HandshakeWithRefs, Error> {
         let mut credentials_storage;
         let authenticate = match self.authenticate.as_mut() {
@@ -163,6 +175,11 @@ where
                                     }
                                 }
                             }
+                            for prefix in extra_ref_prefixes {
+                                let mut arg: BString = "ref-prefix ".into();
+                                arg.push_str(prefix);
+                                arguments.push(arg)
+                            }
                         }
                         Ok(git_protocol::fetch::delegate::LsRefsAction::Continue)
                     },
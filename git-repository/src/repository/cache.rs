@@ -7,6 +7,9 @@ impl crate::Repository {
     /// Well-chosen cache sizes can improve performance particularly if objects are accessed multiple times in a row.
     /// The cache is configured to grow gradually.
     ///
+    /// The cache is keyed by object id and independent of the pack delta cache. It's consulted by all object lookups, including
+    /// those performed when traversing or diffing trees, so repeatedly accessed trees don't have to be decoded from packs again.
+    ///
     /// Note that a cache on application level should be considered as well as the best object access is not doing one.
     pub fn object_cache_size(&mut self, bytes: impl Into<Option<usize>>) {
         let bytes = bytes.into();
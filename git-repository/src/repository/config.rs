@@ -6,7 +6,10 @@ use crate::{bstr::ByteSlice, config};
 impl crate::Repository {
     /// Return a snapshot of the configuration as seen upon opening the repository.
     pub fn config_snapshot(&self) -> config::Snapshot<'_> {
-        config::Snapshot { repo: self }
+        config::Snapshot {
+            repo: self,
+            trust_level: self.git_dir_trust(),
+        }
     }
 
     /// Return a mutable snapshot of the configuration as seen upon opening the repository, starting a transaction.
@@ -16,11 +16,10 @@ impl crate::Repository {
     /// There are various legitimate reasons for an object to not be present, which is why
     /// [`try_find_object(…)`][crate::Repository::try_find_object()] might be preferable instead.
     ///
-    /// # Important
+    /// # Buffer Usage
     ///
-    /// As a shared buffer is written to back the object data, the returned `ObjectRef` will prevent other
-    /// `find_object()` operations from succeeding while alive.
-    /// To bypass this limit, clone this `sync::Handle` instance.
+    /// Each returned object owns its data, backed by a buffer taken from a free-list of this instance, and returns it there
+    /// once dropped. Thus any amount of objects can be alive at the same time, while buffers are reused by subsequent lookups.
     ///
     /// # Performance Note
     ///
@@ -43,11 +42,9 @@ impl crate::Repository {
 
     /// Try to find the object with `id` or return `None` it it wasn't found.
     ///
-    /// # Important
+    /// # Buffer Usage
     ///
-    /// As a shared buffer is written to back the object data, the returned `ObjectRef` will prevent other
-    /// `try_find_object()` operations from succeeding while alive.
-    /// To bypass this limit, clone this `sync::Handle` instance.
+    /// Like [`find_object(…)`][crate::Repository::find_object()], any amount of the returned objects can be alive at the same time.
     pub fn try_find_object(&self, id: impl Into<ObjectId>) -> Result<Option<Object<'_>>, object::find::Error> {
         let id = id.into();
         if id == git_hash::ObjectId::empty_tree(self.object_hash()) {
@@ -69,6 +66,28 @@ impl crate::Repository {
         }
     }
 
+    /// Return `true` if the object with `id` is contained in the object database, without reading or decoding its data.
+    ///
+    /// Loose objects and pack indices of all alternates are consulted, with the latter being queried using their fan-out table.
+    /// Like [`find_object()`][crate::Repository::find_object()], the empty tree is always considered present.
+    pub fn has_object(&self, id: impl AsRef<oid>) -> bool {
+        let id = id.as_ref();
+        if id == git_hash::ObjectId::empty_tree(self.object_hash()) {
+            return true;
+        }
+        self.objects.contains(id)
+    }
+
+    /// Scan the object database for new or removed packs, and discard all cached objects.
+    ///
+    /// Call this after packs were added or removed by other means than this instance, like after receiving a pack or after
+    /// garbage collection, if the object database handle [never refreshes][git_odb::store::Handle::refresh_never()] on its own.
+    pub fn refresh_object_database(&mut self) -> Result<(), git_odb::store::load_index::Error> {
+        self.objects.store_ref().refresh()?;
+        self.objects.clear_caches();
+        Ok(())
+    }
+
     /// Write the given object into the object database and return its object id.
     pub fn write_object(&self, object: impl git_object::WriteTo) -> Result<Id<'_>, object::write::Error> {
         self.objects
@@ -1,4 +1,4 @@
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 
 use git_hash::ObjectId;
 use git_ref::{
@@ -211,6 +211,66 @@ impl crate::Repository {
             .ok_or(reference::find::existing::Error::NotFound)
     }
 
+    /// Find the reference that the partial `name` unambiguously refers to, like `main`, `v1.0` or `origin/main`, or return an error
+    /// if there is none or if it is ambiguous.
+    ///
+    /// An existing reference with exactly the given name is returned right away. Otherwise `name` is looked up with
+    /// `refs/`, `refs/tags/`, `refs/heads/` and `refs/remotes/` prepended, as well as `refs/remotes/<name>/HEAD`,
+    /// and [`Ambiguous`][reference::find::Error::Ambiguous] is returned if more than one of these exist, unless
+    /// [`options`][reference::find::Options::allow_ambiguous] permit to return the first match in that order
+    /// like [`find_reference(…)`][crate::Repository::find_reference()] does.
+    pub fn find_reference_by_prefix(
+        &self,
+        name: &crate::bstr::BStr,
+        options: reference::find::Options,
+    ) -> Result<Reference<'_>, reference::find::existing::Error> {
+        let partial: &PartialNameRef = name
+            .try_into()
+            .map_err(|err| reference::find::Error::from(git_ref::file::find::Error::from(err)))?;
+        let name = partial.as_bstr();
+        if let Some(r) = self.try_find_reference_exactly(name.into())? {
+            return Ok(r);
+        }
+
+        let mut matches = Vec::new();
+        for (prefix, suffix) in &[
+            ("refs/", ""),
+            ("refs/tags/", ""),
+            ("refs/heads/", ""),
+            ("refs/remotes/", ""),
+            ("refs/remotes/", "/HEAD"),
+        ] {
+            let mut candidate = BString::from(*prefix);
+            candidate.extend_from_slice(name);
+            candidate.extend_from_slice(suffix.as_bytes());
+            if let Some(r) = self.try_find_reference_exactly(candidate)? {
+                if options.allow_ambiguous {
+                    return Ok(r);
+                }
+                matches.push(r);
+            }
+        }
+        match matches.len() {
+            0 => Err(reference::find::existing::Error::NotFound),
+            1 => Ok(matches.pop().expect("one match")),
+            _ => Err(reference::find::Error::Ambiguous {
+                candidates: matches.into_iter().map(|r| r.inner.name).collect(),
+            }
+            .into()),
+        }
+    }
+
+    /// Return the reference with the given full `name` if it exists, without trying any other names.
+    fn try_find_reference_exactly(&self, name: BString) -> Result<Option<Reference<'_>>, reference::find::Error> {
+        let name = match FullName::try_from(name) {
+            Ok(name) => name,
+            Err(_) => return Ok(None),
+        };
+        Ok(self
+            .try_find_reference(name.as_ref())?
+            .filter(|r| r.name() == name.as_ref()))
+    }
+
     /// Return a platform for iterating references.
     ///
     /// Common kinds of iteration are [all][crate::reference::iter::Platform::all()] or [prefixed][crate::reference::iter::Platform::prefixed()]
@@ -240,4 +300,12 @@ impl crate::Repository {
             Err(err) => Err(err.into()),
         }
     }
+
+    /// Return `true` if garbage collection should be run as its thresholds, as configured by `options`, were exceeded.
+    ///
+    /// This is meant to be called after operations which add references or objects, like fetches or commits,
+    /// similar to how `git` would run `git gc --auto`.
+    pub fn should_auto_gc(&self, options: crate::gc::AutoOptions) -> bool {
+        self.refs.should_auto_pack_refs(options.loose_refs_limit)
+    }
 }
@@ -74,6 +74,17 @@ pub enum Error {
         desired: usize,
         available: usize,
     },
+    #[error(
+        "Reference {:?} has no ref-log entry at or before {}, the oldest entry is from {}",
+        reference.name.as_bstr(),
+        desired.format(git_date::time::format::ISO8601),
+        oldest.format(git_date::time::format::ISO8601)
+    )]
+    RefLogEntryOutOfRangeForDate {
+        reference: git_ref::Reference,
+        desired: git_date::Time,
+        oldest: git_date::Time,
+    },
     #[error(
         "Commit {oid} has {available} ancestors along the first parent and ancestor number {desired} is out of range"
     )]
@@ -102,3 +102,75 @@ fn clone_into_empty_directory_and_early_drop() -> crate::Result {
     assert!(!head.is_file(), "we cleanup if the clone isn't followed through");
     Ok(())
 }
+
+#[test]
+#[cfg(feature = "blocking-network-client")]
+fn fetch_and_checkout() -> crate::Result {
+    let tmp = git_testtools::tempfile::TempDir::new()?;
+    let dir = tmp.path().join("clone");
+    let mut prepare = git::prepare_clone(remote::repo("base").path(), &dir)?;
+    let (mut checkout, _out) =
+        prepare.fetch_then_checkout(git::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+    let (repo, out) = checkout.main_worktree(git::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+    drop(checkout);
+    drop(prepare);
+
+    let head = repo.head()?;
+    assert_eq!(
+        head.referent_name().expect("symbolic").as_bstr(),
+        "refs/heads/main",
+        "HEAD points to the same branch as the remote HEAD"
+    );
+    assert_eq!(
+        repo.head_id()?,
+        repo.find_reference("refs/remotes/origin/main")?.id(),
+        "the local branch was created from the remote tracking branch"
+    );
+    let branch = repo.config_snapshot();
+    assert_eq!(branch.string("branch.main.remote").as_deref(), Some("origin".into()));
+    assert_eq!(
+        branch.string("branch.main.merge").as_deref(),
+        Some("refs/heads/main".into())
+    );
+
+    assert_eq!(out.files_updated, 1);
+    assert_eq!(
+        std::fs::read(dir.join("file"))?,
+        b"g\nh\ni\nj\nd\ne\nf\nb\nc\na\n",
+        "the worktree matches the tree of HEAD"
+    );
+    let index = repo.open_index()?;
+    assert_eq!(index.entries().len(), 1, "the index was written as well");
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "blocking-network-client")]
+fn fetch_only_bare_sets_head_without_checkout() -> crate::Result {
+    let tmp = git_testtools::tempfile::TempDir::new()?;
+    let (repo, _out) = git::prepare_clone_bare(remote::repo("base").path(), tmp.path())?
+        .fetch_only(git::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+    assert_eq!(
+        repo.head()?.referent_name().expect("symbolic").as_bstr(),
+        "refs/heads/main"
+    );
+    assert!(repo.head_id().is_ok(), "the branch pointed to by HEAD exists");
+    assert!(!repo.index_path().is_file(), "bare repositories have no index");
+    Ok(())
+}
+
+#[test]
+fn clone_into_non_empty_directory_fails_and_leaves_it_alone() -> crate::Result {
+    let tmp = git_testtools::tempfile::TempDir::new()?;
+    let existing_file = tmp.path().join("existing");
+    std::fs::write(&existing_file, b"content")?;
+    for res in [
+        git::prepare_clone(remote::repo("base").path(), tmp.path()).map(|_| ()),
+        git::prepare_clone_bare(remote::repo("base").path(), tmp.path()).map(|_| ()),
+    ] {
+        assert!(matches!(res, Err(git::clone::prepare::Error::DirectoryNotEmpty { .. })));
+    }
+    assert!(existing_file.is_file(), "nothing was removed");
+    assert!(!tmp.path().join(".git").exists(), "nothing was created");
+    Ok(())
+}
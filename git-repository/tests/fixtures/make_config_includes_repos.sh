@@ -0,0 +1,43 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q on-main
+(cd on-main
+  git symbolic-ref HEAD refs/heads/main
+
+  cat <<EOF >>.git/config
+[a]
+  local = base
+  always = base
+  on-main = base
+[b]
+  string = base
+  integer = 1
+  boolean = false
+  implicit
+[include]
+  path = ../always.config
+[includeIf "onbranch:main"]
+  path = ../main.config
+EOF
+
+  cat <<EOF >>always.config
+[a]
+  always = from-always.config
+[b]
+  string = from-always.config
+  integer = 2
+  boolean = true
+  implicit = false
+EOF
+
+  cat <<EOF >>main.config
+[a]
+  on-main = from-main.config
+EOF
+)
+
+cp -R on-main on-other
+(cd on-other
+  git symbolic-ref HEAD refs/heads/other
+)
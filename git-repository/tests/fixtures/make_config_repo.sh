@@ -16,6 +16,8 @@ cat <<EOF >>.git/config
   single-string = hello world
   local-override = base
   env-override = base
+  implicit-bool
+  implicit-bool-override = false
 
 [include]
   path = ../a.config
@@ -29,6 +31,7 @@ EOF
 cat <<EOF >>a.config
 [a]
   local-override = from-a.config
+  implicit-bool-override
 
 [committer]
   name = committer
@@ -0,0 +1,10 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+
+git checkout -b main
+mkdir -p dir/sub
+touch a dir/b dir/sub/file z
+git add .
+git commit -q -m c1
@@ -31,6 +31,9 @@ mod bare {
 }
 
 mod non_bare {
+    use git_testtools::Env;
+    use serial_test::serial;
+
     #[test]
     fn init_into_empty_directory_creates_a_dot_git_dir() -> crate::Result {
         let tmp = tempfile::tempdir()?;
@@ -58,4 +61,83 @@ mod non_bare {
         git_repository::init(tmp.path())?;
         Ok(())
     }
+
+    #[test]
+    #[serial]
+    fn head_points_to_the_configured_default_branch() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let global_config = tmp.path().join("global.config");
+        std::fs::write(&global_config, b"[init]\n\tdefaultBranch = trunk\n")?;
+        let _env = Env::new()
+            .set("GIT_CONFIG_NO_SYSTEM", "1")
+            .set("GIT_CONFIG_GLOBAL", global_config.display().to_string());
+
+        let repo = git_repository::init(tmp.path().join("repo"))?;
+        assert_eq!(
+            repo.head_name()?.expect("symbolic").as_bstr(),
+            "refs/heads/trunk",
+            "init.defaultBranch is used for the initial branch"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn invalid_default_branch_names_are_ignored() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let global_config = tmp.path().join("global.config");
+        std::fs::write(&global_config, b"[init]\n\tdefaultBranch = in..valid\n")?;
+        let _env = Env::new()
+            .set("GIT_CONFIG_NO_SYSTEM", "1")
+            .set("GIT_CONFIG_GLOBAL", global_config.display().to_string());
+
+        let repo = git_repository::init(tmp.path().join("repo"))?;
+        assert_eq!(repo.head_name()?.expect("symbolic").as_bstr(), "refs/heads/master");
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn the_default_branch_is_only_read_from_configuration_permitted_by_the_open_options() -> crate::Result {
+        use git_repository::sec::trust::DefaultForLevel;
+        let tmp = tempfile::tempdir()?;
+        let global_config = tmp.path().join("global.config");
+        std::fs::write(&global_config, b"[init]\n\tdefaultBranch = trunk\n")?;
+        let _env = Env::new()
+            .set("GIT_CONFIG_NO_SYSTEM", "1")
+            .set("GIT_CONFIG_GLOBAL", global_config.display().to_string());
+
+        let mut open_options = git_repository::open::Options::default_for_level(git_repository::sec::Trust::Full);
+        open_options.permissions.config.git = false;
+        open_options.permissions.config.user = false;
+        let repo: git_repository::Repository = git_repository::ThreadSafeRepository::init_opts(
+            tmp.path().join("repo"),
+            git_repository::create::Options {
+                bare: false,
+                fs_capabilities: None,
+            },
+            open_options,
+        )?
+        .into();
+        assert_eq!(
+            repo.head_name()?.expect("symbolic").as_bstr(),
+            "refs/heads/master",
+            "the global configuration wasn't permitted"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn head_points_to_master_without_configured_default_branch() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let _env = Env::new().set("GIT_CONFIG_NO_SYSTEM", "1").set(
+            "GIT_CONFIG_GLOBAL",
+            tmp.path().join("missing.config").display().to_string(),
+        );
+
+        let repo = git_repository::init(tmp.path())?;
+        assert_eq!(repo.head_name()?.expect("symbolic").as_bstr(), "refs/heads/master");
+        Ok(())
+    }
 }
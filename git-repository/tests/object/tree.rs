@@ -84,3 +84,41 @@ mod diff {
             .into_tree()
     }
 }
+
+mod depthfirst_entries {
+    use std::convert::Infallible;
+
+    use crate::named_repo;
+
+    #[test]
+    fn entries_know_their_parent_path() -> crate::Result {
+        let repo = named_repo("make_nested_tree_repo.sh")?;
+        let tree = repo.head_commit()?.tree()?;
+        assert!(
+            tree.iter()
+                .all(|entry| entry.expect("valid").relative_path().is_empty()),
+            "non-recursive iteration has no parent path"
+        );
+
+        let mut paths = Vec::new();
+        tree.traverse().depthfirst_entries(|entry| {
+            paths.push((entry.relative_path().to_string(), entry.full_path().to_string()));
+            Ok::<_, Infallible>(())
+        })?;
+        assert_eq!(
+            paths,
+            [
+                ("", "a"),
+                ("", "dir"),
+                ("dir", "dir/b"),
+                ("dir", "dir/sub"),
+                ("dir/sub", "dir/sub/file"),
+                ("", "z"),
+            ]
+            .iter()
+            .map(|(parent, path)| (parent.to_string(), path.to_string()))
+            .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+}
@@ -3,6 +3,54 @@ use crate::repo_rw;
 mod log {
     use git_repository as git;
 
+    #[test]
+    fn iter_in_both_directions() -> crate::Result {
+        let (repo, _tmp) = crate::repo_rw("make_basic_repo.sh")?;
+        let c1 = repo.rev_parse_single(":/c1")?.detach();
+        let c2 = repo.rev_parse_single(":/c2")?.detach();
+        let mut r = repo.reference(
+            "refs/heads/reflog-test",
+            c1,
+            git::refs::transaction::PreviousValue::MustNotExist,
+            "first",
+        )?;
+        r.set_target_id(c2, "second")?;
+        r.set_target_id(c1, "third")?;
+
+        let mut log = r.log_iter();
+        let forward: Vec<_> = log
+            .all()?
+            .expect("log present")
+            .map(|line| line.map(|line| (line.previous_oid(), line.new_oid(), line.message.to_owned())))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(
+            forward,
+            vec![
+                (repo.object_hash().null(), c1, "first".into()),
+                (c1, c2, "second".into()),
+                (c2, c1, "third".into())
+            ],
+            "oldest to newest"
+        );
+
+        let messages: Vec<_> = log
+            .rev()?
+            .expect("log present")
+            .map(|line| line.map(|line| line.message))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(messages, vec!["third", "second", "first"], "newest to oldest");
+        Ok(())
+    }
+
+    #[test]
+    fn iter_without_reflog_yields_none() -> crate::Result {
+        let repo = crate::named_repo("make_references_repo.sh")?;
+        let r = repo.find_reference("dt1")?;
+        assert!(r.log_iter().all()?.is_none(), "packed refs don't have a reflog here");
+        assert!(r.log_iter().rev()?.is_none());
+        Ok(())
+    }
+
     #[test]
     fn message() {
         assert_eq!(
@@ -50,3 +50,108 @@ mod blocking_io {
         }
     }
 }
+
+#[cfg(feature = "blocking-http-transport")]
+mod http_options {
+    use crate::named_repo;
+
+    #[test]
+    fn ssl_options_are_read_from_configuration() -> crate::Result {
+        let mut repo = named_repo("make_basic_repo.sh")?;
+        {
+            let remote = repo.remote_at("https://example.com/repo.git")?;
+            let ssl = remote.http_ssl_options()?;
+            assert_eq!(ssl, Default::default(), "certificates are verified by default");
+            assert!(ssl.verify);
+        }
+        {
+            let mut config = repo.config_snapshot_mut();
+            config.set_raw_value("http", None, "sslVerify", "false")?;
+            config.set_raw_value("http", None, "sslCAInfo", "/certs/ca.pem")?;
+            config.set_raw_value("http", None, "sslCert", "/certs/client.pem")?;
+            config.set_raw_value("http", None, "sslKey", "/certs/client.key")?;
+        }
+        let remote = repo.remote_at("https://example.com/repo.git")?;
+        let ssl = remote.http_ssl_options()?;
+        assert!(!ssl.verify);
+        assert_eq!(ssl.ca_info.as_deref(), Some(std::path::Path::new("/certs/ca.pem")));
+        assert_eq!(
+            ssl.client_cert.as_deref(),
+            Some(std::path::Path::new("/certs/client.pem"))
+        );
+        assert_eq!(
+            ssl.client_key.as_deref(),
+            Some(std::path::Path::new("/certs/client.key"))
+        );
+        Ok(())
+    }
+}
+
+#[cfg(feature = "blocking-http-transport")]
+mod http_proxy {
+    use git_repository as git;
+    use serial_test::serial;
+
+    use crate::named_repo;
+
+    #[test]
+    #[serial]
+    fn proxy_configuration_overrides_the_environment() -> crate::Result {
+        let _env = git_testtools::Env::new()
+            .set("https_proxy", "http://env-proxy:8080")
+            .set("no_proxy", "internal.example.com");
+        let mut repo = named_repo("make_basic_repo.sh")?;
+        let remote = repo.remote_at("https://example.com/repo.git")?;
+        let options = remote.http_proxy_options(&git::url::Scheme::Https);
+        assert_eq!(
+            options.url.as_deref(),
+            Some("http://env-proxy:8080"),
+            "the environment is used without configuration"
+        );
+        assert_eq!(options.no_proxy, ["internal.example.com"]);
+
+        repo.config_snapshot_mut()
+            .set_raw_value("http", None, "proxy", "http://proxy:3128")?;
+        let remote = repo.remote_at("https://example.com/repo.git")?;
+        let options = remote.http_proxy_options(&git::url::Scheme::Https);
+        assert_eq!(options.url.as_deref(), Some("http://proxy:3128"));
+        assert_eq!(
+            options.no_proxy,
+            ["internal.example.com"],
+            "hosts to connect to directly still come from the environment"
+        );
+
+        repo.config_snapshot_mut().set_raw_value("http", None, "proxy", "")?;
+        let remote = repo.remote_at("https://example.com/repo.git")?;
+        assert_eq!(
+            remote.http_proxy_options(&git::url::Scheme::Https).url,
+            None,
+            "an empty proxy disables it"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn proxy_configuration_is_ignored_in_untrusted_files() -> crate::Result {
+        let _env = git_testtools::Env::new().set("https_proxy", "http://env-proxy:8080");
+        let (repo, _tmp) = crate::repo_rw("make_basic_repo.sh")?;
+        let mut config = std::fs::OpenOptions::new()
+            .append(true)
+            .open(repo.git_dir().join("config"))?;
+        std::io::Write::write_all(&mut config, b"[http]\n\tproxy = http://untrusted-proxy:3128\n")?;
+
+        for (trust, expected) in [
+            (git_sec::Trust::Full, "http://untrusted-proxy:3128"),
+            (git_sec::Trust::Reduced, "http://env-proxy:8080"),
+        ] {
+            let repo = git::open_opts(repo.git_dir(), repo.open_options().clone().with(trust))?;
+            let remote = repo.remote_at("https://example.com/repo.git")?;
+            assert_eq!(
+                remote.http_proxy_options(&git::url::Scheme::Https).url.as_deref(),
+                Some(expected)
+            );
+        }
+        Ok(())
+    }
+}
@@ -147,6 +147,26 @@ fn https_urls_match_the_host_without_path_as_well() {
     baseline::agrees_with("https://example.com:8080/path/");
 }
 
+#[test]
+fn overlapping_patterns_all_apply_and_the_more_specific_one_overrides_settings() {
+    use git_repository as git;
+    let repo = crate::remote::repo("credential-helpers");
+    let use_http_path = |url: &str| {
+        repo.config_snapshot()
+            .credential_helpers(git::url::parse(url.into()).expect("valid input URL"))
+            .expect("valid configuration")
+            .0
+            .use_http_path
+    };
+    assert!(
+        use_http_path("https://example.com:8080/path"),
+        "set only in the pattern with path, which overrides the default of the general host pattern"
+    );
+    assert!(!use_http_path("https://example.com:8080/other/path"));
+    assert!(!use_http_path("https://example.com:8080"));
+    baseline::agrees_with("https://example.com:8080/path");
+}
+
 #[test]
 fn empty_helper_clears_helper_list() {
     baseline::agrees_with("https://example.com:8080/clear");
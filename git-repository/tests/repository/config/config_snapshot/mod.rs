@@ -44,6 +44,94 @@ fn snapshot_mut_commit_and_forget() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn boolean_or_integer() -> crate::Result {
+    use git_repository::config::BooleanOrInteger;
+
+    let mut repo = named_repo("make_config_repo.sh")?;
+    {
+        let mut config = repo.config_snapshot_mut();
+        config.set_raw_value("a", None, "true", "true")?;
+        config.set_raw_value("a", None, "zero", "0")?;
+        config.set_raw_value("a", None, "four", "4")?;
+        config.set_raw_value("a", None, "invalid", "maybe")?;
+    }
+    let config = repo.config_snapshot();
+    assert_eq!(
+        config.boolean_or_integer("a.true"),
+        Some(BooleanOrInteger::Boolean(true))
+    );
+    assert_eq!(
+        config.boolean_or_integer("a.zero"),
+        Some(BooleanOrInteger::Integer(0)),
+        "numbers are integers, even though they could be booleans as well"
+    );
+    assert_eq!(config.boolean_or_integer("a.four"), Some(BooleanOrInteger::Integer(4)));
+    assert_eq!(
+        config.boolean_or_integer("core.bare"),
+        Some(BooleanOrInteger::Boolean(false))
+    );
+    assert!(config.try_boolean_or_integer("a.invalid").expect("present").is_err());
+    assert_eq!(config.boolean_or_integer("a.missing"), None);
+    Ok(())
+}
+
+#[test]
+fn integer_in_range() -> crate::Result {
+    use git_repository::config::RangeError;
+
+    let mut repo = named_repo("make_config_repo.sh")?;
+    {
+        let mut config = repo.config_snapshot_mut();
+        config.set_raw_value("core", None, "compression", "9")?;
+        config.set_raw_value("a", None, "too-large", "10")?;
+        config.set_raw_value("a", None, "invalid", "maybe")?;
+    }
+    let config = repo.config_snapshot();
+    assert_eq!(
+        config.integer_in_range("core.compression", -1..=9).expect("present")?,
+        9,
+        "the range is inclusive"
+    );
+    match config.integer_in_range("a.too-large", -1..=9).expect("present") {
+        Err(err @ RangeError::OutOfRange { .. }) => assert_eq!(
+            err.to_string(),
+            "Value 10 at key 'a.too-large' is out of range, it must be between -1 and 9"
+        ),
+        res => unreachable!("expected out of range error, got {:?}", res),
+    }
+    assert!(matches!(
+        config.integer_in_range("a.invalid", -1..=9),
+        Some(Err(RangeError::Decode(_)))
+    ));
+    assert!(config.integer_in_range("a.missing", -1..=9).is_none());
+    Ok(())
+}
+
+#[test]
+fn colors() -> crate::Result {
+    let mut repo = named_repo("make_config_repo.sh")?;
+    {
+        let mut config = repo.config_snapshot_mut();
+        config.set_raw_value("color", Some("diff"), "meta", "bold cyan")?;
+        config.set_raw_value("color", Some("diff"), "old", "red green blue")?;
+    }
+    let config = repo.config_snapshot();
+
+    let color = config.color("color.diff.meta").expect("present and valid");
+    assert_eq!(color.foreground, Some(git_repository::config::color::Name::Cyan));
+    assert_eq!(color.background, None);
+    assert_eq!(color.attributes, git_repository::config::color::Attribute::BOLD);
+    assert_eq!(config.try_color("color.diff.meta").transpose()?, Some(color));
+
+    assert_eq!(config.color("color.diff.old"), None, "at most two colors are allowed");
+    assert!(config.try_color("color.diff.old").expect("present").is_err());
+
+    assert_eq!(config.color("color.diff.new"), None);
+    assert!(config.try_color("color.diff.new").is_none());
+    Ok(())
+}
+
 #[test]
 fn values_are_set_in_memory_only() {
     let mut repo = named_repo("make_config_repo.sh").unwrap();
@@ -77,6 +165,33 @@ fn values_are_set_in_memory_only() {
     assert_eq!(repo_clone.config_snapshot().string(key_subsection), None);
 }
 
+#[test]
+fn only_section_and_value_names_are_case_insensitive() -> crate::Result {
+    let mut repo = named_repo("make_config_repo.sh")?;
+    {
+        let mut config = repo.config_snapshot_mut();
+        config.set_raw_value("core", None, "ignoreCase", "true")?;
+        config.set_raw_value("remote", Some("Origin"), "url", "upper")?;
+        config.set_raw_value("remote", Some("origin"), "url", "lower")?;
+    }
+
+    let config = repo.config_snapshot();
+    assert_eq!(config.boolean("core.ignoreCase"), Some(true));
+    assert_eq!(config.string("remote.Origin.url").expect("present").as_ref(), "upper");
+    assert_eq!(config.string("remote.origin.url").expect("present").as_ref(), "lower");
+    assert_eq!(
+        config.string("REMOTE.Origin.URL").expect("present").as_ref(),
+        "upper",
+        "section and value names are case-folded"
+    );
+    assert_eq!(
+        config.string("remote.ORIGIN.url"),
+        None,
+        "subsection names are compared exactly, even if core.ignoreCase is set"
+    );
+    Ok(())
+}
+
 #[test]
 fn apply_cli_overrides() -> crate::Result {
     let mut repo = named_repo("make_config_repo.sh").unwrap();
@@ -114,4 +229,94 @@ fn apply_cli_overrides() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn conditional_includes_on_branch_only_apply_on_that_branch() -> crate::Result {
+    let base = git_testtools::scripted_fixture_repo_read_only("make_config_includes_repos.sh")?;
+    let mut opts = crate::restricted();
+    opts.permissions.config.includes = true;
+
+    let repo = git_repository::open_opts(base.join("on-main"), opts.clone())?;
+    let config = repo.config_snapshot();
+    assert_eq!(config.string("a.local").expect("present").as_ref(), "base");
+    assert_eq!(
+        config.string("a.always").expect("present").as_ref(),
+        "from-always.config"
+    );
+    assert_eq!(
+        config.string("a.on-main").expect("present").as_ref(),
+        "from-main.config",
+        "the conditional include applies on the main branch"
+    );
+    assert_eq!(
+        config
+            .included_paths()
+            .iter()
+            .map(|p| p.file_name().expect("file name"))
+            .collect::<Vec<_>>(),
+        ["always.config", "main.config"]
+    );
+
+    let repo = git_repository::open_opts(base.join("on-other"), opts.clone())?;
+    let config = repo.config_snapshot();
+    assert_eq!(
+        config.string("a.always").expect("present").as_ref(),
+        "from-always.config"
+    );
+    assert_eq!(
+        config.string("a.on-main").expect("present").as_ref(),
+        "base",
+        "the conditional include doesn't apply on other branches"
+    );
+    assert_eq!(
+        config
+            .included_paths()
+            .iter()
+            .map(|p| p.file_name().expect("file name"))
+            .collect::<Vec<_>>(),
+        ["always.config"]
+    );
+
+    opts.permissions.config.includes = false;
+    let repo = git_repository::open_opts(base.join("on-main"), opts)?;
+    let config = repo.config_snapshot();
+    assert_eq!(config.string("a.on-main").expect("present").as_ref(), "base");
+    assert!(
+        config.included_paths().is_empty(),
+        "nothing is included if includes aren't permitted"
+    );
+    Ok(())
+}
+
+#[test]
+fn first_value_accessors_see_the_earliest_value_in_the_hierarchy() -> crate::Result {
+    let base = git_testtools::scripted_fixture_repo_read_only("make_config_includes_repos.sh")?;
+    let mut opts = crate::restricted();
+    opts.permissions.config.includes = true;
+
+    let repo = git_repository::open_opts(base.join("on-main"), opts)?;
+    let config = repo.config_snapshot();
+    assert_eq!(
+        config.string("b.string").expect("present").as_ref(),
+        "from-always.config"
+    );
+    assert_eq!(config.string_first("b.string").expect("present").as_ref(), "base");
+    assert_eq!(config.integer("b.integer"), Some(2));
+    assert_eq!(config.integer_first("b.integer"), Some(1));
+    assert_eq!(config.boolean("b.boolean"), Some(true));
+    assert_eq!(config.boolean_first("b.boolean"), Some(false));
+    assert_eq!(config.boolean("b.implicit"), Some(false));
+    assert_eq!(
+        config.boolean_first("b.implicit"),
+        Some(true),
+        "keys without value are implicitly true"
+    );
+    assert_eq!(
+        config.string_first("b.implicit"),
+        None,
+        "keys without value have no string"
+    );
+    assert_eq!(config.string_first("b.missing"), None);
+    Ok(())
+}
+
 mod credential_helpers;
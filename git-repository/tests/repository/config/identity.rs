@@ -62,26 +62,8 @@ fn author_and_committer_and_fallback() {
         }
 
         let config = repo.config_snapshot();
+        assert_eq!(config.trust_level(), trust);
 
-        assert_eq!(config.boolean("core.bare"), Some(false));
-        assert_eq!(config.boolean("a.bad-bool"), None);
-        assert_eq!(config.try_boolean("core.bare"), Some(Ok(false)));
-        assert!(matches!(config.try_boolean("a.bad-bool"), Some(Err(_))));
-
-        assert_eq!(config.integer("a.int"), Some(42));
-        assert_eq!(config.integer("a.int-overflowing"), None);
-        assert_eq!(config.integer("a.int-overflowing"), None);
-        assert!(config.try_integer("a.int-overflowing").expect("present").is_err());
-
-        assert_eq!(
-            config.string("a.single-string").expect("present").as_ref(),
-            "hello world"
-        );
-
-        assert_eq!(
-            config.string("a.local-override").expect("present").as_ref(),
-            "from-a.config"
-        );
         assert_eq!(
             config.string("a.system").expect("present").as_ref(),
             "from-system.config"
@@ -91,10 +73,62 @@ fn author_and_committer_and_fallback() {
             "from-b.config"
         );
 
-        assert_eq!(
-            config.string("a.env-override").expect("present").as_ref(),
-            "from-c.config"
-        );
+        if trust == git_sec::Trust::Full {
+            assert_eq!(config.boolean("core.bare"), Some(false));
+            assert_eq!(config.boolean("a.bad-bool"), None);
+            assert_eq!(config.try_boolean("core.bare"), Some(Ok(false)));
+            assert!(matches!(config.try_boolean("a.bad-bool"), Some(Err(_))));
+            assert_eq!(
+                config.try_boolean("a.implicit-bool"),
+                Some(Ok(true)),
+                "keys without value are true"
+            );
+            assert_eq!(
+                config.boolean("a.implicit-bool-override"),
+                Some(true),
+                "implicit values override explicit ones set earlier"
+            );
+            assert_eq!(config.boolean("a.missing-bool"), None, "absent keys have no value");
+
+            assert_eq!(config.integer("a.int"), Some(42));
+            assert_eq!(config.integer("a.int-overflowing"), None);
+            assert_eq!(config.integer("a.int-overflowing"), None);
+            assert!(config.try_integer("a.int-overflowing").expect("present").is_err());
+
+            assert_eq!(
+                config.string("a.single-string").expect("present").as_ref(),
+                "hello world"
+            );
+
+            assert_eq!(
+                config.string("a.local-override").expect("present").as_ref(),
+                "from-a.config"
+            );
+            assert_eq!(
+                config.string("a.env-override").expect("present").as_ref(),
+                "from-c.config"
+            );
+        } else {
+            assert_eq!(
+                config.boolean("core.bare"),
+                None,
+                "values from the repository configuration are ignored with reduced trust"
+            );
+            assert_eq!(config.try_boolean("a.bad-bool"), None);
+            assert_eq!(config.integer("a.int"), None);
+            assert_eq!(config.try_integer("a.int-overflowing"), None);
+            assert_eq!(config.string("a.single-string"), None);
+            assert_eq!(
+                config.string("a.local-override"),
+                None,
+                "this includes files included by the repository configuration"
+            );
+            assert_eq!(
+                config.string("a.env-override").expect("present").as_ref(),
+                "from-c.config",
+                "the environment is trusted"
+            );
+        }
 
         assert_eq!(config.boolean("core.missing"), None);
         assert_eq!(config.try_boolean("core.missing"), None);
@@ -93,6 +93,118 @@ mod find {
         Ok(())
     }
 
+    #[test]
+    fn has_object_checks_for_presence_including_alternates() -> crate::Result {
+        let repo = basic_repo()?;
+        let head_id = repo.head_id()?;
+        assert!(repo.has_object(head_id));
+        assert!(!repo.has_object(git::hash::ObjectId::from_hex(
+            b"badc0ffee0ddf00d0badc0ffee0ddf00d0badc0f"
+        )?));
+        assert!(
+            repo.has_object(git::hash::ObjectId::empty_tree(repo.object_hash())),
+            "the empty tree is always present, just like when finding it"
+        );
+
+        let repo = crate::remote::repo("clone");
+        assert!(
+            repo.git_dir().join("objects").join("info").join("alternates").is_file(),
+            "the clone borrows its objects"
+        );
+        assert!(
+            repo.has_object(repo.head_id()?),
+            "objects in alternates are found as well"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn objects_can_be_looked_up_while_others_are_alive() -> crate::Result {
+        let repo = basic_repo()?;
+        let commit = repo.head_commit()?;
+        let tree = repo.find_object(commit.tree_id()?)?.into_tree();
+        let mut num_entries = 0;
+        for entry in tree.iter() {
+            let entry = entry?;
+            let object = repo.find_object(entry.oid())?;
+            let same_object = repo.try_find_object(entry.oid())?.expect("present");
+            assert_eq!(
+                object.data, same_object.data,
+                "interleaved lookups don't share their data"
+            );
+            assert_eq!(object.kind == git_object::Kind::Tree, entry.mode().is_tree());
+            num_entries += 1;
+        }
+        assert!(num_entries > 0, "there was something to look up");
+        assert_eq!(
+            commit.id,
+            repo.head_id()?,
+            "the object we started out with is still intact"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn packs_added_by_others_are_found_after_refreshing_the_object_database() -> crate::Result {
+        let (_tmp, mut repo) = crate::repository::object::empty_bare_repo()?;
+        repo.objects.refresh_never();
+        let (kind, data) = (git_object::Kind::Blob, b"written by someone else".to_vec());
+        let id = {
+            let mut hasher = git_features::hash::hasher(repo.object_hash());
+            hasher.update(&git_object::encode::loose_header(kind, data.len()));
+            hasher.update(&data);
+            git::hash::ObjectId::from(hasher.digest())
+        };
+        assert!(!repo.has_object(id), "the object database is initialized without it");
+
+        git_pack::Bundle::write_objects_to_directory(
+            Some((id, kind, data)),
+            Some(repo.objects.store_ref().path().join("pack")),
+            git::progress::Discard,
+            &std::sync::atomic::AtomicBool::default(),
+            Default::default(),
+        )?;
+        assert!(
+            !repo.has_object(id),
+            "the new pack isn't picked up automatically as refreshes are disabled"
+        );
+
+        repo.refresh_object_database()?;
+        assert!(repo.has_object(id), "it's found after refreshing");
+        Ok(())
+    }
+
+    #[test]
+    fn trees_are_served_from_the_object_cache_once_decoded() -> crate::Result {
+        let (mut repo, _keep) = crate::basic_rw_repo()?;
+        repo.object_cache_size(128 * 1024);
+        let tree_id = repo.head_commit()?.tree_id()?.detach();
+        let entries = |repo: &git::Repository| -> crate::Result<Vec<_>> {
+            Ok(repo
+                .find_object(tree_id)?
+                .into_tree()
+                .iter()
+                .map(|e| e.map(|e| e.filename().to_owned()))
+                .collect::<Result<_, _>>()?)
+        };
+        let first = entries(&repo)?;
+
+        let hex = tree_id.to_hex().to_string();
+        std::fs::remove_file(repo.objects.store_ref().path().join(&hex[..2]).join(&hex[2..]))?;
+        assert_eq!(
+            entries(&repo)?,
+            first,
+            "the second decode hits the cache as the object isn't present anymore"
+        );
+
+        repo.object_cache_size(None);
+        assert!(
+            repo.try_find_object(tree_id)?.is_none(),
+            "without cache, the tree can't be found anymore"
+        );
+        Ok(())
+    }
+
     #[test]
     fn empty_tree_can_always_be_found() -> crate::Result {
         let repo = basic_repo()?;
@@ -152,9 +264,19 @@ mod commit {
 
     use crate::{freeze_time, restricted_and_git};
 
+    /// Make sure `init.defaultBranch` of the system or global configuration doesn't affect `git::init()`.
+    fn without_default_branch_configuration(tmp: &tempfile::TempDir) -> git_testtools::Env<'static> {
+        git_testtools::Env::new().set("GIT_CONFIG_NO_SYSTEM", "1").set(
+            "GIT_CONFIG_GLOBAL",
+            tmp.path().join("missing.config").display().to_string(),
+        )
+    }
+
     #[test]
+    #[serial_test::serial]
     fn parent_in_initial_commit_causes_failure() {
         let tmp = tempfile::tempdir().unwrap();
+        let _env = without_default_branch_configuration(&tmp);
         let repo = git::init(&tmp).unwrap();
         let empty_tree_id = repo.write_object(&git::objs::Tree::empty()).unwrap().detach();
         let err = repo
@@ -162,7 +284,7 @@ mod commit {
             .unwrap_err();
         assert_eq!(
             err.to_string(),
-            "Reference \"refs/heads/main\" was supposed to exist with value 4b825dc642cb6eb9a060e54bf8d69288fbee4904, but didn't.",
+            "Reference \"refs/heads/master\" was supposed to exist with value 4b825dc642cb6eb9a060e54bf8d69288fbee4904, but didn't.",
             "cannot provide parent id in initial commit"
         );
     }
@@ -170,8 +292,9 @@ mod commit {
     #[test]
     #[serial_test::serial]
     fn single_line_initial_commit_empty_tree_ref_nonexisting() -> crate::Result {
-        let _env = freeze_time();
         let tmp = tempfile::tempdir()?;
+        let _env = without_default_branch_configuration(&tmp);
+        let _time = freeze_time();
         let repo = git::open_opts(git::init(&tmp)?.path(), restricted_and_git())?;
         let empty_tree_id = repo.write_object(&git::objs::Tree::empty())?;
         let commit_id = repo.commit("HEAD", "initial", empty_tree_id, git::commit::NO_PARENT_IDS)?;
@@ -182,7 +305,11 @@ mod commit {
         );
 
         let head = repo.head()?.try_into_referent().expect("born");
-        assert_eq!(head.name().as_bstr(), "refs/heads/main", "'main' is the default name");
+        assert_eq!(
+            head.name().as_bstr(),
+            "refs/heads/master",
+            "'master' is the default name"
+        );
         assert_eq!(
             head.log_iter()
                 .rev()?
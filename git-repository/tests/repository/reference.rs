@@ -80,6 +80,77 @@ mod set_namespace {
     }
 }
 
+mod find_reference_by_prefix {
+    use git_repository as git;
+
+    fn repo() -> crate::Result<git::Repository> {
+        crate::named_repo("make_references_repo.sh")
+    }
+
+    #[test]
+    fn unambiguous_names() -> crate::Result {
+        let repo = repo()?;
+        for (partial, expected) in [
+            ("main", "refs/heads/main"),
+            ("refs/heads/main", "refs/heads/main"),
+            ("heads/main", "refs/heads/main"),
+            ("t1", "refs/tags/t1"),
+            ("origin/main", "refs/remotes/origin/main"),
+            ("origin", "refs/remotes/origin/HEAD"),
+        ] {
+            assert_eq!(
+                repo.find_reference_by_prefix(partial.into(), Default::default())?
+                    .name()
+                    .as_bstr(),
+                expected,
+                "{}",
+                partial
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn ambiguous_names() -> crate::Result {
+        let repo = repo()?;
+        let err = repo
+            .find_reference_by_prefix("dt1".into(), Default::default())
+            .unwrap_err();
+        match err {
+            git::reference::find::existing::Error::Find(git::reference::find::Error::Ambiguous { candidates }) => {
+                assert_eq!(
+                    candidates.iter().map(|n| n.as_bstr()).collect::<Vec<_>>(),
+                    ["refs/tags/dt1", "refs/heads/dt1"],
+                    "candidates are in lookup order"
+                );
+            }
+            err => panic!("unexpected error: {:?}", err),
+        }
+        assert!(
+            repo.find_reference("dt1").is_ok(),
+            "find_reference() picks the first match instead"
+        );
+        assert_eq!(
+            repo.find_reference_by_prefix("dt1".into(), git::reference::find::Options { allow_ambiguous: true })?
+                .name()
+                .as_bstr(),
+            "refs/tags/dt1",
+            "ambiguity can be allowed to pick the first match as well"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn missing_names() -> crate::Result {
+        let repo = repo()?;
+        assert!(matches!(
+            repo.find_reference_by_prefix("does-not-exist".into(), Default::default()),
+            Err(git::reference::find::existing::Error::NotFound)
+        ));
+        Ok(())
+    }
+}
+
 mod iter_references {
     use git_repository as git;
     use git_testtools::hex_to_id;
@@ -224,3 +295,26 @@ mod head {
         Ok(())
     }
 }
+
+mod should_auto_gc {
+    use git_repository as git;
+
+    #[test]
+    fn loose_refs_limit() -> crate::Result {
+        let repo = crate::repo("make_references_repo.sh")?.to_thread_local();
+        let loose_refs = repo.refs.loose_iter()?.count();
+        assert!(loose_refs > 1, "need some loose references to test with");
+
+        assert!(repo.should_auto_gc(git::gc::AutoOptions {
+            loose_refs_limit: loose_refs - 1
+        }));
+        assert!(!repo.should_auto_gc(git::gc::AutoOptions {
+            loose_refs_limit: loose_refs
+        }));
+        assert!(
+            !repo.should_auto_gc(git::gc::AutoOptions { loose_refs_limit: 0 }),
+            "a limit of 0 disables the check"
+        );
+        Ok(())
+    }
+}
@@ -214,6 +214,83 @@ fn from_nonbare_parent_repo() {
     run_assertions(repo, false /* bare */);
 }
 
+#[test]
+fn linked_worktree_opened_by_its_checkout_shares_refs_and_config_with_the_main_repo() -> crate::Result {
+    if git_testtools::should_skip_as_git_version_is_smaller_than(2, 31, 0) {
+        return Ok(());
+    }
+    let dir = git_testtools::scripted_fixture_repo_writable_with_args(
+        "make_worktree_repo.sh",
+        None::<String>,
+        git_testtools::Creation::ExecuteScript,
+    )?;
+    let main_repo = git::open(dir.path().join("repo"))?;
+    let repo = git::open(dir.path().join("wt-a"))?;
+
+    let common_dir = main_repo.git_dir().canonicalize()?;
+    assert_eq!(
+        repo.git_dir().canonicalize()?,
+        common_dir.join("worktrees").join("wt-a"),
+        "the '.git' file of the worktree is followed to its private git dir"
+    );
+    assert_eq!(repo.common_dir().canonicalize()?, common_dir);
+    assert_eq!(
+        repo.work_dir().expect("non-bare").canonicalize()?,
+        dir.path().join("wt-a").canonicalize()?
+    );
+
+    assert_eq!(
+        repo.head_name()?.expect("not detached").as_bstr(),
+        "refs/heads/wt-a",
+        "HEAD is private to the worktree"
+    );
+    assert_eq!(
+        main_repo.head_name()?.expect("not detached").as_bstr(),
+        "refs/heads/main"
+    );
+    assert_eq!(
+        repo.find_reference("refs/heads/main")?.id(),
+        main_repo.head_id()?,
+        "branches are shared"
+    );
+    assert_eq!(
+        repo.config_snapshot().boolean("core.bare"),
+        Some(false),
+        "configuration is read from the common dir"
+    );
+
+    let id = repo.head_id()?.detach();
+    repo.reference(
+        "refs/tags/from-worktree",
+        id,
+        git_ref::transaction::PreviousValue::MustNotExist,
+        "",
+    )?;
+    repo.reference(
+        "refs/bisect/bad",
+        id,
+        git_ref::transaction::PreviousValue::MustNotExist,
+        "",
+    )?;
+    assert!(
+        common_dir.join("refs/tags/from-worktree").is_file(),
+        "tags are written to the common dir"
+    );
+    assert!(
+        main_repo.try_find_reference("refs/tags/from-worktree")?.is_some(),
+        "and thus visible to all worktrees"
+    );
+    assert!(
+        repo.git_dir().join("refs/bisect/bad").is_file(),
+        "bisect refs are written to the private git dir of the worktree"
+    );
+    assert!(
+        main_repo.try_find_reference("refs/bisect/bad")?.is_none(),
+        "and thus invisible to other worktrees"
+    );
+    Ok(())
+}
+
 fn run_assertions(main_repo: git::Repository, should_be_bare: bool) {
     assert_eq!(main_repo.is_bare(), should_be_bare);
     let mut baseline = Baseline::collect(
@@ -76,10 +76,55 @@ fn by_index() {
 }
 
 #[test]
-fn by_date_is_planned_until_git_date_crate_is_implements_parsing() {
-    let repo = repo("complex_graph").unwrap();
+fn by_index_of_head() {
+    let repo = &repo("complex_graph").unwrap();
+    for (spec, expected) in [
+        ("HEAD@{0}", "55e825ebe8fd2ff78cad3826afb696b96b576a7e"),
+        ("HEAD@{1}", "5b3f9e24965d0b28780b7ce5daf2b5b7f7e0459f"),
+    ] {
+        assert_eq!(
+            parse_spec_no_baseline(spec, repo).unwrap(),
+            Spec::from_id(hex_to_id(expected).attach(repo))
+        );
+    }
     assert!(matches!(
-        parse_spec_no_baseline("main@{1979-02-26 18:30:00}", &repo).unwrap_err(),
-        Error::Planned { .. }
+        parse_spec_no_baseline("HEAD@{12345}", repo).unwrap_err(),
+        Error::RefLogEntryOutOfRange {
+            desired: 12345,
+            available,
+            ..
+        } if available > 0
     ));
 }
+
+#[test]
+fn by_date() {
+    let repo = &repo("complex_graph").unwrap();
+    for (spec, expected) in [
+        (
+            "main@{Thu, 07 Apr 2005 22:22:13 +0000}",
+            "55e825ebe8fd2ff78cad3826afb696b96b576a7e",
+        ),
+        (
+            "main@{Thu, 07 Apr 2005 22:20:00 +0000}",
+            "a8fbd8e0c8753ef07ccdbfa93da030d53b68f0bc",
+        ),
+        (
+            "main@{Thu, 07 Apr 2005 22:13:13 +0000}",
+            "9f9eac6bd1cd4b4cc6a494f044b28c985a22972b",
+        ),
+    ] {
+        let spec = parse_spec_no_baseline(spec, repo).unwrap();
+        assert_eq!(spec, Spec::from_id(hex_to_id(expected).attach(repo)));
+        assert_eq!(spec.first_reference().expect("set").name.as_bstr(), "refs/heads/main");
+    }
+
+    // Known deviation: git warns and resolves to the oldest entry instead.
+    assert_eq!(
+        parse_spec_no_baseline("main@{2001-01-01}", repo)
+            .unwrap_err()
+            .to_string(),
+        "Reference \"refs/heads/main\" has no ref-log entry at or before 2001-01-01 00:00:00 +0000, the oldest entry is from 2005-04-07 22:13:13 -0700",
+        "dates before the oldest entry can't be resolved"
+    );
+}
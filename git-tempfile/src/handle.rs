@@ -54,17 +54,15 @@ impl Handle<()> {
         directory: ContainingDirectory,
         cleanup: AutoRemove,
         mode: Mode,
+        permissions: Option<u32>,
     ) -> io::Result<usize> {
         let containing_directory = directory.resolve(containing_directory.as_ref())?;
+        let tempfile = NamedTempFile::new_in(containing_directory)?;
+        if let Some(permissions) = permissions {
+            set_permissions(tempfile.as_file(), permissions)?;
+        }
         let id = NEXT_MAP_INDEX.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-        expect_none(REGISTER.insert(
-            id,
-            Some(ForksafeTempfile::new(
-                NamedTempFile::new_in(containing_directory)?,
-                cleanup,
-                mode,
-            )),
-        ));
+        expect_none(REGISTER.insert(id, Some(ForksafeTempfile::new(tempfile, cleanup, mode))));
         Ok(id)
     }
 }
@@ -114,7 +112,30 @@ impl Handle<Writable> {
         cleanup: AutoRemove,
     ) -> io::Result<Self> {
         Ok(Handle {
-            id: Handle::<()>::new_writable_inner(containing_directory, directory, cleanup, Mode::Writable)?,
+            id: Handle::<()>::new_writable_inner(containing_directory, directory, cleanup, Mode::Writable, None)?,
+            _marker: Default::default(),
+        })
+    }
+
+    /// Like [`new()`][Handle::new()], but set the `permissions` of the temporary file right after its creation and before
+    /// it is registered, independently of the process umask.
+    ///
+    /// On unix, `permissions` are the mode bits, like `0o600` to assure only the current user can read and write the file.
+    /// On other platforms, including windows, `permissions` are ignored and the file is created with default permissions.
+    pub fn new_with_permissions(
+        containing_directory: impl AsRef<Path>,
+        directory: ContainingDirectory,
+        cleanup: AutoRemove,
+        permissions: u32,
+    ) -> io::Result<Self> {
+        Ok(Handle {
+            id: Handle::<()>::new_writable_inner(
+                containing_directory,
+                directory,
+                cleanup,
+                Mode::Writable,
+                Some(permissions),
+            )?,
             _marker: Default::default(),
         })
     }
@@ -176,6 +197,17 @@ impl Handle<Writable> {
     }
 }
 
+#[cfg(unix)]
+fn set_permissions(file: &std::fs::File, permissions: u32) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    file.set_permissions(std::fs::Permissions::from_mode(permissions))
+}
+
+#[cfg(not(unix))]
+fn set_permissions(_file: &std::fs::File, _permissions: u32) -> io::Result<()> {
+    Ok(())
+}
+
 mod io_impls {
     use std::{io, io::SeekFrom};
 
@@ -268,6 +300,62 @@ pub mod persist {
                 Some(Ok(None)) => unreachable!("no open files in an open handle"),
             }
         }
+
+        /// Like [`persist()`][Handle::<Writable>::persist()], but assure the data written so far is durable and matches
+        /// `expected` before renaming the tempfile to `path`.
+        ///
+        /// For this, all data is flushed and synced to disk, and `hash(content)` is called with a reader over the whole file
+        /// to compute its hash, which is expected to use the same kind of hash as `expected`. Only if both hashes match the
+        /// tempfile is persisted, otherwise an error of kind [`InvalidData`][std::io::ErrorKind::InvalidData] is returned
+        /// along with the handle to the tempfile.
+        ///
+        /// The caller provides the hash implementation to avoid choosing one on their behalf.
+        ///
+        /// This protects against data silently being corrupted between writing and syncing it, which may happen on filesystems
+        /// with weak durability guarantees.
+        pub fn persist_with_fsync_and_checksum(
+            mut self,
+            path: impl AsRef<Path>,
+            expected: &git_hash::oid,
+            hash: impl FnOnce(&mut dyn std::io::Read) -> std::io::Result<git_hash::ObjectId>,
+        ) -> Result<Option<std::fs::File>, Error<Writable>> {
+            let actual = match self
+                .with_mut(|tempfile| sync_and_hash(tempfile.as_file_mut(), hash))
+                .and_then(|res| res)
+            {
+                Ok(actual) => actual,
+                Err(err) => {
+                    return Err(Error {
+                        error: err,
+                        handle: self,
+                    })
+                }
+            };
+            if actual != expected {
+                return Err(Error {
+                    error: std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!(
+                            "The tempfile was expected to hash to {}, but it hashed to {} after syncing it",
+                            expected, actual
+                        ),
+                    ),
+                    handle: self,
+                });
+            }
+            self.persist(path)
+        }
+    }
+
+    fn sync_and_hash(
+        file: &mut std::fs::File,
+        hash: impl FnOnce(&mut dyn std::io::Read) -> std::io::Result<git_hash::ObjectId>,
+    ) -> std::io::Result<git_hash::ObjectId> {
+        use std::io::{Seek, SeekFrom, Write};
+        file.flush()?;
+        file.sync_all()?;
+        file.seek(SeekFrom::Start(0))?;
+        hash(file)
     }
 
     impl Handle<Closed> {
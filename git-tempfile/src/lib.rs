@@ -162,6 +162,19 @@ pub fn new(
     Handle::<Writable>::new(containing_directory, directory, cleanup)
 }
 
+/// A shortcut to [`Handle::<Writable>::new_with_permissions()`], creating a writable temporary file with non-clashing name in a
+/// directory, and setting the `permissions` of the file right after its creation.
+///
+/// Note that `permissions` are only applied on unix and ignored everywhere else, which includes windows.
+pub fn new_with_permissions(
+    containing_directory: impl AsRef<Path>,
+    directory: ContainingDirectory,
+    cleanup: AutoRemove,
+    permissions: u32,
+) -> io::Result<Handle<Writable>> {
+    Handle::<Writable>::new_with_permissions(containing_directory, directory, cleanup, permissions)
+}
+
 /// A shortcut to [`Handle::<Writable>::at()`] providing a writable temporary file at the given path.
 pub fn writable_at(
     path: impl AsRef<Path>,
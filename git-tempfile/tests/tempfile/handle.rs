@@ -195,6 +195,25 @@ mod new {
         Ok(())
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn it_can_be_created_with_exact_permissions() -> crate::Result {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir()?;
+        for permissions in [0o600, 0o640, 0o644] {
+            let mut tempfile = git_tempfile::new_with_permissions(
+                dir.path(),
+                ContainingDirectory::Exists,
+                AutoRemove::Tempfile,
+                permissions,
+            )?;
+            let mode = tempfile.with_mut(|tf| tf.as_file().metadata())??.permissions().mode();
+            assert_eq!(mode & 0o777, permissions, "the umask has no effect");
+        }
+        assert_eq!(filecount_in(&dir), 0, "tempfiles are removed as usual");
+        Ok(())
+    }
+
     #[test]
     fn it_is_removed_if_it_goes_out_of_scope() -> crate::Result {
         let dir = tempfile::tempdir()?;
@@ -242,3 +261,63 @@ mod new {
         Ok(())
     }
 }
+
+mod persist_with_fsync_and_checksum {
+    use std::io::{ErrorKind, Seek, SeekFrom, Write};
+
+    use git_tempfile::{AutoRemove, ContainingDirectory};
+
+    fn hello_world_sha1() -> git_hash::ObjectId {
+        git_hash::ObjectId::from_hex(b"2aae6c35c94fcfb415dbe95f408b9ce91ee846ed").expect("valid hex")
+    }
+
+    fn sha1(content: &mut dyn std::io::Read) -> std::io::Result<git_hash::ObjectId> {
+        let mut buf = Vec::new();
+        content.read_to_end(&mut buf)?;
+        let mut hasher = git_features::hash::hasher(git_hash::Kind::Sha1);
+        hasher.update(&buf);
+        Ok(hasher.digest().into())
+    }
+
+    #[test]
+    fn it_persists_the_tempfile_if_its_hash_matches() -> crate::Result {
+        let dir = tempfile::tempdir()?;
+        let new_filename = dir.path().join("file.ext");
+        let mut handle = git_tempfile::new(dir.path(), ContainingDirectory::Exists, AutoRemove::Tempfile)?;
+        handle.write_all(b"hello world")?;
+
+        let file = handle
+            .persist_with_fsync_and_checksum(&new_filename, &hello_world_sha1(), sha1)?
+            .expect("not interrupted");
+        drop(file);
+        assert_eq!(std::fs::read(&new_filename)?, &b"hello world"[..]);
+        assert_eq!(std::fs::read_dir(dir.path())?.count(), 1, "the tempfile was renamed");
+        Ok(())
+    }
+
+    #[test]
+    fn it_keeps_the_tempfile_if_its_hash_does_not_match() -> crate::Result {
+        let dir = tempfile::tempdir()?;
+        let new_filename = dir.path().join("file.ext");
+        let mut handle = git_tempfile::new(dir.path(), ContainingDirectory::Exists, AutoRemove::Tempfile)?;
+        handle.write_all(b"hello world!")?;
+
+        let err = handle
+            .persist_with_fsync_and_checksum(&new_filename, &hello_world_sha1(), sha1)
+            .expect_err("the content doesn't match");
+        assert_eq!(err.error.kind(), ErrorKind::InvalidData);
+        assert!(!new_filename.exists(), "nothing was renamed into place");
+
+        let mut handle = err.handle;
+        handle.with_mut(|file| file.as_file_mut().set_len(0))??;
+        handle.seek(SeekFrom::Start(0))?;
+        handle.write_all(b"hello world")?;
+        drop(handle.persist_with_fsync_and_checksum(&new_filename, &hello_world_sha1(), sha1)?);
+        assert_eq!(
+            std::fs::read(&new_filename)?,
+            &b"hello world"[..],
+            "the handle can be used to try again"
+        );
+        Ok(())
+    }
+}
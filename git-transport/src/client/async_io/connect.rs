@@ -1,10 +1,13 @@
-pub use crate::client::non_io_types::connect::Error;
+pub use crate::client::non_io_types::connect::{Error, Options};
 
 #[cfg(any(feature = "async-std"))]
 pub(crate) mod function {
     use std::convert::TryInto;
 
-    use crate::client::{git, non_io_types::connect::Error};
+    use crate::client::{
+        git,
+        non_io_types::connect::{Error, Options},
+    };
 
     /// A general purpose connector connecting to a repository identified by the given `url`.
     ///
@@ -12,9 +15,11 @@ pub(crate) mod function {
     /// [git daemons][crate::client::git::connect()] only at the moment.
     ///
     /// Use `desired_version` to set the desired protocol version to use when connecting, but note that the server may downgrade it.
+    /// `options` configure timeouts, as far as they are supported by the transport.
     pub async fn connect<Url, E>(
         url: Url,
         desired_version: crate::Protocol,
+        options: Options,
     ) -> Result<Box<dyn crate::client::Transport + Send>, Error>
     where
         Url: TryInto<git_url::Url, Error = E>,
@@ -36,6 +41,7 @@ pub(crate) mod function {
                         url.port,
                         path,
                         desired_version,
+                        options,
                     )
                     .await
                     .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?,
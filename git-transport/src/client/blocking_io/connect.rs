@@ -1,9 +1,12 @@
-pub use crate::client::non_io_types::connect::Error;
+pub use crate::client::non_io_types::connect::{Error, Options};
 
 pub(crate) mod function {
     use std::convert::TryInto;
 
-    use crate::client::{non_io_types::connect::Error, Transport};
+    use crate::client::{
+        non_io_types::connect::{Error, Options},
+        Transport,
+    };
 
     /// A general purpose connector connecting to a repository identified by the given `url`.
     ///
@@ -14,7 +17,12 @@ pub(crate) mod function {
     /// and if compiled in connections to [git repositories over https][crate::client::http::connect()].
     ///
     /// Use `desired_version` to set the desired protocol version to use when connecting, but note that the server may downgrade it.
-    pub fn connect<Url, E>(url: Url, desired_version: crate::Protocol) -> Result<Box<dyn Transport + Send>, Error>
+    /// `options` configure timeouts, as far as they are supported by the transport.
+    pub fn connect<Url, E>(
+        url: Url,
+        desired_version: crate::Protocol,
+        options: Options,
+    ) -> Result<Box<dyn Transport + Send>, Error>
     where
         Url: TryInto<git_url::Url, Error = E>,
         git_url::parse::Error: From<E>,
@@ -42,6 +50,7 @@ pub(crate) mod function {
                     desired_version,
                     url.user(),
                     url.port,
+                    options,
                 )
                 .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?
             }),
@@ -59,6 +68,7 @@ pub(crate) mod function {
                         path,
                         desired_version,
                         url.port,
+                        options,
                     )
                     .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?
                 })
@@ -66,10 +76,11 @@ pub(crate) mod function {
             #[cfg(not(any(feature = "http-client-curl", feature = "http-client-reqwest")))]
             git_url::Scheme::Https | git_url::Scheme::Http => return Err(Error::CompiledWithoutHttp(url.scheme)),
             #[cfg(any(feature = "http-client-curl", feature = "http-client-reqwest"))]
-            git_url::Scheme::Https | git_url::Scheme::Http => Box::new(crate::client::http::connect(
-                &url.to_bstring().to_string(),
-                desired_version,
-            )),
+            git_url::Scheme::Https | git_url::Scheme::Http => Box::new({
+                let mut transport = crate::client::http::connect(&url.to_bstring().to_string(), desired_version);
+                crate::client::TransportWithoutIO::configure(&mut transport, &options)?;
+                transport
+            }),
         })
     }
 }
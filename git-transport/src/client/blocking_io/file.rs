@@ -38,7 +38,7 @@ pub struct SpawnProcessOnDemand {
     url: git_url::Url,
     pub(crate) path: BString,
     ssh_program: Option<String>,
-    ssh_args: Vec<String>,
+    pub(crate) ssh_args: Vec<String>,
     ssh_env: Vec<(&'static str, String)>,
     connection: Option<git::Connection<process::ChildStdout, process::ChildStdin>>,
     child: Option<process::Child>,
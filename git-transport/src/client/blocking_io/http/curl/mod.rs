@@ -14,6 +14,9 @@ pub struct Curl {
     req: SyncSender<remote::Request>,
     res: Receiver<remote::Response>,
     handle: Option<thread::JoinHandle<Result<(), curl::Error>>>,
+    config: crate::client::connect::Options,
+    proxy: Option<http::proxy::Options>,
+    ssl: http::ssl::Options,
 }
 
 impl Curl {
@@ -48,6 +51,9 @@ impl Curl {
                 url: url.to_owned(),
                 headers: list,
                 upload,
+                config: self.config,
+                proxy: http::proxy_for_url(self.proxy.as_ref(), url),
+                ssl: self.ssl.clone(),
             })
             .is_err()
         {
@@ -76,6 +82,9 @@ impl Default for Curl {
             handle: Some(handle),
             req,
             res,
+            config: Default::default(),
+            proxy: None,
+            ssl: Default::default(),
         }
     }
 }
@@ -102,7 +111,14 @@ impl http::Http for Curl {
         self.make_request(url, headers, true)
     }
 
-    fn configure(&mut self, _config: &dyn std::any::Any) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+    fn configure(&mut self, config: &dyn std::any::Any) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+        if let Some(config) = config.downcast_ref::<crate::client::connect::Options>() {
+            self.config = *config;
+        } else if let Some(proxy) = config.downcast_ref::<http::proxy::Options>() {
+            self.proxy = Some(proxy.clone());
+        } else if let Some(ssl) = config.downcast_ref::<http::ssl::Options>() {
+            self.ssl = ssl.clone();
+        }
         Ok(())
     }
 }
@@ -92,6 +92,10 @@ pub struct Request {
     pub url: String,
     pub headers: curl::easy::List,
     pub upload: bool,
+    pub config: crate::client::connect::Options,
+    /// The proxy to use, with `Some(None)` meaning to not use any proxy, and `None` meaning to use the defaults.
+    pub proxy: Option<Option<String>>,
+    pub ssl: http::ssl::Options,
 }
 
 pub struct Response {
@@ -110,18 +114,49 @@ pub fn new() -> (
     let handle = std::thread::spawn(move || -> Result<(), curl::Error> {
         let mut handle = Easy2::new(Handler::default());
 
-        for Request { url, headers, upload } in req_recv {
+        for Request {
+            url,
+            headers,
+            upload,
+            config,
+            proxy,
+            ssl,
+        } in req_recv
+        {
             handle.url(&url)?;
 
             // GitHub sends 'chunked' to avoid unknown clients to choke on the data, I suppose
             handle.post(upload)?;
             handle.http_headers(headers)?;
             handle.transfer_encoding(false)?;
-            handle.connect_timeout(Duration::from_secs(20))?;
-            // handle.proxy("http://localhost:9090")?; // DEBUG
-            let low_bytes_per_second = 1024;
-            handle.low_speed_limit(low_bytes_per_second)?;
-            handle.low_speed_time(Duration::from_secs(20))?;
+            handle.connect_timeout(config.connect_timeout.unwrap_or(Duration::from_secs(20)))?;
+            if let Some(proxy) = proxy {
+                // An empty proxy disables the proxy, even if configured in the environment.
+                handle.proxy(proxy.as_deref().unwrap_or_default())?;
+            }
+            handle.ssl_verify_peer(ssl.verify)?;
+            handle.ssl_verify_host(ssl.verify)?;
+            if let Some(ca_info) = ssl.ca_info {
+                handle.cainfo(ca_info)?;
+            }
+            if let Some(cert) = ssl.client_cert {
+                handle.ssl_cert(cert)?;
+            }
+            if let Some(key) = ssl.client_key {
+                handle.ssl_key(key)?;
+            }
+            match config.read_timeout {
+                Some(timeout) => {
+                    // Abort only if the transfer stalls completely for the given time.
+                    handle.low_speed_limit(1)?;
+                    handle.low_speed_time(timeout)?;
+                }
+                None => {
+                    let low_bytes_per_second = 1024;
+                    handle.low_speed_limit(low_bytes_per_second)?;
+                    handle.low_speed_time(Duration::from_secs(20))?;
+                }
+            }
 
             let (receive_data, receive_headers, send_body) = {
                 let handler = handle.get_mut();
@@ -148,7 +183,12 @@ pub fn new() -> (
             if let Err(err) = handle.perform() {
                 let handler = handle.get_mut();
                 handler.reset();
-                let err = Err(io::Error::new(io::ErrorKind::Other, err));
+                let kind = if err.is_operation_timedout() {
+                    io::ErrorKind::TimedOut
+                } else {
+                    io::ErrorKind::Other
+                };
+                let err = Err(io::Error::new(kind, err));
                 handler.receive_body.take();
                 match (handler.send_header.take(), handler.send_data.take()) {
                     (Some(header), mut data) => {
@@ -21,6 +21,12 @@ mod reqwest;
 ///
 mod traits;
 
+///
+pub mod proxy;
+
+///
+pub mod ssl;
+
 /// The http client configuration when using reqwest
 #[cfg(feature = "http-client-reqwest")]
 pub type Options = reqwest::Options;
@@ -118,6 +124,18 @@ fn append_url(base: &str, suffix: &str) -> String {
     buf
 }
 
+/// Return the proxy URL `options` suggest for use with `url`, or `None` to connect directly, or `None` in the outer option
+/// if there are no options at all.
+#[cfg(any(feature = "http-client-curl", feature = "http-client-reqwest"))]
+fn proxy_for_url(options: Option<&proxy::Options>, url: &str) -> Option<Option<String>> {
+    options.map(|options| {
+        let host = git_url::parse(url.into())
+            .ok()
+            .and_then(|url| url.host().map(ToOwned::to_owned));
+        options.proxy_for(host.as_deref()).map(ToOwned::to_owned)
+    })
+}
+
 impl<H: Http> client::TransportWithoutIO for Transport<H> {
     fn set_identity(&mut self, identity: git_sec::identity::Account) -> Result<(), client::Error> {
         self.identity = Some(identity);
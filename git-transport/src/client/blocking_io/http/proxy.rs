@@ -0,0 +1,85 @@
+/// Options to configure the proxy used by the http transport, passed to [`configure()`][crate::client::TransportWithoutIO::configure()].
+///
+/// If no proxy options are configured, the default of the http implementation applies, which may also take the environment
+/// into account. Once configured, only these options are used.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// The proxy to connect through, like `http://proxy:3128` or `socks5://proxy:1080`, or `None` to connect directly.
+    ///
+    /// Note that `socks5` proxies are only supported by the `curl` based implementation.
+    pub url: Option<String>,
+    /// Host patterns for which to connect directly even if `url` is set, as parsed by [`parse_no_proxy()`].
+    pub no_proxy: Vec<String>,
+}
+
+impl Options {
+    /// Obtain the proxy configuration from the `https_proxy` or `HTTPS_PROXY` variables if `https` is true, or from
+    /// `http_proxy` or `HTTP_PROXY` otherwise, along with hosts to exclude from `no_proxy` or `NO_PROXY`.
+    ///
+    /// Empty values are treated as if they were not set.
+    pub fn from_environment(https: bool) -> Self {
+        let var = |names: &[&str]| {
+            names
+                .iter()
+                .find_map(|name| std::env::var(name).ok().filter(|value| !value.trim().is_empty()))
+        };
+        Options {
+            url: if https {
+                var(&["https_proxy", "HTTPS_PROXY"])
+            } else {
+                var(&["http_proxy", "HTTP_PROXY"])
+            },
+            no_proxy: var(&["no_proxy", "NO_PROXY"])
+                .map(|value| parse_no_proxy(&value))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Return the proxy to use when connecting to `host`, or `None` if the connection should be direct.
+    pub fn proxy_for(&self, host: Option<&str>) -> Option<&str> {
+        let url = self.url.as_deref()?;
+        match host {
+            Some(host) if self.is_excluded(host) => None,
+            _ => Some(url),
+        }
+    }
+
+    /// Return true if `host` matches one of the [`no_proxy`][Self::no_proxy] patterns.
+    ///
+    /// `*` matches all hosts, while any other pattern matches the host itself and all of its subdomains,
+    /// with leading `*.` or `.` being ignored. The comparison is case-insensitive, and brackets around IPv6 addresses
+    /// are ignored in both patterns and hosts.
+    pub fn is_excluded(&self, host: &str) -> bool {
+        fn strip_brackets(host: &str) -> &str {
+            host.strip_prefix('[')
+                .and_then(|host| host.strip_suffix(']'))
+                .unwrap_or(host)
+        }
+        let host = strip_brackets(host).to_ascii_lowercase();
+        self.no_proxy.iter().any(|pattern| {
+            if pattern == "*" {
+                return true;
+            }
+            let pattern = strip_brackets(pattern)
+                .trim_start_matches("*.")
+                .trim_start_matches('.')
+                .to_ascii_lowercase();
+            !pattern.is_empty()
+                && (host == pattern
+                    || host
+                        .strip_suffix(pattern.as_str())
+                        .map_or(false, |prefix| prefix.ends_with('.')))
+        })
+    }
+}
+
+/// Parse `value` as comma-separated list of host patterns as found in the `NO_PROXY` environment variable,
+/// skipping empty entries and ignoring surrounding whitespace.
+pub fn parse_no_proxy(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|pattern| !pattern.is_empty())
+        .map(ToOwned::to_owned)
+        .collect()
+}
@@ -9,6 +9,12 @@ pub struct Remote {
     response: std::sync::mpsc::Receiver<remote::Response>,
     /// A mechanism for configuring the remote.
     config: Options,
+    /// Timeouts to apply to each request.
+    connect_options: crate::client::connect::Options,
+    /// The proxy configuration, if set.
+    proxy: Option<crate::client::http::proxy::Options>,
+    /// The way to verify the server certificate.
+    ssl: crate::client::http::ssl::Options,
 }
 
 mod remote {
@@ -25,6 +31,11 @@ mod remote {
         Reqwest(#[from] reqwest::Error),
         #[error("Request configuration failed")]
         ConfigureRequest(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
+        #[error("Could not read certificate authorities from \"{}\"", path.display())]
+        ReadCaInfo {
+            path: std::path::PathBuf,
+            source: std::io::Error,
+        },
     }
 
     impl Default for Remote {
@@ -37,13 +48,37 @@ mod remote {
                     headers,
                     upload,
                     config,
+                    connect_options,
+                    proxy,
+                    ssl,
                 } in req_recv
                 {
                     // We may error while configuring, which is expected as part of the internal protocol. The error will be
                     // received and the sender of the request might restart us.
-                    let client = reqwest::blocking::ClientBuilder::new()
-                        .connect_timeout(std::time::Duration::from_secs(20))
-                        .build()?;
+                    let mut client = reqwest::blocking::ClientBuilder::new().connect_timeout(
+                        connect_options
+                            .connect_timeout
+                            .unwrap_or_else(|| std::time::Duration::from_secs(20)),
+                    );
+                    if let Some(timeout) = connect_options.read_timeout {
+                        // Without a way to time out stalled reads, this limits the time the request may take in total.
+                        client = client.timeout(timeout);
+                    }
+                    match proxy {
+                        Some(Some(url)) => client = client.proxy(reqwest::Proxy::all(url)?),
+                        Some(None) => client = client.no_proxy(),
+                        None => {}
+                    }
+                    if !ssl.verify {
+                        client = client.danger_accept_invalid_certs(true);
+                    }
+                    if let Some(path) = ssl.ca_info {
+                        let pem = std::fs::read(&path).map_err(|err| Error::ReadCaInfo { path, source: err })?;
+                        for certificate in http::ssl::pem_certificates(&pem) {
+                            client = client.add_root_certificate(reqwest::Certificate::from_pem(certificate)?);
+                        }
+                    }
+                    let client = client.build()?;
                     let mut req_builder = if upload { client.post(url) } else { client.get(url) }.headers(headers);
                     let (post_body_tx, post_body_rx) = pipe::unidirectional(0);
                     if upload {
@@ -80,6 +115,7 @@ mod remote {
                                     };
                                     (kind, format!("Received HTTP status {}", status.as_str()))
                                 }
+                                None if err.is_timeout() => (std::io::ErrorKind::TimedOut, err.to_string()),
                                 None => (std::io::ErrorKind::Other, err.to_string()),
                             };
                             let err = Err(std::io::Error::new(kind, err));
@@ -121,6 +157,9 @@ mod remote {
                 request: req_send,
                 response: res_recv,
                 config: Options::default(),
+                connect_options: Default::default(),
+                proxy: None,
+                ssl: Default::default(),
             }
         }
     }
@@ -156,6 +195,9 @@ mod remote {
                     headers: header_map,
                     upload,
                     config: self.config.clone(),
+                    connect_options: self.connect_options,
+                    proxy: http::proxy_for_url(self.proxy.as_ref(), url),
+                    ssl: self.ssl.clone(),
                 })
                 .expect("the remote cannot be down at this point");
 
@@ -210,6 +252,15 @@ mod remote {
         fn configure(&mut self, config: &dyn Any) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
             if let Some(config) = config.downcast_ref::<Options>() {
                 self.config = config.clone();
+            } else if let Some(config) = config.downcast_ref::<crate::client::connect::Options>() {
+                self.connect_options = *config;
+            } else if let Some(proxy) = config.downcast_ref::<http::proxy::Options>() {
+                self.proxy = Some(proxy.clone());
+            } else if let Some(ssl) = config.downcast_ref::<http::ssl::Options>() {
+                if ssl.client_cert.is_some() || ssl.client_key.is_some() {
+                    return Err("Client certificates are not supported by the reqwest based http transport".into());
+                }
+                self.ssl = ssl.clone();
             }
             Ok(())
         }
@@ -238,6 +289,10 @@ mod remote {
         pub headers: reqwest::header::HeaderMap,
         pub upload: bool,
         pub config: Options,
+        pub connect_options: crate::client::connect::Options,
+        /// The proxy to use, with `Some(None)` meaning to not use any proxy, and `None` meaning to use the defaults.
+        pub proxy: Option<Option<String>>,
+        pub ssl: http::ssl::Options,
     }
 
     /// A link to a thread who provides data for the contained readers.
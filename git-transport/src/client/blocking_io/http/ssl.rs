@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+/// Options to configure how the http transport verifies the server and identifies itself when using `https`, passed to
+/// [`configure()`][crate::client::TransportWithoutIO::configure()].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// If `false`, the server certificate and host name will not be verified, which allows connecting to servers using
+    /// self-signed certificates at the cost of being open to man-in-the-middle attacks. Defaults to `true`.
+    pub verify: bool,
+    /// A file with one or more certificate authorities in `PEM` format to verify the server certificate with.
+    ///
+    /// With `curl`, these replace the default certificate authorities, while `reqwest` trusts them in addition to the defaults.
+    pub ca_info: Option<PathBuf>,
+    /// A file with the client certificate in `PEM` format to authenticate with if the server asks for it.
+    ///
+    /// Note that client certificates are only supported by the `curl` based implementation.
+    pub client_cert: Option<PathBuf>,
+    /// A file with the private key belonging to [`client_cert`][Self::client_cert] in `PEM` format.
+    pub client_key: Option<PathBuf>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            verify: true,
+            ca_info: None,
+            client_cert: None,
+            client_key: None,
+        }
+    }
+}
+
+/// Return each `CERTIFICATE` block in the `PEM` encoded `bundle`, including its `BEGIN` and `END` markers.
+///
+/// This is useful for implementations which only parse one certificate at a time, but need to trust all certificate
+/// authorities in [`ca_info`][Options::ca_info]. Everything outside of these blocks, including incomplete blocks, is ignored.
+pub fn pem_certificates(bundle: &[u8]) -> Vec<&[u8]> {
+    use bstr::ByteSlice;
+    const BEGIN: &[u8] = b"-----BEGIN CERTIFICATE-----";
+    const END: &[u8] = b"-----END CERTIFICATE-----";
+
+    let mut out = Vec::new();
+    let mut rest = bundle;
+    while let Some(start) = rest.find(BEGIN) {
+        let block = &rest[start..];
+        match block.find(END) {
+            Some(end) => {
+                let end = end + END.len();
+                out.push(&block[..end]);
+                rest = &block[end..];
+            }
+            None => break,
+        }
+    }
+    out
+}
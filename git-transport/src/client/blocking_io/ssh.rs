@@ -20,6 +20,8 @@ pub enum Error {
 /// The `desired_version` is the preferred protocol version when establishing the connection, but note that it can be
 /// downgraded by servers not supporting it.
 ///
+/// Of the `options`, only the `connect_timeout` is used, which is passed to the ssh program as `ConnectTimeout`.
+///
 /// # Environment Variables
 ///
 /// Use `GIT_SSH_COMMAND` to override the `ssh` program to execute. This can be a script dealing with using the correct
@@ -30,6 +32,7 @@ pub fn connect(
     desired_version: crate::Protocol,
     user: Option<&str>,
     port: Option<u16>,
+    options: crate::client::connect::Options,
 ) -> Result<blocking_io::file::SpawnProcessOnDemand, Error> {
     let ssh_cmd_line = std::env::var("GIT_SSH_COMMAND").unwrap_or_else(|_| "ssh".into());
     let mut ssh_cmd_line = ssh_cmd_line.split(' ');
@@ -38,8 +41,13 @@ pub fn connect(
     type EnvVar = (&'static str, String);
     let args_and_env: Option<(Vec<Cow<'_, str>>, Vec<EnvVar>)> = match ssh_cmd {
         "ssh" | "ssh.exe" => {
+            let mut args = Vec::new();
+            if let Some(timeout) = options.connect_timeout {
+                let secs = timeout.as_secs() + u64::from(timeout.subsec_nanos() != 0);
+                args.extend([Cow::from("-o"), format!("ConnectTimeout={}", secs.max(1)).into()]);
+            }
             if desired_version != Protocol::V1 {
-                let mut args = vec![Cow::from("-o"), "SendEnv=GIT_PROTOCOL".into()];
+                args.extend([Cow::from("-o"), "SendEnv=GIT_PROTOCOL".into()]);
                 if let Some(port) = port {
                     args.push(format!("-p={}", port).into());
                 }
@@ -47,6 +55,8 @@ pub fn connect(
                     args,
                     vec![("GIT_PROTOCOL", format!("version={}", desired_version as usize))],
                 ))
+            } else if !args.is_empty() {
+                Some((args, Vec::new()))
             } else {
                 None
             }
@@ -92,7 +102,10 @@ pub fn connect(
 mod tests {
     use bstr::ByteSlice;
 
-    use crate::{client::blocking_io::ssh::connect, Protocol};
+    use crate::{
+        client::{blocking_io::ssh::connect, connect::Options},
+        Protocol,
+    };
 
     #[test]
     fn connect_with_tilde_in_path() {
@@ -101,7 +114,7 @@ mod tests {
             ("ssh://host.xy/~username/repo", "~username/repo"),
         ] {
             let url = git_url::parse((*url).into()).expect("valid url");
-            let cmd = connect("host", url.path, Protocol::V1, None, None).expect("parse success");
+            let cmd = connect("host", url.path, Protocol::V1, None, None, Default::default()).expect("parse success");
             assert_eq!(
                 cmd.path,
                 expected.as_bytes().as_bstr(),
@@ -109,4 +122,32 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn connect_timeout_is_passed_as_option_in_whole_seconds() {
+        for (version, timeout, expected) in [
+            (Protocol::V1, std::time::Duration::from_millis(1500), "ConnectTimeout=2"),
+            (Protocol::V2, std::time::Duration::from_millis(1), "ConnectTimeout=1"),
+            (Protocol::V2, std::time::Duration::from_secs(10), "ConnectTimeout=10"),
+        ] {
+            let cmd = connect(
+                "host",
+                "/repo".into(),
+                version,
+                None,
+                None,
+                Options {
+                    connect_timeout: Some(timeout),
+                    ..Default::default()
+                },
+            )
+            .expect("parse success");
+            assert!(
+                cmd.ssh_args.windows(2).any(|w| w[0] == "-o" && w[1] == expected),
+                "{:?} should contain {:?}",
+                cmd.ssh_args,
+                expected
+            );
+        }
+    }
 }
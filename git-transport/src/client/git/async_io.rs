@@ -137,14 +137,17 @@ mod async_net {
     impl git::Connection<TcpStream, TcpStream> {
         /// Create a new TCP connection using the `git` protocol of `desired_version`, and make a connection to `host`
         /// at `port` for accessing the repository at `path` on the server side.
+        ///
+        /// Of the `options`, only the `connect_timeout` is used, defaulting to 5 seconds.
         pub async fn new_tcp(
             host: &str,
             port: Option<u16>,
             path: bstr::BString,
             desired_version: crate::Protocol,
+            options: crate::client::connect::Options,
         ) -> Result<git::Connection<TcpStream, TcpStream>, Error> {
             let read = async_std::io::timeout(
-                Duration::from_secs(5),
+                options.connect_timeout.unwrap_or(Duration::from_secs(5)),
                 TcpStream::connect(&(host, port.unwrap_or(9418))),
             )
             .await?;
@@ -170,19 +170,22 @@ pub mod connect {
     /// Connect to a git daemon running on `host` and optionally `port` and a repository at `path`.
     ///
     /// Use `desired_version` to specify a preferred protocol to use, knowing that it can be downgraded by a server not supporting it.
+    /// The `connect_timeout` of `options` defaults to 5 seconds, and the `read_timeout` is applied to the socket.
     pub fn connect(
         host: &str,
         path: BString,
         desired_version: crate::Protocol,
         port: Option<u16>,
+        options: crate::client::connect::Options,
     ) -> Result<git::Connection<TcpStream, TcpStream>, Error> {
         let read = TcpStream::connect_timeout(
             &(host, port.unwrap_or(9418))
                 .to_socket_addrs()?
                 .next()
                 .expect("after successful resolution there is an IP address"),
-            std::time::Duration::from_secs(5),
+            options.connect_timeout.unwrap_or(std::time::Duration::from_secs(5)),
         )?;
+        read.set_read_timeout(options.read_timeout)?;
         let write = read.try_clone()?;
         let vhost = std::env::var("GIT_OVERRIDE_VIRTUAL_HOST")
             .ok()
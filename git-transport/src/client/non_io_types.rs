@@ -31,6 +31,28 @@ pub enum MessageKind {
 
 #[cfg(any(feature = "blocking-client", feature = "async-client"))]
 pub(crate) mod connect {
+    use std::time::Duration;
+
+    /// Options for connecting to a remote, passed to [`connect()`][crate::connect()] and the transport specific
+    /// connect functions.
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Options {
+        /// The time to wait for a connection to be established, or `None` to use the default of the respective transport.
+        ///
+        /// For `ssh`, this becomes the `ConnectTimeout` setting of the ssh program, with fractions rounded up to whole seconds.
+        pub connect_timeout: Option<Duration>,
+        /// The time to wait for the remote to send data before giving up with a [timeout error][crate::client::Error::Timeout],
+        /// or `None` to use the default of the respective transport, which is to wait indefinitely for `git://` connections.
+        ///
+        /// It applies to `git://` connections of the blocking client and to `http(s)://` connections.
+        /// It has no effect on the `ssh` and `file` transports as well as on the async client.
+        ///
+        /// Note that the `reqwest` backend for `http(s)://` doesn't support waiting for data, and uses it as deadline for each
+        /// request as a whole instead, including receiving the response body. Thus it must be large enough to receive the biggest
+        /// expected pack when using that backend.
+        pub read_timeout: Option<Duration>,
+    }
+
     /// The error used in [`connect()`][crate::connect()].
     #[derive(Debug, thiserror::Error)]
     #[allow(missing_docs)]
@@ -74,9 +96,11 @@ mod error {
     pub enum Error {
         #[error("An IO error occurred when talking to the server")]
         Io {
-            #[from]
+            #[source]
             err: std::io::Error,
         },
+        #[error("The remote did not respond in time")]
+        Timeout,
         #[error("Capabilities could not be parsed")]
         Capabilities {
             #[from]
@@ -100,6 +124,16 @@ mod error {
         #[error(transparent)]
         Http(#[from] HttpError),
     }
+
+    impl From<std::io::Error> for Error {
+        fn from(err: std::io::Error) -> Self {
+            match err.kind() {
+                // Blocking sockets indicate an expired read timeout with `WouldBlock` on some platforms.
+                std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock => Error::Timeout,
+                _ => Error::Io { err },
+            }
+        }
+    }
 }
 
 pub use error::Error;
@@ -513,3 +513,115 @@ fn check_content_type_is_case_insensitive() -> crate::Result {
     assert!(result.is_ok());
     Ok(())
 }
+
+mod proxy {
+    use git_transport::{
+        client::{http, http::proxy, Transport, TransportWithoutIO},
+        Protocol, Service,
+    };
+
+    use crate::client::blocking_io::http::mock;
+
+    #[test]
+    fn requests_go_through_the_configured_proxy() -> crate::Result {
+        let proxy_server = mock::serve_once("v1/http-handshake.response");
+        let mut client = http::connect("http://example.invalid/repo.git", Protocol::V1);
+        client
+            .configure(&proxy::Options {
+                url: Some(format!("http://{}", proxy_server.addr)),
+                no_proxy: proxy::parse_no_proxy("localhost, .example.org"),
+            })
+            .expect("configuration never fails");
+        client.handshake(Service::UploadPack, &[])?;
+
+        let request = proxy_server.received_as_string();
+        assert_eq!(
+            request.lines().next(),
+            Some("GET http://example.invalid/repo.git/info/refs?service=git-upload-pack HTTP/1.1"),
+            "the proxy receives the absolute URL to forward the request to"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn hosts_matching_no_proxy_are_contacted_directly() -> crate::Result {
+        let (server, mut client) = mock::serve_and_connect("v1/http-handshake.response", "repo.git", Protocol::V1)?;
+        client
+            .configure(&proxy::Options {
+                url: Some("http://127.0.0.1:1".into()),
+                no_proxy: proxy::parse_no_proxy("example.org,127.0.0.1"),
+            })
+            .expect("configuration never fails");
+        client.handshake(Service::UploadPack, &[])?;
+
+        let request = server.received_as_string();
+        assert_eq!(
+            request.lines().next(),
+            Some("GET /repo.git/info/refs?service=git-upload-pack HTTP/1.1"),
+            "the configured proxy was bypassed"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn no_proxy_patterns_match_hosts_and_their_subdomains() {
+        let options = proxy::Options {
+            url: Some("socks5://proxy:1080".into()),
+            no_proxy: proxy::parse_no_proxy(" example.org,, .internal , *.corp.net,[::1]"),
+        };
+        assert_eq!(options.no_proxy, ["example.org", ".internal", "*.corp.net", "[::1]"]);
+        for host in [
+            "example.org",
+            "EXAMPLE.org",
+            "git.example.org",
+            "a.internal",
+            "corp.net",
+            "b.corp.net",
+        ] {
+            assert_eq!(options.proxy_for(Some(host)), None, "{} is excluded", host);
+        }
+        for host in ["example.com", "notexample.org", "internal.com", "github.com"] {
+            assert_eq!(
+                options.proxy_for(Some(host)),
+                Some("socks5://proxy:1080"),
+                "{} uses the proxy",
+                host
+            );
+        }
+        assert!(options.is_excluded("::1"), "brackets are ignored in patterns");
+        assert!(options.is_excluded("[::1]"), "and in hosts");
+
+        let all = proxy::Options {
+            url: Some("http://proxy".into()),
+            no_proxy: vec!["*".into()],
+        };
+        assert_eq!(all.proxy_for(Some("anything")), None);
+        assert_eq!(
+            proxy::Options::default().proxy_for(Some("anything")),
+            None,
+            "no url means no proxy"
+        );
+    }
+}
+
+mod ssl {
+    use git_transport::client::http::ssl;
+
+    #[test]
+    fn pem_certificates_yields_each_certificate_of_a_bundle() {
+        let first = "-----BEGIN CERTIFICATE-----\nMIIBfirstCA\n-----END CERTIFICATE-----";
+        let second = "-----BEGIN CERTIFICATE-----\nMIIBsecondCA\n-----END CERTIFICATE-----";
+        let bundle = format!("# first CA\n{}\n\n# second CA\n{}\n", first, second);
+        assert_eq!(
+            ssl::pem_certificates(bundle.as_bytes()),
+            [first.as_bytes(), second.as_bytes()],
+            "all certificates are returned, without the text around them"
+        );
+        assert_eq!(
+            ssl::pem_certificates(format!("{}\n-----BEGIN CERTIFICATE-----\nMIIB", first).as_bytes()),
+            [first.as_bytes()],
+            "incomplete trailing certificates are ignored"
+        );
+        assert!(ssl::pem_certificates(b"no certificates").is_empty());
+    }
+}
@@ -363,3 +363,26 @@ async fn handshake_v2_and_request_inner() -> crate::Result {
     );
     Ok(())
 }
+
+#[cfg(feature = "blocking-client")]
+#[test]
+fn handshake_with_unresponsive_daemon_fails_with_timeout_error() -> crate::Result {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let mut c = git::connect(
+        "127.0.0.1",
+        "/foo.git".into(),
+        Protocol::V2,
+        Some(listener.local_addr()?.port()),
+        client::connect::Options {
+            read_timeout: Some(std::time::Duration::from_millis(50)),
+            ..Default::default()
+        },
+    )?;
+    let (_server_side_kept_open_without_responding, _) = listener.accept()?;
+    let err = c
+        .handshake(Service::UploadPack, &[])
+        .map(|_| ())
+        .expect_err("the server never responds");
+    assert!(matches!(err, client::Error::Timeout), "unexpected error: {:?}", err);
+    Ok(())
+}
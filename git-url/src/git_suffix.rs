@@ -0,0 +1,10 @@
+/// Determine what to do with the `.git` suffix of the path when [canonicalizing][crate::Url::canonicalized()] a [`Url`][crate::Url].
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
+pub enum GitSuffix {
+    /// Leave the path as is, with or without `.git` suffix.
+    Keep,
+    /// Add the `.git` suffix if it isn't present yet.
+    Add,
+    /// Remove the `.git` suffix if it is present.
+    Strip,
+}
@@ -16,6 +16,7 @@ impl Default for Url {
             host: None,
             port: None,
             path: bstr::BString::default(),
+            query: None,
         }
     }
 }
@@ -20,9 +20,15 @@ pub mod expand_path;
 #[doc(inline)]
 pub use expand_path::expand_path;
 
+///
+pub mod ssh_form;
+
 mod scheme;
 pub use scheme::Scheme;
 
+mod git_suffix;
+pub use git_suffix::GitSuffix;
+
 /// A URL with support for specialized git related capabilities.
 ///
 /// Additionally there is support for [deserialization][Url::from_bytes()] and serialization
@@ -44,6 +50,8 @@ pub struct Url {
     pub port: Option<u16>,
     /// The path portion of the URL, usually the location of the git repository.
     pub path: bstr::BString,
+    /// The query portion of the URL without the leading `?`, like `foo=bar` in `https://host/repo.git?foo=bar`.
+    query: Option<BString>,
 }
 
 /// Instantiation
@@ -63,6 +71,7 @@ impl Url {
                 host,
                 port,
                 path,
+                query: None,
                 serialize_alternative_form: false,
             }
             .to_bstring()
@@ -79,6 +88,11 @@ impl Url {
         self.user = user;
         prev
     }
+
+    /// Set the given `query` without the leading `?`, with `None` unsetting it. Returns the previous value.
+    pub fn set_query(&mut self, query: Option<BString>) -> Option<BString> {
+        std::mem::replace(&mut self.query, query)
+    }
 }
 
 /// Builder
@@ -103,6 +117,29 @@ impl Url {
     pub fn host(&self) -> Option<&str> {
         self.host.as_deref()
     }
+    /// Returns the host mentioned in the url in a form suitable as key for matching, like credential or TLS configuration, if present.
+    ///
+    /// It's lower-cased as hosts are case-insensitive, and IPv6 addresses are returned without the enclosing brackets, so
+    /// `[::1]` becomes `::1`. Like the [`host()`][Url::host()], it never contains the user or the port.
+    pub fn host_for_matching(&self) -> Option<std::borrow::Cow<'_, str>> {
+        self.host.as_deref().map(|host| {
+            let host = host
+                .strip_prefix('[')
+                .and_then(|host| host.strip_suffix(']'))
+                .unwrap_or(host);
+            if host.bytes().any(|b| b.is_ascii_uppercase()) {
+                host.to_ascii_lowercase().into()
+            } else {
+                host.into()
+            }
+        })
+    }
+    /// Returns the query mentioned in the url without the leading `?`, if present.
+    ///
+    /// Note that only URLs with an explicit scheme other than `file` can have a query, for all others it is part of the path.
+    pub fn query(&self) -> Option<&BStr> {
+        self.query.as_ref().map(|q| q.as_ref())
+    }
     /// Returns true if the path portion of the url is `/`.
     pub fn path_is_root(&self) -> bool {
         self.path == "/"
@@ -110,19 +147,128 @@ impl Url {
     /// Returns the actual or default port for use according to the url scheme.
     /// Note that there may be no default port either.
     pub fn port_or_default(&self) -> Option<u16> {
-        self.port.or_else(|| {
-            use Scheme::*;
-            Some(match self.scheme {
-                Http => 80,
-                Https => 443,
-                Ssh => 21,
-                Git => 9418,
-                File | Ext(_) => return None,
-            })
+        self.port.or_else(|| self.default_port())
+    }
+
+    fn default_port(&self) -> Option<u16> {
+        use Scheme::*;
+        Some(match self.scheme {
+            Http => 80,
+            Https => 443,
+            Ssh => 22,
+            Git => 9418,
+            File | Ext(_) => return None,
         })
     }
 }
 
+/// Conversion
+impl Url {
+    /// Return a copy of this ssh URL that serializes in the explicit `ssh://[user@]host[:port]/path` form.
+    ///
+    /// This is useful for libraries that don't understand the scp-like form. User, host, port and path are retained,
+    /// and an error is returned if this isn't an ssh URL.
+    pub fn to_ssh_explicit(&self) -> Result<Url, ssh_form::Error> {
+        self.assure_ssh()?;
+        Ok(self.clone().serialize_alternate_form(false))
+    }
+
+    /// Return a copy of this ssh URL that serializes in the scp-like `[user@]host:path` form.
+    ///
+    /// As the scp-like form can't carry a port or a query, an error is returned if either is set instead of dropping them,
+    /// along with URLs that wouldn't be parsed back as the same ssh URL, like those whose host doesn't contain a `.`.
+    /// An error is also returned if this isn't an ssh URL.
+    pub fn to_ssh_scp_like(&self) -> Result<Url, ssh_form::Error> {
+        self.assure_ssh()?;
+        if let Some(port) = self.port {
+            return Err(ssh_form::Error::PortInScpLikeForm { port });
+        }
+        if self.query.is_some() {
+            return Err(ssh_form::Error::QueryInScpLikeForm);
+        }
+        let url = self.clone().serialize_alternate_form(true);
+        let serialized = url.to_bstring();
+        match parse(serialized.as_ref()) {
+            Ok(parsed) if parsed == url => Ok(url),
+            _ => Err(ssh_form::Error::AmbiguousScpLikeForm { url: serialized }),
+        }
+    }
+
+    /// Return a copy of this URL in a canonical form that is suitable for comparisons.
+    ///
+    /// The host is lower-cased as it's case-insensitive, the port is removed if it is the default port of the scheme,
+    /// and trailing slashes are removed from the path unless it is the root path.
+    pub fn normalize(&self) -> Url {
+        let mut url = self.clone();
+        if let Some(host) = url.host.as_mut() {
+            host.make_ascii_lowercase();
+        }
+        if url.port.is_some() && url.port == url.default_port() {
+            url.port = None;
+        }
+        let path_len = url.path.len();
+        let trailing_slashes = url.path.iter().rev().take_while(|b| **b == b'/').count();
+        url.path.truncate((path_len - trailing_slashes).max(1).min(path_len));
+        url
+    }
+
+    /// Return a copy of this URL with a single trailing slash removed from its path, and with its `.git` suffix handled
+    /// according to `suffix`, so that `https://host/repo`, `https://host/repo/` and `https://host/repo.git` can map to the same URL.
+    ///
+    /// Scheme, user, host, port and query are left untouched. Note that this is a purely textual transformation which doesn't contact
+    /// any server, hence it can't know if the resulting URL actually refers to the same repository.
+    pub fn canonicalized(&self, suffix: GitSuffix) -> Url {
+        let mut url = self.clone();
+        if url.path.len() > 1 && url.path.ends_with(b"/") {
+            url.path.pop();
+        }
+        let has_suffix = url.path.ends_with(b".git");
+        match suffix {
+            GitSuffix::Keep => {}
+            GitSuffix::Add => {
+                if !has_suffix && !url.path_is_root() && !url.path.is_empty() {
+                    url.path.extend_from_slice(b".git");
+                }
+            }
+            GitSuffix::Strip => {
+                let stripped_len = url.path.len().saturating_sub(".git".len());
+                // A path component that is just `.git` has no name to fall back to, so it's kept.
+                if has_suffix && stripped_len > 0 && url.path[stripped_len - 1] != b'/' {
+                    url.path.truncate(stripped_len);
+                }
+            }
+        }
+        url
+    }
+
+    /// Return true if this URL, used as the scope of a credential, matches the `other` URL, using git's semantics for
+    /// credential URL matching.
+    ///
+    /// Both URLs are [normalized][Url::normalize()] first, and scheme, host and port must be equal. If a user is set in this URL,
+    /// it must match the user of `other` as well. If `use_path` is true, the paths must also be equal, otherwise they are ignored.
+    /// The query is never compared.
+    pub fn matches_scope(&self, other: &Url, use_path: bool) -> bool {
+        let (scope, other) = (self.normalize(), other.normalize());
+        scope.scheme == other.scheme
+            && scope.host == other.host
+            && scope.port_or_default() == other.port_or_default()
+            && scope
+                .user
+                .as_ref()
+                .map_or(true, |user| other.user.as_ref() == Some(user))
+            && (!use_path || scope.path == other.path)
+    }
+
+    fn assure_ssh(&self) -> Result<(), ssh_form::Error> {
+        if self.scheme != Scheme::Ssh {
+            return Err(ssh_form::Error::NotSsh {
+                scheme: self.scheme.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
 /// Serialization
 impl Url {
     /// Write this URL losslessly to `out`, ready to be parsed again.
@@ -152,6 +298,10 @@ impl Url {
         } else {
             out.write_all(&self.path)?;
         }
+        if let Some(query) = &self.query {
+            out.write_all(b"?")?;
+            out.write_all(query)?;
+        }
         Ok(())
     }
 
@@ -163,7 +313,8 @@ impl Url {
                 + 1
                 + self.host.as_ref().map(|h| h.len()).unwrap_or_default()
                 + self.port.map(|_| 5).unwrap_or_default()
-                + self.path.len(),
+                + self.path.len()
+                + self.query.as_ref().map(|q| 1 + q.len()).unwrap_or_default(),
         );
         self.write_to(&mut buf).expect("io cannot fail in memory");
         buf.into()
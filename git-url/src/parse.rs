@@ -76,6 +76,7 @@ fn to_owned_url(url: url::Url) -> Result<crate::Url, Error> {
         host: url.host_str().map(Into::into),
         port: url.port(),
         path: url.path().into(),
+        query: url.query().map(Into::into),
     })
 }
 
@@ -127,5 +128,13 @@ pub fn parse(input: &BStr) -> Result<crate::Url, Error> {
         return Err(Error::RelativeUrl { url: url.into() });
     }
 
-    to_owned_url(url).map(|url| url.serialize_alternate_form(sanitized_scp))
+    let mut url = to_owned_url(url)?;
+    if sanitized_scp {
+        // scp-like URLs have no query, so it's part of the path instead.
+        if let Some(query) = url.query.take() {
+            url.path.push(b'?');
+            url.path.extend_from_slice(&query);
+        }
+    }
+    Ok(url.serialize_alternate_form(sanitized_scp))
 }
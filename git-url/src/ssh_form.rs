@@ -0,0 +1,19 @@
+//! Conversions between the explicit `ssh://` form and the scp-like form of ssh URLs.
+use bstr::BString;
+
+use crate::Scheme;
+
+/// The error returned by [`Url::to_ssh_explicit()`][crate::Url::to_ssh_explicit()] and
+/// [`Url::to_ssh_scp_like()`][crate::Url::to_ssh_scp_like()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The URL with scheme {scheme:?} isn't an ssh URL")]
+    NotSsh { scheme: Scheme },
+    #[error("The port {port} cannot be represented in the scp-like form")]
+    PortInScpLikeForm { port: u16 },
+    #[error("The query of the URL cannot be represented in the scp-like form")]
+    QueryInScpLikeForm,
+    #[error("The scp-like form {url:?} would not be parsed as the same ssh URL")]
+    AmbiguousScpLikeForm { url: BString },
+}
@@ -0,0 +1,176 @@
+fn parse(url: &str) -> git_url::Url {
+    git_url::parse(url.into()).expect("valid url")
+}
+
+#[test]
+fn host_case_default_ports_and_trailing_slashes_are_normalized() {
+    let url = parse("https://Example.com:443/repo.git/").normalize();
+    assert_eq!(url.host(), Some("example.com"));
+    assert_eq!(url.port, None, "the default port of the scheme is removed");
+    assert_eq!(url.path, "/repo.git");
+    assert_eq!(url.to_bstring(), "https://example.com/repo.git");
+
+    assert_eq!(
+        parse("https://example.com:8443/").normalize().port,
+        Some(8443),
+        "non-default ports are kept"
+    );
+    assert!(
+        parse("https://example.com/").normalize().path_is_root(),
+        "the root path is kept"
+    );
+}
+
+#[test]
+fn scope_matching_compares_scheme_host_and_port() {
+    let scope = parse("https://Example.com:443/");
+    assert!(scope.matches_scope(&parse("https://example.com/"), false));
+    assert!(scope.matches_scope(&parse("https://example.com/org/repo.git"), false));
+    assert!(
+        !scope.matches_scope(&parse("http://example.com/"), false),
+        "scheme must match"
+    );
+    assert!(
+        !scope.matches_scope(&parse("https://example.org/"), false),
+        "host must match"
+    );
+    assert!(
+        !scope.matches_scope(&parse("https://example.com:8443/"), false),
+        "port must match"
+    );
+}
+
+#[test]
+fn default_ssh_port_is_removed() {
+    let url = parse("ssh://git@example.com:22/repo.git").normalize();
+    assert_eq!(url.port, None, "22 is the default port for ssh");
+    assert_eq!(url.to_bstring(), "ssh://git@example.com/repo.git");
+    assert_eq!(
+        parse("ssh://example.com:2222/repo.git").normalize().port,
+        Some(2222),
+        "non-default ports are kept"
+    );
+    assert!(parse("ssh://h:22/x").matches_scope(&parse("ssh://h/x"), true));
+    assert!(parse("ssh://h/x").matches_scope(&parse("ssh://h:22/x"), true));
+    assert!(!parse("ssh://h:21/x").matches_scope(&parse("ssh://h/x"), true));
+}
+
+#[test]
+fn scope_matching_with_user() {
+    let scope = parse("https://user@example.com");
+    assert!(scope.matches_scope(&parse("https://user@example.com/repo"), false));
+    assert!(!scope.matches_scope(&parse("https://other@example.com/repo"), false));
+    assert!(
+        !scope.matches_scope(&parse("https://example.com/repo"), false),
+        "the user of the scope must be present"
+    );
+    assert!(
+        parse("https://example.com").matches_scope(&parse("https://user@example.com/repo"), false),
+        "scopes without user match any user"
+    );
+}
+
+#[test]
+fn scope_matching_with_path() {
+    let scope = parse("https://example.com/org/repo.git/");
+    assert!(scope.matches_scope(&parse("https://example.com/org/repo.git"), true));
+    assert!(!scope.matches_scope(&parse("https://example.com/org/other.git"), true));
+    assert!(
+        scope.matches_scope(&parse("https://example.com/org/other.git"), false),
+        "paths are ignored unless requested"
+    );
+}
+
+mod canonicalized {
+    use git_url::GitSuffix;
+
+    use super::parse;
+
+    const VARIANTS: &[&str] = &["https://host/repo", "https://host/repo/", "https://host/repo.git"];
+
+    fn canonicalized(url: &str, suffix: GitSuffix) -> String {
+        parse(url).canonicalized(suffix).to_bstring().to_string()
+    }
+
+    #[test]
+    fn all_variants_map_to_the_same_url_when_adding_or_stripping_the_suffix() {
+        for url in VARIANTS {
+            assert_eq!(canonicalized(url, GitSuffix::Add), "https://host/repo.git");
+            assert_eq!(canonicalized(url, GitSuffix::Strip), "https://host/repo");
+        }
+    }
+
+    #[test]
+    fn keeping_the_suffix_only_removes_a_single_trailing_slash() {
+        assert_eq!(canonicalized("https://host/repo", GitSuffix::Keep), "https://host/repo");
+        assert_eq!(
+            canonicalized("https://host/repo/", GitSuffix::Keep),
+            "https://host/repo"
+        );
+        assert_eq!(
+            canonicalized("https://host/repo.git", GitSuffix::Keep),
+            "https://host/repo.git"
+        );
+        assert_eq!(
+            canonicalized("https://host/repo.git/", GitSuffix::Keep),
+            "https://host/repo.git"
+        );
+        assert_eq!(
+            canonicalized("https://host/repo//", GitSuffix::Keep),
+            "https://host/repo/"
+        );
+    }
+
+    #[test]
+    fn scheme_host_port_and_root_paths_are_left_untouched() {
+        assert_eq!(
+            canonicalized("ssh://User@Host:2222/repo/", GitSuffix::Add),
+            "ssh://User@Host:2222/repo.git"
+        );
+        assert_eq!(canonicalized("https://host/", GitSuffix::Add), "https://host/");
+        assert_eq!(
+            canonicalized("https://host/.git", GitSuffix::Strip),
+            "https://host/.git"
+        );
+        assert_eq!(
+            canonicalized("git@host:org/repo/", GitSuffix::Strip),
+            "git@host:org/repo"
+        );
+        assert_eq!(
+            canonicalized("git@host:org/.git", GitSuffix::Strip),
+            "git@host:org/.git"
+        );
+    }
+
+    #[test]
+    fn the_suffix_is_stripped_from_short_names_as_well() {
+        assert_eq!(canonicalized("https://host/r.git", GitSuffix::Strip), "https://host/r");
+        assert_eq!(canonicalized("git@host:r.git", GitSuffix::Strip), "git@host:r");
+    }
+}
+
+#[test]
+fn host_for_matching_is_lowercase_without_user_port_or_brackets() {
+    let host = |url: &str| parse(url).host_for_matching().map(|h| h.into_owned());
+    assert_eq!(host("https://User@Example.COM:8080/repo"), Some("example.com".into()));
+    assert_eq!(
+        host("ssh://user@[::1]:22/repo"),
+        Some("::1".into()),
+        "IPv6 brackets are removed"
+    );
+    assert_eq!(host("https://[FE80::1]/repo"), Some("fe80::1".into()));
+    assert_eq!(
+        host("Git@Example.COM:org/repo.git"),
+        Some("example.com".into()),
+        "scp-like"
+    );
+    assert_eq!(host("file:///path/to/repo"), None);
+
+    assert!(
+        matches!(
+            parse("https://example.com/").host_for_matching(),
+            Some(std::borrow::Cow::Borrowed(_))
+        ),
+        "hosts which are already normalized are borrowed"
+    );
+}
@@ -11,6 +11,16 @@ fn file_path_with_protocol() -> crate::Result {
     )
 }
 
+#[test]
+fn file_path_with_protocol_has_no_query() -> crate::Result {
+    let url = assert_url_and(
+        "file:///path/to/git?foo=bar",
+        url(Scheme::File, None, None, None, b"/path/to/git?foo=bar"),
+    )?;
+    assert_eq!(url.query(), None);
+    Ok(())
+}
+
 #[test]
 fn file_path_without_protocol() -> crate::Result {
     let url = assert_url_and(
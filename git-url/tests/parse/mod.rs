@@ -96,6 +96,28 @@ mod http {
             url(Scheme::Https, None, "github.com", None, b"/byron/gitoxide"),
         )
     }
+
+    #[test]
+    fn without_query() -> crate::Result {
+        let url = git_url::parse("https://example.com/repo.git".into())?;
+        assert_eq!(url.query(), None);
+        assert_eq!(url.path, "/repo.git");
+        Ok(())
+    }
+
+    #[test]
+    fn query_is_not_part_of_the_path() -> crate::Result {
+        let mut expected = url(Scheme::Https, None, "example.com", None, b"/repo.git");
+        expected.set_query(Some("foo=bar&baz".into()));
+        assert_url_roundtrip("https://example.com/repo.git?foo=bar&baz", expected)?;
+
+        let mut url = git_url::parse("https://example.com/repo.git?foo=bar".into())?;
+        assert_eq!(url.path, "/repo.git", "the path excludes the query");
+        assert_eq!(url.query().expect("present"), "foo=bar");
+        assert_eq!(url.set_query(None).expect("previous value"), "foo=bar");
+        assert_eq!(url.to_bstring(), "https://example.com/repo.git", "it can be stripped");
+        Ok(())
+    }
 }
 mod git {
     use git_url::Scheme;
@@ -97,3 +97,12 @@ fn scp_like_with_user_and_relative_path_turns_into_absolute_path() -> crate::Res
     assert_eq!(url, "user@host.xz:relative");
     Ok(())
 }
+
+#[test]
+fn scp_like_has_no_query() -> crate::Result {
+    let url = git_url::parse("user@host.xz:path/to/repo.git?foo=bar".into())?;
+    assert_eq!(url.query(), None);
+    assert_eq!(url.path, "/path/to/repo.git?foo=bar", "the query is part of the path");
+    assert_eq!(url.to_bstring(), "user@host.xz:path/to/repo.git?foo=bar");
+    Ok(())
+}
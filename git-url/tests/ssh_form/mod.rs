@@ -0,0 +1,80 @@
+use git_url::{ssh_form::Error, Scheme};
+
+fn parse(url: &str) -> git_url::Url {
+    git_url::parse(url.into()).expect("valid url")
+}
+
+#[test]
+fn scp_like_to_explicit_and_back() -> crate::Result {
+    for (scp_like, explicit) in [
+        ("user@host.xz:path/to/repo.git", "ssh://user@host.xz/path/to/repo.git"),
+        ("host.xz:~/repo", "ssh://host.xz/~/repo"),
+        ("git@github.com:Byron/gitoxide", "ssh://git@github.com/Byron/gitoxide"),
+    ] {
+        let url = parse(scp_like);
+        let converted = url.to_ssh_explicit()?;
+        assert_eq!(converted.to_bstring(), explicit);
+        assert_eq!(converted.user(), url.user());
+        assert_eq!(converted.host(), url.host());
+        assert_eq!(converted.port, url.port);
+        assert_eq!(converted.path, url.path);
+        assert_eq!(
+            converted,
+            parse(explicit),
+            "the explicit form parses back into the same url"
+        );
+
+        let back = converted.to_ssh_scp_like()?;
+        assert_eq!(back.to_bstring(), scp_like);
+        assert_eq!(back, url, "the conversion is lossless");
+    }
+    Ok(())
+}
+
+#[test]
+fn conversions_into_the_same_form_are_no_ops() -> crate::Result {
+    let explicit = parse("ssh://user@host.xz/repo");
+    assert_eq!(explicit.to_ssh_explicit()?, explicit);
+    let scp_like = parse("user@host.xz:repo");
+    assert_eq!(scp_like.to_ssh_scp_like()?, scp_like);
+    Ok(())
+}
+
+#[test]
+fn ports_can_only_be_represented_in_the_explicit_form() -> crate::Result {
+    let url = parse("ssh://user@host.xz:2222/repo");
+    assert_eq!(url.to_ssh_explicit()?.to_bstring(), "ssh://user@host.xz:2222/repo");
+    assert!(matches!(
+        url.to_ssh_scp_like(),
+        Err(Error::PortInScpLikeForm { port: 2222 })
+    ));
+    Ok(())
+}
+
+#[test]
+fn queries_can_only_be_represented_in_the_explicit_form() {
+    let url = parse("ssh://host.xz/repo?foo=bar");
+    assert!(matches!(url.to_ssh_scp_like(), Err(Error::QueryInScpLikeForm)));
+}
+
+#[test]
+fn hosts_without_dot_would_be_parsed_as_path_in_the_scp_like_form() {
+    let url = parse("ssh://localhost/repo");
+    assert!(matches!(
+        url.to_ssh_scp_like(),
+        Err(Error::AmbiguousScpLikeForm { url }) if url == "localhost:repo"
+    ));
+}
+
+#[test]
+fn non_ssh_urls_cannot_be_converted() {
+    for url in ["https://host.xz/repo", "file:///repo", "git://host.xz/repo"] {
+        let url = parse(url);
+        assert!(matches!(url.to_ssh_explicit(), Err(Error::NotSsh { .. })));
+        assert!(matches!(url.to_ssh_scp_like(), Err(Error::NotSsh { .. })));
+    }
+    assert!(matches!(
+        parse("https://host.xz/repo").to_ssh_explicit(),
+        Err(Error::NotSsh { scheme: Scheme::Https })
+    ));
+}
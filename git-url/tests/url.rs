@@ -2,4 +2,6 @@ pub type Error = Box<dyn std::error::Error>;
 pub type Result = std::result::Result<(), Error>;
 
 mod expand_path;
+mod normalize;
 mod parse;
+mod ssh_form;
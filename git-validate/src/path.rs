@@ -0,0 +1,192 @@
+use bstr::{BStr, ByteSlice};
+
+///
+pub mod component {
+    use bstr::BString;
+
+    /// The error returned by [`component()`][super::component()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("A path component must not be empty")]
+        Empty,
+        #[error("A path component must not contain a path separator")]
+        PathSeparator,
+        #[error("A path component must not contain invalid bytes: {byte:?}")]
+        InvalidByte { byte: BString },
+        #[error("A path component must not be '.'")]
+        SingleDot,
+        #[error("A path component must not be '..' as it would refer to the parent directory")]
+        DoubleDot,
+        #[error("A path component must not be named '.git' in any casing as it could be mistaken for a repository")]
+        DotGitDir,
+    }
+}
+
+/// Assure the given `name` is a valid path component, i.e. a file or directory name as stored in tree entries,
+/// which is returned unchanged on success.
+///
+/// Besides empty names and names containing path separators or null bytes, the names `.`, `..` and `.git` are rejected
+/// as writing them to disk could escape the worktree or alter the repository itself. `.git` is matched case-insensitively
+/// as it would alias the actual `.git` directory on case-insensitive filesystems.
+///
+/// On Windows, names containing `\`, `:`, `*`, `?`, `"`, `<`, `>` or `|` are rejected as well, along with names that
+/// Windows treats as equivalent to `.git`, like `git~1` or `.git` followed by dots or spaces.
+pub fn component(name: &BStr) -> Result<&BStr, component::Error> {
+    use component::Error;
+    if name.is_empty() {
+        return Err(Error::Empty);
+    }
+    for byte in name.iter() {
+        match byte {
+            b'/' => return Err(Error::PathSeparator),
+            b'\\' if cfg!(windows) => return Err(Error::PathSeparator),
+            b'\0' => {
+                return Err(Error::InvalidByte {
+                    byte: byte_to_bstring(*byte),
+                })
+            }
+            b':' | b'*' | b'?' | b'"' | b'<' | b'>' | b'|' if cfg!(windows) => {
+                return Err(Error::InvalidByte {
+                    byte: byte_to_bstring(*byte),
+                })
+            }
+            _ => {}
+        }
+    }
+    match name.as_bytes() {
+        b"." => return Err(Error::SingleDot),
+        b".." => return Err(Error::DoubleDot),
+        _ => {}
+    }
+    if is_dot_git(name) {
+        return Err(Error::DotGitDir);
+    }
+    Ok(name)
+}
+
+fn byte_to_bstring(byte: u8) -> bstr::BString {
+    (&[byte][..]).into()
+}
+
+fn is_dot_git(name: &BStr) -> bool {
+    let name = if cfg!(windows) {
+        if name.eq_ignore_ascii_case(b"git~1") {
+            return true;
+        }
+        name.trim_end_with(|c| c == '.' || c == ' ').as_bstr()
+    } else {
+        name
+    };
+    name.eq_ignore_ascii_case(b".git")
+}
+
+/// The platform whose filesystem conventions to consider when checking whether paths alias special files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Platform {
+    /// Filesystems without any special treatment of names beyond case-insensitivity, which is assumed to be possible anywhere.
+    Unix,
+    /// NTFS, which ignores trailing dots and spaces, supports alternate data streams and 8.3 short names.
+    Windows,
+    /// HFS+ and APFS, which ignore certain unicode code points in names.
+    MacOs,
+}
+
+impl Platform {
+    /// The platform we are compiled for.
+    pub fn current() -> Self {
+        if cfg!(windows) {
+            Platform::Windows
+        } else if cfg!(target_os = "macos") {
+            Platform::MacOs
+        } else {
+            Platform::Unix
+        }
+    }
+}
+
+/// Return `true` if no component of `path` could be interpreted as `.gitmodules` file on `os`, or `false` if one is
+/// `.gitmodules` or an alias of it that could be used to inject submodule configuration while bypassing checks
+/// for the literal name.
+///
+/// Besides case-insensitive matches, these aliases are detected:
+///
+/// * **Windows**: trailing dots and spaces as in `.gitmodules .`, alternate data streams as in `.gitmodules::$DATA`
+///   and 8.3 short names like `GITMOD~1` or `gi7eba~1`.
+/// * **MacOs**: unicode code points ignored by HFS+, like zero-width joiners, anywhere in the name.
+pub fn is_gitmodules_safe(path: &BStr, os: Platform) -> bool {
+    !path
+        .split(|b| *b == b'/' || (os == Platform::Windows && *b == b'\\'))
+        .any(|component| {
+            let component = component.as_bstr();
+            match os {
+                Platform::Unix => component.eq_ignore_ascii_case(b".gitmodules"),
+                Platform::Windows => is_ntfs_dot_generic(component, "gitmodules", "gi7eba"),
+                Platform::MacOs => is_hfs_dot_generic(component, "gitmodules"),
+            }
+        })
+}
+
+/// Return true if `name` is `.<dotname>` on NTFS, also considering 8.3 short names made from the first 6 characters
+/// of `dotname` or from `fallback_prefix`, which is used by Windows if the former are taken.
+fn is_ntfs_dot_generic(name: &BStr, dotname: &str, fallback_prefix: &str) -> bool {
+    fn only_spaces_and_periods(rest: &[u8]) -> bool {
+        rest.iter()
+            .take_while(|b| **b != b':')
+            .all(|b| *b == b' ' || *b == b'.')
+    }
+    let dotname = dotname.as_bytes();
+    if let Some(rest) = name.strip_prefix(b".") {
+        if rest.len() >= dotname.len() && rest[..dotname.len()].eq_ignore_ascii_case(dotname) {
+            return only_spaces_and_periods(&rest[dotname.len()..]);
+        }
+    }
+
+    if name.len() >= 8
+        && name[..6].eq_ignore_ascii_case(&dotname[..6])
+        && name[6] == b'~'
+        && (b'1'..=b'4').contains(&name[7])
+    {
+        return only_spaces_and_periods(&name[8..]);
+    }
+
+    let fallback_prefix = fallback_prefix.as_bytes();
+    let mut saw_tilde = false;
+    let mut i = 0;
+    while i < 8 {
+        let b = match name.get(i) {
+            Some(b) => *b,
+            None => return false,
+        };
+        if saw_tilde {
+            if !b.is_ascii_digit() {
+                return false;
+            }
+        } else if b == b'~' {
+            i += 1;
+            match name.get(i) {
+                Some(b'1'..=b'9') => saw_tilde = true,
+                _ => return false,
+            }
+        } else if i >= 6 || !b.is_ascii() || b.to_ascii_lowercase() != fallback_prefix[i] {
+            return false;
+        }
+        i += 1;
+    }
+    only_spaces_and_periods(&name[8..])
+}
+
+/// Return true if `name` is `.<dotname>` on HFS+, which ignores certain unicode code points.
+fn is_hfs_dot_generic(name: &BStr, dotname: &str) -> bool {
+    fn is_ignorable(c: char) -> bool {
+        matches!(
+            c,
+            '\u{200c}'..='\u{200f}' | '\u{202a}'..='\u{202e}' | '\u{206a}'..='\u{206f}' | '\u{feff}'
+        )
+    }
+    let mut chars = name.chars().filter(|c| !is_ignorable(*c));
+    std::iter::once('.')
+        .chain(dotname.chars())
+        .all(|expected| chars.next().map_or(false, |c| c.to_ascii_lowercase() == expected))
+        && chars.next().is_none()
+}
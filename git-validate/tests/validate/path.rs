@@ -0,0 +1,153 @@
+mod component {
+    mod valid {
+        use bstr::ByteSlice;
+
+        macro_rules! mktest {
+            ($name:ident, $input:expr) => {
+                #[test]
+                fn $name() {
+                    assert!(git_validate::path::component($input.as_bstr()).is_ok())
+                }
+            };
+        }
+
+        mktest!(ascii, b"ascii-only_and-dashes");
+        mktest!(unicode, "你好吗😅".as_bytes());
+        mktest!(dot_in_the_middle, b"file.ext");
+        mktest!(leading_dot, b".gitignore");
+        mktest!(triple_dot, b"...");
+        mktest!(dot_git_with_suffix, b".git-file");
+        mktest!(git_without_dot, b"git");
+        #[cfg(not(windows))]
+        mktest!(backslash_is_not_a_separator, b"back\\slash");
+        #[cfg(not(windows))]
+        mktest!(colon, b"with:colon");
+        #[cfg(not(windows))]
+        mktest!(windows_only_special_characters, b"*?\"<>|");
+    }
+
+    mod invalid {
+        use bstr::ByteSlice;
+
+        macro_rules! mktest {
+            ($name:ident, $input:expr, $expected:pat) => {
+                #[test]
+                fn $name() {
+                    match git_validate::path::component($input.as_bstr()) {
+                        Err($expected) => {}
+                        got => panic!("Wanted {}, got {:?}", stringify!($expected), got),
+                    }
+                }
+            };
+        }
+
+        use git_validate::path::component::Error;
+
+        mktest!(empty, b"", Error::Empty);
+        mktest!(slash, b"a/b", Error::PathSeparator);
+        mktest!(only_slash, b"/", Error::PathSeparator);
+        mktest!(null_byte, b"a\0b", Error::InvalidByte { .. });
+        mktest!(single_dot, b".", Error::SingleDot);
+        mktest!(double_dot, b"..", Error::DoubleDot);
+        mktest!(dot_git, b".git", Error::DotGitDir);
+        mktest!(dot_git_uppercase, b".GIT", Error::DotGitDir);
+        mktest!(dot_git_mixed_case, b".GiT", Error::DotGitDir);
+        #[cfg(windows)]
+        mktest!(backslash, b"a\\b", Error::PathSeparator);
+        #[cfg(windows)]
+        mktest!(colon, b"c:", Error::InvalidByte { .. });
+        #[cfg(windows)]
+        mktest!(pipe, b"a|b", Error::InvalidByte { .. });
+        #[cfg(windows)]
+        mktest!(dot_git_with_trailing_dots_and_spaces, b".git. .", Error::DotGitDir);
+        #[cfg(windows)]
+        mktest!(dot_git_short_name, b"GIT~1", Error::DotGitDir);
+    }
+}
+
+mod is_gitmodules_safe {
+    use bstr::ByteSlice;
+    use git_validate::path::{is_gitmodules_safe, Platform};
+
+    fn is_safe(path: &str, os: Platform) -> bool {
+        is_gitmodules_safe(path.as_bytes().as_bstr(), os)
+    }
+
+    #[test]
+    fn regular_names_are_safe_everywhere() {
+        for os in [Platform::Unix, Platform::Windows, Platform::MacOs] {
+            for path in [
+                "file",
+                ".gitmodule",
+                ".gitmodulesx",
+                "gitmodules",
+                "dir/.gitattributes",
+                "%2e%67%69%74%6d%6f%64%75%6c%65%73",
+            ] {
+                assert!(is_safe(path, os), "{:?} on {:?}", path, os);
+            }
+        }
+    }
+
+    #[test]
+    fn the_actual_name_in_any_case_and_directory_is_unsafe_everywhere() {
+        for os in [Platform::Unix, Platform::Windows, Platform::MacOs] {
+            for path in [
+                ".gitmodules",
+                ".GITMODULES",
+                ".GitModules",
+                "dir/.gitmodules",
+                ".gitmodules/file",
+            ] {
+                assert!(!is_safe(path, os), "{:?} on {:?}", path, os);
+            }
+        }
+    }
+
+    #[test]
+    fn ntfs_aliases() {
+        for path in [
+            ".gitmodules .",
+            ".gitmodules...",
+            ".gitmodules::$DATA",
+            ".gitmodules . :stream",
+            "GITMOD~1",
+            "gitmod~4",
+            "gitmod~1 . ",
+            "gi7eba~1",
+            "gi7eb~12",
+            "GI7EBA~9",
+            "dir\\.gitmodules",
+        ] {
+            assert!(!is_safe(path, Platform::Windows), "{:?}", path);
+            assert!(is_safe(path, Platform::Unix), "{:?} is just a name on unix", path);
+        }
+        for path in [
+            ".gitmodules x",
+            "gitmod~5",
+            "gitmod~1x",
+            "gi7eba~0",
+            "gi7ebb~1",
+            "gi7eba~1x",
+        ] {
+            assert!(is_safe(path, Platform::Windows), "{:?}", path);
+        }
+    }
+
+    #[test]
+    fn hfs_aliases() {
+        for path in [
+            ".gitmodules\u{200c}",
+            "\u{feff}.gitmodules",
+            ".git\u{200d}modules",
+            ".\u{206a}GitModules",
+        ] {
+            assert!(!is_safe(path, Platform::MacOs), "{:?}", path);
+            assert!(is_safe(path, Platform::Unix), "{:?} is just a name on unix", path);
+        }
+        assert!(
+            is_safe(".gitmodules\u{200b}", Platform::MacOs),
+            "zero-width space isn't ignored"
+        );
+    }
+}
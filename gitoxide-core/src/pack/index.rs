@@ -87,6 +87,10 @@ pub fn from_pack(
         iteration_mode: ctx.iteration_mode.into(),
         index_version: pack::index::Version::default(),
         object_hash: ctx.object_hash,
+        write_reverse_index: false,
+        temp_file_name: None,
+        use_mmap: true,
+        verify_crc_on_write: false,
     };
     let out = ctx.out;
     let format = ctx.format;
@@ -151,7 +151,7 @@ mod blocking_io {
         progress: P,
         ctx: Context<W>,
     ) -> anyhow::Result<()> {
-        let transport = net::connect(url, protocol.unwrap_or_default().into())?;
+        let transport = net::connect(url, protocol.unwrap_or_default().into(), Default::default())?;
         let delegate = CloneDelegate {
             ctx,
             directory,
@@ -219,7 +219,7 @@ mod async_io {
         progress: P,
         ctx: Context<W>,
     ) -> anyhow::Result<()> {
-        let transport = net::connect(url.to_string(), protocol.unwrap_or_default().into()).await?;
+        let transport = net::connect(url.to_string(), protocol.unwrap_or_default().into(), Default::default()).await?;
         let mut delegate = CloneDelegate {
             ctx,
             directory,
@@ -343,10 +343,21 @@ fn receive_pack_blocking<W: io::Write>(
         index_version: pack::index::Version::V2,
         iteration_mode: pack::data::input::Mode::Verify,
         object_hash: ctx.object_hash,
+        write_reverse_index: false,
+        temp_file_name: None,
+        use_mmap: true,
+        verify_crc_on_write: false,
     };
-    let outcome =
-        pack::Bundle::write_to_directory(input, directory.take(), progress, &ctx.should_interrupt, None, options)
-            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    let outcome = pack::Bundle::write_to_directory(
+        input,
+        None,
+        directory.take(),
+        progress,
+        &ctx.should_interrupt,
+        None,
+        options,
+    )
+    .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
 
     if let Some(directory) = refs_directory.take() {
         write_raw_refs(refs, directory)?;
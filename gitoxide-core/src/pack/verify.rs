@@ -253,6 +253,27 @@ fn print_statistics(out: &mut impl io::Write, stats: &index::traverse::Statistic
         "num tags", stats.num_tags,
         width = width
     )?;
+    #[rustfmt::skip]
+    writeln!(
+        out,
+        "\n\t{:<width$}: {}\n\t{:<width$}: {}",
+        "num ofs deltas", stats.num_ofs_deltas,
+        "num ref deltas", stats.num_ref_deltas,
+        width = width
+    )?;
+    if let (Some((largest_id, largest_size)), Some((deepest_id, deepest_length))) =
+        (stats.largest_object, stats.deepest_chain)
+    {
+        writeln!(out, "\noutliers")?;
+        #[rustfmt::skip]
+        writeln!(
+            out,
+            "\t{:<width$}: {} ({})\n\t{:<width$}: {} ({})",
+            "largest object", largest_id, ByteSize(largest_size),
+            "deepest delta chain", deepest_id, deepest_length,
+            width = width
+        )?;
+    }
     let compression_ratio = stats.total_decompressed_entries_size as f64 / stats.total_compressed_entries_size as f64;
     let delta_compression_ratio = stats.total_object_size as f64 / stats.total_compressed_entries_size as f64;
     #[rustfmt::skip]
@@ -6,6 +6,8 @@ use crate::OutputFormat;
 pub struct Options {
     pub format: OutputFormat,
     pub dry_run: bool,
+    /// If set, delete tracking refs that don't exist on the remote anymore
+    pub prune: bool,
     pub remote: Option<String>,
     /// If non-empty, override all ref-specs otherwise configured in the remote
     pub ref_specs: Vec<BString>,
@@ -17,7 +19,11 @@ pub const PROGRESS_RANGE: std::ops::RangeInclusive<u8> = 1..=3;
 pub(crate) mod function {
     use anyhow::bail;
     use git_repository as git;
-    use git_repository::{prelude::ObjectIdExt, refspec::match_group::validate::Fix, remote::fetch::Status};
+    use git_repository::{
+        prelude::{Find, ObjectIdExt},
+        refspec::match_group::validate::Fix,
+        remote::fetch::Status,
+    };
 
     use super::Options;
     use crate::OutputFormat;
@@ -30,6 +36,7 @@ pub(crate) mod function {
         Options {
             format,
             dry_run,
+            prune,
             remote,
             handshake_info,
             ref_specs,
@@ -43,11 +50,14 @@ pub(crate) mod function {
         if !ref_specs.is_empty() {
             remote.replace_refspecs(ref_specs.iter(), git::remote::Direction::Fetch)?;
         }
+        let start = std::time::Instant::now();
         let res: git::remote::fetch::Outcome = remote
             .connect(git::remote::Direction::Fetch, progress)?
             .prepare_fetch(Default::default())?
             .with_dry_run(dry_run)
+            .with_prune(prune)
             .receive(&git::interrupt::IS_INTERRUPTED)?;
+        let elapsed = start.elapsed();
 
         if handshake_info {
             writeln!(out, "Handshake Information")?;
@@ -59,12 +69,39 @@ pub(crate) mod function {
             Status::NoChange => {
                 crate::repository::remote::refs::print_refmap(&repo, ref_specs, res.ref_map, &mut out, err)
             }
-            Status::DryRun { update_refs } => print_updates(&repo, update_refs, ref_specs, res.ref_map, &mut out, err),
+            Status::DryRun { update_refs } => {
+                let num_wants = count_wants(&repo, &res.ref_map);
+                print_updates(&repo, update_refs, ref_specs, res.ref_map, &mut out, err)?;
+                writeln!(out, "would want {} object(s)", num_wants).ok();
+                Ok(())
+            }
             Status::Change {
                 update_refs,
                 write_pack_bundle,
             } => {
                 print_updates(&repo, update_refs, ref_specs, res.ref_map, &mut out, err)?;
+                let pack_size = write_pack_bundle
+                    .data_path
+                    .as_ref()
+                    .and_then(|path| std::fs::metadata(path).ok())
+                    .map(|m| m.len());
+                let elapsed_s = elapsed.as_secs_f64();
+                match pack_size {
+                    Some(pack_size) => writeln!(
+                        out,
+                        "received {} objects, {} in {:.2}s ({}/s)",
+                        write_pack_bundle.index.num_objects,
+                        bytesize::ByteSize(pack_size).to_string_as(true),
+                        elapsed_s,
+                        bytesize::ByteSize((pack_size as f64 / elapsed_s.max(f64::EPSILON)) as u64).to_string_as(true)
+                    ),
+                    None => writeln!(
+                        out,
+                        "received {} objects in {:.2}s",
+                        write_pack_bundle.index.num_objects, elapsed_s
+                    ),
+                }
+                .ok();
                 if let Some(data_path) = write_pack_bundle.data_path {
                     writeln!(out, "pack  file: \"{}\"", data_path.display()).ok();
                 }
@@ -74,12 +111,37 @@ pub(crate) mod function {
                 Ok(())
             }
         }?;
+        print_pruned(&res.pruned, &mut out)?;
         if dry_run {
             writeln!(out, "DRY-RUN: No ref was updated and no pack was received.").ok();
         }
         Ok(())
     }
 
+    fn print_pruned(pruned: &[git::refs::transaction::RefEdit], mut out: impl std::io::Write) -> std::io::Result<()> {
+        if pruned.is_empty() {
+            return Ok(());
+        }
+        writeln!(out, "pruned tracking refs that are gone on the remote")?;
+        for edit in pruned {
+            writeln!(out, "\t{} [deleted]", edit.name)?;
+        }
+        Ok(())
+    }
+
+    /// Count the distinct remote tips that would be requested from the remote as they are not yet present in our object database.
+    fn count_wants(repo: &git::Repository, map: &git::remote::fetch::RefMap) -> usize {
+        let mut wants = map
+            .mappings
+            .iter()
+            .map(|m| m.remote.as_id())
+            .filter(|id| !repo.objects.contains(id))
+            .collect::<Vec<_>>();
+        wants.sort();
+        wants.dedup();
+        wants.len()
+    }
+
     pub(crate) fn print_updates(
         repo: &git::Repository,
         update_refs: git::remote::fetch::refs::update::Outcome,
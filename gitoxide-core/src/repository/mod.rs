@@ -3,8 +3,8 @@ use std::path::PathBuf;
 use anyhow::{Context as AnyhowContext, Result};
 use git_repository as git;
 
-pub fn init(directory: Option<PathBuf>) -> Result<git::discover::repository::Path> {
-    git_repository::create::into(
+pub fn init(directory: Option<PathBuf>) -> Result<git::ThreadSafeRepository> {
+    git::ThreadSafeRepository::init(
         directory.unwrap_or_default(),
         git::create::Options {
             bare: false,
@@ -115,6 +115,7 @@ pub fn main() -> Result<()> {
         #[cfg(feature = "gitoxide-core-blocking-client")]
         Subcommands::Fetch(crate::plumbing::options::fetch::Platform {
             dry_run,
+            prune,
             handshake_info,
             remote,
             ref_spec,
@@ -122,6 +123,7 @@ pub fn main() -> Result<()> {
             let opts = core::repository::fetch::Options {
                 format,
                 dry_run,
+                prune,
                 remote,
                 handshake_info,
                 ref_specs: ref_spec,
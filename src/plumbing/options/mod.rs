@@ -126,6 +126,10 @@ pub mod fetch {
         #[clap(long, short = 'n')]
         pub dry_run: bool,
 
+        /// Delete tracking references that would be written by a ref-spec but don't exist on the remote anymore.
+        #[clap(long, short = 'p')]
+        pub prune: bool,
+
         /// Output additional typically information provided by the server as part of the connection handshake.
         #[clap(long, short = 'H')]
         pub handshake_info: bool,
@@ -151,6 +151,21 @@ title "gix (with repository)"
       )
     )
   )
+
+  title "gix fetch"
+  (when "running 'fetch'"
+    (small-repo-in-sandbox
+      if [[ "$kind" != "small" && "$kind" != "async" ]]; then
+      repo-with-remotes clone origin "$PWD"
+      cd clone
+      (with "a remote which has objects we don't have"
+        it "shows the amount of received objects, the pack size and the throughput" && {
+          expect_run_sh $SUCCESSFULLY "'$exe_plumbing' fetch 2>/dev/null | grep -E '^received [1-9][0-9]* objects, .+ in [0-9]+\.[0-9]{2}s \(.+/s\)$'"
+        }
+      )
+      fi
+    )
+  )
 )
 
 (with "gix free"